@@ -3,7 +3,7 @@ use solana_program::{
 };
 use spl_token::state::Account;
 
-use crate::assert_initialized;
+use super::{is_token_program, unpack_initialized};
 
 pub trait ToTokenAccount {
     fn to_token_account(self) -> Account;
@@ -11,7 +11,7 @@ pub trait ToTokenAccount {
 
 impl ToTokenAccount for AccountInfo<'_> {
     fn to_token_account(self) -> Account {
-        assert_initialized(&self, ProgramError::UninitializedAccount).unwrap()
+        unpack_initialized(&self, ProgramError::UninitializedAccount).unwrap()
     }
 }
 
@@ -21,11 +21,12 @@ impl ToTokenAccount for Account {
     }
 }
 
+/// Asserts that the account is either the SPL Token or the Token-2022 program.
 pub fn assert_token_program_matches_package(
     token_program_info: &AccountInfo,
     error: impl Into<ProgramError>,
 ) -> ProgramResult {
-    if *token_program_info.key != spl_token::id() {
+    if !is_token_program(token_program_info.key) {
         return Err(error.into());
     }
 
@@ -2,6 +2,9 @@ use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
 };
 
+// Token-2022 instruction builders are used since they accept both the SPL Token and
+// Token-2022 program ids; the instruction layouts are the same for both programs.
+
 pub fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> ProgramResult {
     let TokenBurnParams {
         mint,
@@ -16,7 +19,7 @@ pub fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> ProgramResult {
         seeds.push(seed);
     }
     invoke_signed(
-        &spl_token::instruction::burn(
+        &spl_token_2022::instruction::burn(
             token_program.key,
             source.key,
             mint.key,
@@ -58,7 +61,7 @@ pub fn spl_token_close(params: TokenCloseParams<'_, '_>) -> ProgramResult {
         seeds.push(seed);
     }
     invoke_signed(
-        &spl_token::instruction::close_account(
+        &spl_token_2022::instruction::close_account(
             token_program.key,
             account.key,
             destination.key,
@@ -98,7 +101,7 @@ pub fn spl_token_mint_to(params: TokenMintToParams<'_, '_>) -> ProgramResult {
         seeds.push(seed);
     }
     invoke_signed(
-        &spl_token::instruction::mint_to(
+        &spl_token_2022::instruction::mint_to(
             token_program.key,
             mint.key,
             destination.key,
@@ -127,6 +130,9 @@ pub struct TokenMintToParams<'a: 'b, 'b> {
     pub token_program: AccountInfo<'a>,
 }
 
+/// Transfers tokens using `transfer`, which is not supported by Token-2022 mints with transfer
+/// fees; use `spl_token_transfer_checked` for those.
+#[allow(deprecated)]
 pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult {
     let TokenTransferParams {
         mint: _,
@@ -142,7 +148,7 @@ pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult
         seeds.push(seed);
     }
     invoke_signed(
-        &spl_token::instruction::transfer(
+        &spl_token_2022::instruction::transfer(
             token_program.key,
             source.key,
             destination.key,
@@ -173,3 +179,38 @@ pub struct TokenTransferParams<'a: 'b, 'b> {
     /// token_program
     pub token_program: AccountInfo<'a>,
 }
+
+/// Transfers tokens using `transfer_checked`, which is required by Token-2022 mints with
+/// extensions (e.g., transfer fees). The mint account is included in the CPI.
+pub fn spl_token_transfer_checked(
+    params: TokenTransferParams<'_, '_>,
+    decimals: u8,
+) -> ProgramResult {
+    let TokenTransferParams {
+        mint,
+        source,
+        destination,
+        amount,
+        authority,
+        token_program,
+        authority_signer_seeds,
+    } = params;
+    let mut seeds: Vec<&[&[u8]]> = vec![];
+    if let Some(seed) = authority_signer_seeds {
+        seeds.push(seed);
+    }
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[source, mint, destination, authority],
+        seeds.as_slice(),
+    )
+}
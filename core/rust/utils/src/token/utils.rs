@@ -1,7 +1,60 @@
 use arrayref::{array_ref, array_refs};
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_option::COption, pubkey::Pubkey,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
 };
+use spl_token_2022::extension::AccountType;
+
+/// Returns whether the key is the SPL Token or the Token-2022 program.
+pub fn is_token_program(key: &Pubkey) -> bool {
+    *key == spl_token::id() || *key == spl_token_2022::id()
+}
+
+/// Returns the base state of a token account or mint owned by either token program.
+///
+/// Token-2022 accounts with extensions store the base state in the first `T::LEN` bytes,
+/// followed by (for mints, after padding to the size of a token account) the account type
+/// and the extension data.
+fn base_state<T: Pack>(data: &[u8]) -> Result<&[u8], ProgramError> {
+    if data.len() <= T::LEN {
+        return Ok(data);
+    }
+
+    let account_type = if T::LEN == spl_token::state::Mint::LEN {
+        AccountType::Mint
+    } else {
+        AccountType::Account
+    };
+
+    if data.get(spl_token::state::Account::LEN) != Some(&(account_type as u8)) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(&data[..T::LEN])
+}
+
+/// Unpacks a token account or mint, ignoring any Token-2022 extension data.
+pub fn unpack<T: Pack + IsInitialized>(data: &[u8]) -> Result<T, ProgramError> {
+    T::unpack(base_state::<T>(data)?)
+}
+
+/// Unpacks a token account or mint, ignoring any Token-2022 extension data, and returns
+/// the given error if it is not initialized.
+pub fn unpack_initialized<T: Pack + IsInitialized>(
+    account_info: &AccountInfo,
+    error: impl Into<ProgramError>,
+) -> Result<T, ProgramError> {
+    let account: T = T::unpack_unchecked(base_state::<T>(&account_info.data.borrow())?)?;
+
+    if !account.is_initialized() {
+        Err(error.into())
+    } else {
+        Ok(account)
+    }
+}
 
 /// Unpacks COption from a slice, taken from token program
 fn unpack_coption_key(src: &[u8; 36]) -> Result<COption<Pubkey>, ProgramError> {
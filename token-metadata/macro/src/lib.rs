@@ -192,7 +192,9 @@ pub fn account_context_derive(input: TokenStream) -> TokenStream {
 /// The struct will contain all shank annotated accounts and the impl block
 /// will initialize them using the accounts iterators. It support the use of
 /// optional accounts, which would generate an account field with an
-/// `Option<AccountInfo<'a>>` type.
+/// `Option<AccountInfo<'a>>` type. Optional accounts at the end of the list
+/// (i.e., not followed by a required account) can also be omitted, so accounts
+/// can be appended to an instruction without breaking existing clients.
 ///
 /// ```ignore
 /// pub struct MyAccount<'a> {
@@ -236,9 +238,19 @@ fn generate_accounts(variants: &[Variant]) -> TokenStream {
             }
         });
         // accounts initialization for the impl block
-        let impl_fields = variant.accounts.iter().map(|account| {
+        // index of the first account of the trailing optional accounts
+        let trailing_index = variant
+            .accounts
+            .iter()
+            .rposition(|account| !account.optional)
+            .map_or(0, |index| index + 1);
+        let impl_fields = variant.accounts.iter().enumerate().map(|(index, account)| {
             let account_name = syn::parse_str::<syn::Ident>(format!("{}_info", &account.name).as_str()).unwrap();
-            if account.optional {
+            if account.optional && index >= trailing_index {
+                quote! {
+                    let #account_name = crate::processor::next_trailing_optional_account_info(account_info_iter);
+                }
+            } else if account.optional {
                 quote! {
                     let #account_name = crate::processor::next_optional_account_info(account_info_iter)?;
                 }
@@ -22,12 +22,15 @@ pub fn assert_edition_is_not_mint_authority(mint_account_info: &AccountInfo) ->
     Ok(())
 }
 
-/// Checks that the `master_edition` is not a pNFT master edition.
+/// Checks that the `master_edition` is not a programmable asset master edition.
 pub fn assert_edition_is_not_programmable(master_edition_info: &AccountInfo) -> ProgramResult {
     let edition_data = master_edition_info.data.borrow();
 
     if edition_data.len() > TOKEN_STANDARD_INDEX
-        && edition_data[TOKEN_STANDARD_INDEX] == TokenStandard::ProgrammableNonFungible as u8
+        && (edition_data[TOKEN_STANDARD_INDEX] == TokenStandard::ProgrammableNonFungible as u8
+            || edition_data[TOKEN_STANDARD_INDEX] == TokenStandard::ProgrammableFungibleAsset as u8
+            || edition_data[TOKEN_STANDARD_INDEX]
+                == TokenStandard::ProgrammableNonTransferable as u8)
     {
         return Err(MetadataError::InvalidTokenStandard.into());
     }
@@ -7,7 +7,7 @@ use solana_program::{
 use spl_token::state::Account;
 
 use crate::{
-    assertions::{assert_initialized, assert_owned_by},
+    assertions::{assert_initialized, assert_owned_by, assert_owned_by_token_program},
     error::MetadataError,
     pda::PREFIX,
     state::{
@@ -212,11 +212,11 @@ pub fn assert_holding_amount(
     amount: u64,
 ) -> ProgramResult {
     assert_owned_by(metadata_info, program_id)?;
-    assert_owned_by(mint_info, &spl_token::ID)?;
+    assert_owned_by_token_program(mint_info)?;
 
     let token_account: Account = assert_initialized(token_account_info)?;
 
-    assert_owned_by(token_account_info, &spl_token::ID)?;
+    assert_owned_by(token_account_info, mint_info.owner)?;
 
     if token_account.owner != *owner_info.key {
         return Err(MetadataError::InvalidOwner.into());
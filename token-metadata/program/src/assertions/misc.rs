@@ -35,11 +35,11 @@ pub fn assert_keys_equal_with_error(
     }
 }
 
-/// assert initialized account
+/// assert initialized account (token accounts and mints of either token program)
 pub fn assert_initialized<T: Pack + IsInitialized>(
     account_info: &AccountInfo,
 ) -> Result<T, ProgramError> {
-    mpl_utils::assert_initialized(account_info, MetadataError::Uninitialized)
+    mpl_utils::token::unpack_initialized(account_info, MetadataError::Uninitialized)
 }
 
 pub fn assert_mint_authority_matches_mint(
@@ -86,11 +86,11 @@ pub fn assert_delegated_tokens(
     mint_info: &AccountInfo,
     token_account_info: &AccountInfo,
 ) -> ProgramResult {
-    assert_owned_by(mint_info, &spl_token::ID)?;
+    assert_owned_by_token_program(mint_info)?;
 
     let token_account: Account = assert_initialized(token_account_info)?;
 
-    assert_owned_by(token_account_info, &spl_token::ID)?;
+    assert_owned_by(token_account_info, mint_info.owner)?;
 
     if token_account.mint != *mint_info.key {
         return Err(MetadataError::MintMismatch.into());
@@ -121,6 +121,16 @@ pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
     mpl_utils::assert_owned_by(account, owner, MetadataError::IncorrectOwner)
 }
 
+/// Asserts that the account is owned by the SPL Token or the Token-2022 program.
+pub fn assert_owned_by_token_program(account: &AccountInfo) -> ProgramResult {
+    if !mpl_utils::token::is_token_program(account.owner) {
+        return Err(MetadataError::IncorrectOwner.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is the SPL Token or the Token-2022 program.
 pub fn assert_token_program_matches_package(token_program_info: &AccountInfo) -> ProgramResult {
     mpl_utils::token::assert_token_program_matches_package(
         token_program_info,
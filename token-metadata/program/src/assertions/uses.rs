@@ -18,6 +18,9 @@ pub fn assert_valid_use(
         if i.use_method == UseMethod::Multiple && (i.total < 2 || i.total < i.remaining) {
             return Err(MetadataError::InvalidUseMethod.into());
         }
+        if i.use_method == UseMethod::Refill && (i.total == 0 || i.total < i.remaining) {
+            return Err(MetadataError::InvalidUseMethod.into());
+        }
     }
     match (incoming_use, current_use) {
         (Some(incoming), Some(current)) => {
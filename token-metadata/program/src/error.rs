@@ -759,6 +759,146 @@ pub enum MetadataError {
     /// 192
     #[error("")]
     InvalidMetadataFlags,
+
+    /// 193
+    #[error("Batch item is not under the scope of the authority")]
+    InvalidBatchUpdateItem,
+
+    /// 194
+    #[error("Delegate has expired")]
+    DelegateExpired,
+
+    /// 195
+    #[error("Delegate has no remaining uses")]
+    DelegateUsesExhausted,
+
+    /// 196
+    #[error("Invalid attribute key")]
+    InvalidAttributeKey,
+
+    /// 197
+    #[error("Attribute value too long")]
+    AttributeValueTooLong,
+
+    /// 198
+    #[error("Maximum number of attributes exceeded")]
+    TooManyAttributes,
+
+    /// 199
+    #[error("Duplicate attribute key")]
+    DuplicateAttributeKey,
+
+    /// 200
+    #[error("Attribute not found")]
+    AttributeNotFound,
+
+    /// 201
+    #[error("Attribute is frozen and cannot be changed")]
+    AttributeFrozen,
+
+    /// 202
+    #[error("Edition number has not been printed")]
+    EditionNumberNotPrinted,
+
+    /// 203
+    #[error("Print edition has not been burned or was already reprinted")]
+    PrintEditionNotBurned,
+
+    /// 204
+    #[error("Collection hierarchy exceeds the maximum depth")]
+    CollectionDepthExceeded,
+
+    /// 205
+    #[error("Account is not the metadata of the verified parent collection")]
+    InvalidCollectionAncestor,
+
+    /// 206
+    #[error("Collection ancestors must include the whole chain of verified parents")]
+    IncompleteCollectionChain,
+
+    /// 207
+    #[error("Verified creators whose share decreases must sign")]
+    MissingCreatorSignature,
+
+    /// 208
+    #[error("Rented tokens cannot be transferred, burned or revoked by the holder")]
+    TokenIsRented,
+
+    /// 209
+    #[error("Rental has not expired")]
+    RentalNotExpired,
+
+    /// 210
+    #[error("Rental has expired")]
+    RentalExpired,
+
+    /// 211
+    #[error("Rented tokens can only be transferred to the borrower or back to the lender")]
+    InvalidRentalDestination,
+
+    /// 212
+    #[error("An asset cannot be transferred into its own escrow")]
+    EscrowCannotHoldItself,
+
+    /// 213
+    #[error("The token holder must be a verified creator")]
+    HolderNotVerifiedCreator,
+
+    /// 214
+    #[error("The token holder is not allowed to migrate the asset")]
+    MigrationNotAllowed,
+
+    /// 215
+    #[error("Signer is not the pending update authority")]
+    InvalidPendingUpdateAuthority,
+
+    /// 216
+    #[error("Metadata history capacity must be greater than zero")]
+    InvalidMetadataHistoryCapacity,
+
+    /// 217
+    #[error("The minimum interval between uses has not elapsed")]
+    UseCooldownActive,
+
+    /// 218
+    #[error("Use config account is required for the Refill use method")]
+    MissingUseConfig,
+
+    /// 219
+    #[error("Invalid use config")]
+    InvalidUseConfig,
+
+    /// 220
+    #[error("Edition URI template must contain the {{edition}} placeholder")]
+    InvalidEditionUriTemplate,
+
+    /// 221
+    #[error("Non-transferable assets can only be recovered by the update authority and holder")]
+    NonTransferableAsset,
+
+    /// 222
+    #[error("Non-transferable assets do not support prints")]
+    NonTransferablePrintsNotSupported,
+
+    /// 223
+    #[error("Edition number is reserved")]
+    EditionNumberReserved,
+
+    /// 224
+    #[error("Invalid edition numbering")]
+    InvalidEditionNumbering,
+
+    /// 225
+    #[error("Edition number is not the next edition of the sequence")]
+    EditionNumberOutOfSequence,
+
+    /// 226
+    #[error("No edition number available on the edition marker")]
+    EditionNumberUnavailable,
+
+    /// 227
+    #[error("Lender account must be writable to receive the rent of the token record")]
+    LenderNotWritable,
 }
 
 impl PrintProgramError for MetadataError {
@@ -0,0 +1,171 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+use solana_program::instruction::{AccountMeta, Instruction};
+
+use super::{InstructionBuilder, MetadataInstruction};
+use crate::state::Attribute;
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum CreateAttributesArgs {
+    V1 {
+        /// Initial list of attributes.
+        attributes: Vec<Attribute>,
+        /// Keys (from the initial list) that cannot be changed.
+        frozen_keys: Vec<String>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum UpdateAttributesArgs {
+    V1 {
+        /// Attributes to add or modify.
+        attributes: Vec<Attribute>,
+        /// Keys to freeze after the attributes are updated.
+        freeze_keys: Vec<String>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum RemoveAttributeArgs {
+    V1 {
+        /// Key of the attribute to remove.
+        key: String,
+    },
+}
+
+/// Creates the attributes account of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Attributes account (pda of ['metadata', program id, mint id, 'attributes'])
+///   1. `[]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer]` Update authority or attributes delegate
+///   4. `[optional, writable]` Delegate record PDA
+///   5. `[signer, writable]` Payer
+///   6. `[]` System program
+impl InstructionBuilder for super::builders::CreateAttributes {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.attributes, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new_readonly(self.authority, true),
+                if let Some(delegate_record) = self.delegate_record {
+                    AccountMeta::new(delegate_record, false)
+                } else {
+                    AccountMeta::new_readonly(crate::ID, false)
+                },
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ],
+            data: MetadataInstruction::CreateAttributes(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Adds, modifies or freezes attributes of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Attributes account
+///   1. `[]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer]` Update authority or attributes delegate
+///   4. `[optional, writable]` Delegate record PDA
+///   5. `[signer, writable]` Payer
+///   6. `[]` System program
+impl InstructionBuilder for super::builders::UpdateAttributes {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.attributes, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new_readonly(self.authority, true),
+                if let Some(delegate_record) = self.delegate_record {
+                    AccountMeta::new(delegate_record, false)
+                } else {
+                    AccountMeta::new_readonly(crate::ID, false)
+                },
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ],
+            data: MetadataInstruction::UpdateAttributes(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Removes an attribute of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Attributes account
+///   1. `[]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer]` Update authority or attributes delegate
+///   4. `[optional, writable]` Delegate record PDA
+///   5. `[signer, writable]` Payer
+///   6. `[]` System program
+impl InstructionBuilder for super::builders::RemoveAttribute {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.attributes, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new_readonly(self.authority, true),
+                if let Some(delegate_record) = self.delegate_record {
+                    AccountMeta::new(delegate_record, false)
+                } else {
+                    AccountMeta::new_readonly(crate::ID, false)
+                },
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ],
+            data: MetadataInstruction::RemoveAttribute(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Closes the attributes account of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Attributes account
+///   1. `[]` Metadata account
+///   2. `[]` Mint account
+///   3. `[signer]` Update authority
+///   4. `[signer, writable]` Payer
+impl InstructionBuilder for super::builders::CloseAttributes {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.attributes, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.payer, true),
+            ],
+            data: MetadataInstruction::CloseAttributes.try_to_vec().unwrap(),
+        }
+    }
+}
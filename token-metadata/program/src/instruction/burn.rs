@@ -69,6 +69,9 @@ pub fn burn_edition_nft(
 /// 4. `[writable]` NFT edition account
 /// 5. `[]` SPL Token program.
 /// 6. Optional `[writable]` Collection metadata account
+/// 7. Optional `[writable]` Metadata account of the parent collection of the collection
+/// 8. Optional `[writable]` Metadata account of the parent of the parent collection
+/// 9. Optional `[writable]` Metadata account of the parent of the grandparent collection
 pub fn burn_nft(
     program_id: Pubkey,
     metadata: Pubkey,
@@ -128,9 +131,14 @@ pub enum BurnArgs {
 ///  11.   `[]` System program
 ///  12.   `[]` Instruction sysvar account
 ///  13.   `[]` SPL Token Program
+///  14.   `[optional]` Token Authorization Rules Program
+///  15.   `[optional]` Token Authorization Rules account
+///  16.   `[optional, writable]` Metadata account of the parent collection of the collection
+///  17.   `[optional, writable]` Metadata account of the parent of the parent collection
+///  18.   `[optional, writable]` Metadata account of the parent of the grandparent collection
 impl InstructionBuilder for super::builders::Burn {
     fn instruction(&self) -> solana_program::instruction::Instruction {
-        let accounts = vec![
+        let mut accounts = vec![
             AccountMeta::new(self.authority, true),
             if let Some(collection_metadata) = self.collection_metadata {
                 AccountMeta::new(collection_metadata, false)
@@ -166,6 +174,35 @@ impl InstructionBuilder for super::builders::Burn {
             AccountMeta::new_readonly(self.sysvar_instructions, false),
             AccountMeta::new_readonly(self.spl_token_program, false),
         ];
+        // Optional authorization rules accounts
+        if let Some(rules) = &self.authorization_rules {
+            accounts.push(AccountMeta::new_readonly(
+                self.authorization_rules_program.unwrap_or(crate::ID),
+                false,
+            ));
+            accounts.push(AccountMeta::new_readonly(*rules, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+        }
+        // Optional parent collections accounts
+        accounts.extend([
+            if let Some(parent_collection) = self.parent_collection {
+                AccountMeta::new(parent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(grandparent_collection) = self.grandparent_collection {
+                AccountMeta::new(grandparent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(great_grandparent_collection) = self.great_grandparent_collection {
+                AccountMeta::new(great_grandparent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+        ]);
 
         Instruction {
             program_id: crate::ID,
@@ -148,9 +148,16 @@ pub fn set_and_verify_collection(
 ///   1. `[signer]` Collection Update authority
 ///   2. `[signer]` payer
 ///   3. `[] Update Authority of Collection NFT and NFT
-///   3. `[]` Mint of the Collection
-///   4. `[writable]` Metadata Account of the Collection
-///   5. `[]` MasterEdition2 Account of the Collection Token
+///   4. `[]` Mint of the Collection
+///   5. `[writable]` Metadata Account of the Collection
+///   6. `[]` MasterEdition2 Account of the Collection Token
+///   7. Optional `[]` Collection Authority Record PDA
+///   8. Optional `[writable]` Metadata account of the parent collection of the collection
+///   9. Optional `[writable]` Metadata account of the parent of the parent collection
+///  10. Optional `[writable]` Metadata account of the parent of the grandparent collection
+///
+/// The parent collections accounts are appended to the instruction to propagate the size
+/// change to the parent collections, with the program id set for the accounts not used.
 #[allow(clippy::too_many_arguments)]
 pub fn set_and_verify_sized_collection_item(
     program_id: Pubkey,
@@ -281,6 +288,13 @@ pub fn unverify_collection(
 ///   3. `[]` Mint of the Collection
 ///   4. `[writable]` Metadata Account of the Collection
 ///   5. `[]` MasterEdition2 Account of the Collection Token
+///   6. Optional `[]` Collection Authority Record PDA
+///   7. Optional `[writable]` Metadata account of the parent collection of the collection
+///   8. Optional `[writable]` Metadata account of the parent of the parent collection
+///   9. Optional `[writable]` Metadata account of the parent of the grandparent collection
+///
+/// The parent collections accounts are appended to the instruction to propagate the size
+/// change to the parent collections, with the program id set for the accounts not used.
 #[allow(clippy::too_many_arguments)]
 pub fn unverify_sized_collection_item(
     program_id: Pubkey,
@@ -375,6 +389,13 @@ pub fn verify_collection(
 ///   3. `[]` Mint of the Collection
 ///   4. `[writable]` Metadata Account of the Collection
 ///   5. `[]` MasterEdition2 Account of the Collection Token
+///   6. Optional `[]` Collection Authority Record PDA
+///   7. Optional `[writable]` Metadata account of the parent collection of the collection
+///   8. Optional `[writable]` Metadata account of the parent of the parent collection
+///   9. Optional `[writable]` Metadata account of the parent of the grandparent collection
+///
+/// The parent collections accounts are appended to the instruction to propagate the size
+/// change to the parent collections, with the program id set for the accounts not used.
 #[allow(clippy::too_many_arguments)]
 pub fn verify_sized_collection_item(
     program_id: Pubkey,
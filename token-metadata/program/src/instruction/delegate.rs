@@ -74,6 +74,31 @@ pub enum DelegateArgs {
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
     },
+    AttributesV1 {
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+    },
+    CollectionLockV1 {
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+    },
+    RentalV1 {
+        amount: u64,
+        /// Unix timestamp when the rental ends.
+        expiry: i64,
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+    },
+    LimitedMetadataV1 {
+        /// Role of the metadata delegate.
+        role: MetadataDelegateRole,
+        /// Unix timestamp after which the delegate can no longer be used.
+        expires_at: Option<i64>,
+        /// Maximum number of times the delegate can be used.
+        max_uses: Option<u64>,
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+    },
 }
 
 #[repr(C)]
@@ -94,6 +119,9 @@ pub enum RevokeArgs {
     DataItemV1,
     CollectionItemV1,
     ProgrammableConfigItemV1,
+    AttributesV1,
+    RentalV1,
+    CollectionLockV1,
 }
 
 #[repr(C)]
@@ -108,6 +136,8 @@ pub enum MetadataDelegateRole {
     DataItem,
     CollectionItem,
     ProgrammableConfigItem,
+    Attributes,
+    CollectionLock,
 }
 
 impl fmt::Display for MetadataDelegateRole {
@@ -121,6 +151,8 @@ impl fmt::Display for MetadataDelegateRole {
             Self::DataItem => "data_item_delegate".to_string(),
             Self::CollectionItem => "collection_item_delegate".to_string(),
             Self::ProgrammableConfigItem => "prog_config_item_delegate".to_string(),
+            Self::Attributes => "attributes_delegate".to_string(),
+            Self::CollectionLock => "collection_lock_delegate".to_string(),
         };
 
         write!(f, "{message}")
@@ -7,9 +7,13 @@ use solana_program::{
     system_program,
 };
 
+use super::InstructionBuilder;
 use crate::{
     instruction::MetadataInstruction,
-    state::{EDITION, EDITION_MARKER_BIT_SIZE, PREFIX},
+    state::{
+        EditionNumberingMode, ReservedRange, EDITION, EDITION_MARKER_BIT_SIZE,
+        EDITION_URI_TEMPLATE_SEED, PREFIX, REPRINT_RECORD_SEED,
+    },
 };
 
 /// Converts a master edition v1 to v2
@@ -109,6 +113,15 @@ pub fn mint_new_edition_from_master_edition_via_token(
         ],
         &program_id,
     );
+    let (edition_uri_template, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_mint.as_ref(),
+            EDITION_URI_TEMPLATE_SEED.as_bytes(),
+        ],
+        &program_id,
+    );
 
     let accounts = vec![
         AccountMeta::new(new_metadata, false),
@@ -124,6 +137,7 @@ pub fn mint_new_edition_from_master_edition_via_token(
         AccountMeta::new_readonly(metadata, false),
         AccountMeta::new_readonly(spl_token::ID, false),
         AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(edition_uri_template, false),
     ];
 
     Instruction {
@@ -136,3 +150,205 @@ pub fn mint_new_edition_from_master_edition_via_token(
         .unwrap(),
     }
 }
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ReprintEditionArgs {
+    /// Number of the burned edition to reprint.
+    pub edition: u64,
+}
+
+/// creates a reprint_edition instruction
+#[allow(clippy::too_many_arguments)]
+pub fn reprint_edition(
+    program_id: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    master_edition: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    token_account_owner: Pubkey,
+    token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    edition: u64,
+) -> Instruction {
+    let edition_number = edition.checked_div(EDITION_MARKER_BIT_SIZE).unwrap();
+    let as_string = edition_number.to_string();
+    let (edition_mark_pda, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_mint.as_ref(),
+            EDITION.as_bytes(),
+            as_string.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (reprint_record, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_mint.as_ref(),
+            EDITION.as_bytes(),
+            edition.to_string().as_bytes(),
+            REPRINT_RECORD_SEED.as_bytes(),
+        ],
+        &program_id,
+    );
+    let (edition_uri_template, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_mint.as_ref(),
+            EDITION_URI_TEMPLATE_SEED.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    let accounts = vec![
+        AccountMeta::new(new_metadata, false),
+        AccountMeta::new(new_edition, false),
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new(new_mint, false),
+        AccountMeta::new_readonly(edition_mark_pda, false),
+        AccountMeta::new_readonly(new_mint_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(token_account_owner, true),
+        AccountMeta::new_readonly(token_account, false),
+        AccountMeta::new_readonly(new_metadata_update_authority, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new(reprint_record, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(edition_uri_template, false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::ReprintEdition(ReprintEditionArgs { edition })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetEditionUriTemplateArgs {
+    V1 {
+        /// URI template of the print editions, with an `{edition}` placeholder.
+        uri_template: String,
+    },
+}
+
+/// Creates or updates the edition URI template of a master edition.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Edition URI template account
+///   1. `[]` Master edition account
+///   2. `[]` Master metadata account
+///   3. `[signer]` Update authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System program
+impl InstructionBuilder for super::builders::SetEditionUriTemplate {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.edition_uri_template, false),
+                AccountMeta::new_readonly(self.master_edition, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ],
+            data: MetadataInstruction::SetEditionUriTemplate(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetEditionUriArgs {
+    V1 {
+        /// New URI of the print edition.
+        uri: String,
+    },
+}
+
+/// Sets the URI of a print edition.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Print edition metadata account
+///   1. `[]` Print edition account
+///   2. `[]` Master edition account
+///   3. `[]` Master metadata account
+///   4. `[signer]` Update authority of the master edition
+///   5. `[optional, writable]` Metadata history account of the print edition
+impl InstructionBuilder for super::builders::SetEditionUri {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.metadata, false),
+                AccountMeta::new_readonly(self.edition, false),
+                AccountMeta::new_readonly(self.master_edition, false),
+                AccountMeta::new_readonly(self.master_metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+                if let Some(metadata_history) = self.metadata_history {
+                    AccountMeta::new(metadata_history, false)
+                } else {
+                    AccountMeta::new_readonly(crate::ID, false)
+                },
+            ],
+            data: MetadataInstruction::SetEditionUri(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetEditionNumberingArgs {
+    V1 {
+        /// How edition numbers are assigned.
+        mode: EditionNumberingMode,
+        /// Edition numbers (inclusive ranges) reserved to the update authority.
+        reserved_ranges: Vec<ReservedRange>,
+    },
+}
+
+/// Sets the numbering policy of the print editions of a master edition.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Master edition account
+///   1. `[]` Master metadata account
+///   2. `[signer]` Update authority
+impl InstructionBuilder for super::builders::SetEditionNumbering {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.master_edition, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+            ],
+            data: MetadataInstruction::SetEditionNumbering(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
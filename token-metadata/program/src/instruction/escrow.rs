@@ -7,7 +7,12 @@ use solana_program::{
     system_program, sysvar,
 };
 
-use crate::instruction::MetadataInstruction;
+use crate::{
+    instruction::{
+        builders::EscrowTransferOutBuilder, InstructionBuilder, MetadataInstruction, TransferArgs,
+    },
+    pda::{find_metadata_account, find_token_record_account},
+};
 
 pub fn close_escrow_account(
     program_id: Pubkey,
@@ -75,6 +80,91 @@ pub fn create_escrow_account(
     }
 }
 
+/// Offset of the owner in an SPL Token account.
+///
+/// The holdings of an escrow are listed with a `memcmp` filter of the escrow address at
+/// this offset (and a data size of `spl_token::state::Account::LEN`) on get program
+/// account calls to the SPL Token program.
+pub const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+
+/// A token held by an escrow.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct EscrowHolding {
+    /// Token account owned by the escrow.
+    pub token: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Edition of the token, required for non-fungible and programmable assets.
+    pub edition: Option<Pubkey>,
+    /// Whether the token is a programmable asset, which requires its token records.
+    pub programmable: bool,
+    pub authorization_rules: Option<Pubkey>,
+}
+
+/// Creates the instructions to transfer all holdings of an escrow to the destination owner,
+/// which must be done before closing the escrow account. Holdings are transferred to the
+/// associated token account of the destination owner.
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_escrow(
+    escrow: Pubkey,
+    escrow_mint: Pubkey,
+    escrow_token: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    destination_owner: Pubkey,
+    holdings: &[EscrowHolding],
+) -> Vec<Instruction> {
+    holdings
+        .iter()
+        .filter(|holding| holding.amount > 0)
+        .map(|holding| {
+            let destination = spl_associated_token_account::get_associated_token_address(
+                &destination_owner,
+                &holding.mint,
+            );
+
+            let mut builder = EscrowTransferOutBuilder::new();
+            builder
+                .escrow(escrow)
+                .escrow_mint(escrow_mint)
+                .escrow_token(escrow_token)
+                .token(holding.token)
+                .destination(destination)
+                .destination_owner(destination_owner)
+                .mint(holding.mint)
+                .metadata(find_metadata_account(&holding.mint).0)
+                .authority(authority)
+                .payer(payer);
+
+            if let Some(edition) = holding.edition {
+                builder.edition(edition);
+            }
+
+            if holding.programmable {
+                builder
+                    .owner_token_record(find_token_record_account(&holding.mint, &holding.token).0)
+                    .destination_token_record(
+                        find_token_record_account(&holding.mint, &destination).0,
+                    );
+            }
+
+            if let Some(authorization_rules) = holding.authorization_rules {
+                builder
+                    .authorization_rules_program(mpl_token_auth_rules::ID)
+                    .authorization_rules(authorization_rules);
+            }
+
+            builder
+                .build(TransferArgs::V1 {
+                    amount: holding.amount,
+                    authorization_data: None,
+                })
+                .unwrap()
+                .instruction()
+        })
+        .collect()
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
@@ -124,3 +214,149 @@ pub fn transfer_out_of_escrow(
         data,
     }
 }
+
+/// Transfers an asset into the escrow of another asset.
+///
+/// # Accounts:
+///
+///   0. `[]` Token owned escrow account (destination token account owner)
+///   1. `[]` Mint account that the escrow is attached
+///   2. `[writable]` Token account
+///   3. `[]` Token account owner
+///   4. `[writable]` Destination token account (owned by the escrow)
+///   5. `[]` Mint of token asset
+///   6. `[writable]` Metadata account
+///   7. `[optional]` Edition of token asset
+///   8. `[optional, writable]` Owner token record account
+///   9. `[optional, writable]` Destination token record account
+///   10. `[signer]` Transfer authority (token owner or delegate)
+///   11. `[signer, writable]` Payer
+///   12. `[]` System Program
+///   13. `[]` Instructions sysvar account
+///   14. `[]` SPL Token Program
+///   15. `[]` SPL Associated Token Account program
+///   16. `[optional]` Token Authorization Rules Program
+///   17. `[optional]` Token Authorization Rules account
+impl InstructionBuilder for super::builders::EscrowTransferIn {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.escrow, false),
+            AccountMeta::new_readonly(self.escrow_mint, false),
+            AccountMeta::new(self.token, false),
+            AccountMeta::new_readonly(self.token_owner, false),
+            AccountMeta::new(self.destination, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.edition.unwrap_or(crate::ID), false),
+            if let Some(owner_token_record) = self.owner_token_record {
+                AccountMeta::new(owner_token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(destination_token_record) = self.destination_token_record {
+                AccountMeta::new(destination_token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program, false),
+            AccountMeta::new_readonly(self.spl_ata_program, false),
+        ];
+        // Optional authorization rules accounts
+        if let Some(rules) = &self.authorization_rules {
+            accounts.push(AccountMeta::new_readonly(
+                self.authorization_rules_program.unwrap_or(crate::ID),
+                false,
+            ));
+            accounts.push(AccountMeta::new_readonly(*rules, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+        }
+
+        Instruction {
+            program_id: crate::ID,
+            accounts,
+            data: MetadataInstruction::EscrowTransferIn(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Transfers an asset out of the escrow of another asset.
+///
+/// # Accounts:
+///
+///   0. `[]` Token owned escrow account (source token account owner)
+///   1. `[]` Mint account that the escrow is attached
+///   2. `[]` Token account that holds the token the escrow is attached to
+///   3. `[writable]` Token account (owned by the escrow)
+///   4. `[writable]` Destination token account
+///   5. `[]` Destination token account owner
+///   6. `[]` Mint of token asset
+///   7. `[writable]` Metadata account
+///   8. `[optional]` Edition of token asset
+///   9. `[optional, writable]` Owner token record account
+///   10. `[optional, writable]` Destination token record account
+///   11. `[signer]` Authority/creator of the escrow account
+///   12. `[signer, writable]` Payer
+///   13. `[]` System Program
+///   14. `[]` Instructions sysvar account
+///   15. `[]` SPL Token Program
+///   16. `[]` SPL Associated Token Account program
+///   17. `[optional]` Token Authorization Rules Program
+///   18. `[optional]` Token Authorization Rules account
+impl InstructionBuilder for super::builders::EscrowTransferOut {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.escrow, false),
+            AccountMeta::new_readonly(self.escrow_mint, false),
+            AccountMeta::new_readonly(self.escrow_token, false),
+            AccountMeta::new(self.token, false),
+            AccountMeta::new(self.destination, false),
+            AccountMeta::new_readonly(self.destination_owner, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.edition.unwrap_or(crate::ID), false),
+            if let Some(owner_token_record) = self.owner_token_record {
+                AccountMeta::new(owner_token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(destination_token_record) = self.destination_token_record {
+                AccountMeta::new(destination_token_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program, false),
+            AccountMeta::new_readonly(self.spl_ata_program, false),
+        ];
+        // Optional authorization rules accounts
+        if let Some(rules) = &self.authorization_rules {
+            accounts.push(AccountMeta::new_readonly(
+                self.authorization_rules_program.unwrap_or(crate::ID),
+                false,
+            ));
+            accounts.push(AccountMeta::new_readonly(*rules, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+        }
+
+        Instruction {
+            program_id: crate::ID,
+            accounts,
+            data: MetadataInstruction::EscrowTransferOut(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
@@ -16,7 +16,7 @@ use crate::{
     processor::AuthorizationData,
     state::{
         AssetData, Collection, CollectionDetails, Creator, Data, DataV2, PrintSupply,
-        TokenStandard, Uses,
+        RoyaltyConfig, TokenStandard, Uses,
     },
 };
 
@@ -70,6 +70,17 @@ pub enum TransferArgs {
     },
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum UpdateCreatorsArgs {
+    V1 {
+        /// New list of creators. The `verified` flag of each creator must match
+        /// its current value.
+        creators: Vec<Creator>,
+    },
+}
+
 /// Struct representing the values to be updated for an `update` instructions.
 ///
 /// Values that are set to `None` are not changed.  Any value set to `Some(...)` will
@@ -175,6 +186,13 @@ pub enum UpdateArgs {
         /// Required authorization data to validate the request.
         authorization_data: Option<AuthorizationData>,
     },
+    BatchV1 {
+        /// List of items to update, where the first value is the index of the metadata
+        /// account in the remaining accounts and the second value is the data to update.
+        items: Vec<(u8, PartialData)>,
+        /// Required authorization data to validate the request.
+        authorization_data: Option<AuthorizationData>,
+    },
 }
 
 impl UpdateArgs {
@@ -258,6 +276,54 @@ impl UpdateArgs {
             authorization_data: None,
         }
     }
+
+    pub fn default_batch() -> Self {
+        Self::BatchV1 {
+            items: Vec::new(),
+            authorization_data: None,
+        }
+    }
+}
+
+/// Struct representing the subset of the metadata details that can be updated
+/// by a `BatchV1` update.
+///
+/// Values that are set to `None` are not changed. Creators are never modified by
+/// a batch update.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct PartialData {
+    /// The name of the asset.
+    pub name: Option<String>,
+    /// The symbol for the asset.
+    pub symbol: Option<String>,
+    /// URI pointing to JSON representing the asset.
+    pub uri: Option<String>,
+    /// Royalty basis points that goes to creators in secondary sales (0-10000).
+    pub seller_fee_basis_points: Option<u16>,
+}
+
+impl PartialData {
+    /// Returns a copy of `data` with the values of this struct applied.
+    pub fn merge(&self, data: &Data) -> Data {
+        let mut data = data.clone();
+
+        if let Some(name) = &self.name {
+            data.name = name.clone();
+        }
+        if let Some(symbol) = &self.symbol {
+            data.symbol = symbol.clone();
+        }
+        if let Some(uri) = &self.uri {
+            data.uri = uri.clone();
+        }
+        if let Some(seller_fee_basis_points) = self.seller_fee_basis_points {
+            data.seller_fee_basis_points = seller_fee_basis_points;
+        }
+
+        data
+    }
 }
 
 //-- Toggle implementations
@@ -788,7 +854,7 @@ impl InstructionBuilder for super::builders::Transfer {
 /// # Accounts:
 ///
 ///   0. `[signer]` Update authority or delegate
-///   1. `[optional]` Delegate record PDA
+///   1. `[optional, writable]` Delegate record PDA
 ///   2. `[optional]` Token account
 ///   3. `[]` Mint account
 ///   4. `[writable]` Metadata account
@@ -798,11 +864,16 @@ impl InstructionBuilder for super::builders::Transfer {
 ///   8. `[]` System program
 ///   9. `[optional]` Token Authorization Rules Program
 ///   10. `[optional]` Token Authorization Rules account
+///   11. `[optional, writable]` Metadata history account
 impl InstructionBuilder for super::builders::Update {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let mut accounts = vec![
             AccountMeta::new_readonly(self.authority, true),
-            AccountMeta::new_readonly(self.delegate_record.unwrap_or(crate::ID), false),
+            if let Some(delegate_record) = self.delegate_record {
+                AccountMeta::new(delegate_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
             AccountMeta::new_readonly(self.token.unwrap_or(crate::ID), false),
             AccountMeta::new_readonly(self.mint, false),
             AccountMeta::new(self.metadata, false),
@@ -821,6 +892,12 @@ impl InstructionBuilder for super::builders::Update {
             accounts.push(AccountMeta::new_readonly(crate::ID, false));
         }
 
+        if let Some(metadata_history) = self.metadata_history {
+            accounts.push(AccountMeta::new(metadata_history, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+        }
+
         Instruction {
             program_id: crate::ID,
             accounts,
@@ -830,3 +907,187 @@ impl InstructionBuilder for super::builders::Update {
         }
     }
 }
+
+/// Updates the creators of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Update authority
+///   2. `[optional, writable]` Metadata history account
+///
+/// Verified creators whose share decreases must be added as signer accounts
+/// after the instruction accounts.
+impl InstructionBuilder for super::builders::UpdateCreators {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+                if let Some(metadata_history) = self.metadata_history {
+                    AccountMeta::new(metadata_history, false)
+                } else {
+                    AccountMeta::new_readonly(crate::ID, false)
+                },
+            ],
+            data: MetadataInstruction::UpdateCreators(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Resets the primary sale flag of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Update authority
+///   2. `[]` Token account holding the asset
+impl InstructionBuilder for super::builders::ResetPrimarySaleHappened {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new_readonly(self.token, false),
+            ],
+            data: MetadataInstruction::ResetPrimarySaleHappened
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ProposeUpdateAuthorityArgs {
+    V1 {
+        /// The proposed update authority.
+        #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+        new_update_authority: Pubkey,
+        /// Indicates whether the handover covers the items of the collection.
+        collection: bool,
+    },
+}
+
+/// Proposes a new update authority for an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Pending update authority account (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+///   1. `[]` Metadata account
+///   2. `[signer]` Update authority
+///   3. `[signer, writable]` Payer
+///   4. `[]` System program
+impl InstructionBuilder for super::builders::ProposeUpdateAuthority {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.pending_update_authority, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ],
+            data: MetadataInstruction::ProposeUpdateAuthority(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Accepts a proposed update authority handover.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Pending update authority account (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+///   1. `[writable]` Metadata account
+///   2. `[signer, writable]` Pending update authority
+impl InstructionBuilder for super::builders::AcceptUpdateAuthority {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.pending_update_authority, false),
+                AccountMeta::new(self.metadata, false),
+                AccountMeta::new(self.new_update_authority, true),
+            ],
+            data: MetadataInstruction::AcceptUpdateAuthority
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum EnableMetadataHistoryArgs {
+    V1 {
+        /// Maximum number of entries kept in the history.
+        capacity: u8,
+    },
+}
+
+/// Enables the history of changes to the data of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata history account (pda of ['metadata', program id, mint id, 'metadata_history'])
+///   1. `[]` Metadata account
+///   2. `[signer]` Update authority
+///   3. `[signer, writable]` Payer
+///   4. `[]` System program
+impl InstructionBuilder for super::builders::EnableMetadataHistory {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.metadata_history, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ],
+            data: MetadataInstruction::EnableMetadataHistory(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetRoyaltyConfigArgs {
+    V1 {
+        /// The royalty config of the asset (`None` to use its own royalty settings only).
+        royalty_config: Option<RoyaltyConfig>,
+    },
+}
+
+/// Sets the royalty config of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[signer]` Update authority
+impl InstructionBuilder for super::builders::SetRoyaltyConfig {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+            ],
+            data: MetadataInstruction::SetRoyaltyConfig(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
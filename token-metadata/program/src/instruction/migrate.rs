@@ -0,0 +1,144 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde-feature")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde-feature")]
+use serde_with::{As, DisplayFromStr};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use super::{InstructionBuilder, MetadataInstruction};
+use crate::state::MigrationType;
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum MigrateArgs {
+    V1 {
+        /// Type of the migration.
+        migration_type: MigrationType,
+        /// Rule set of the migrated asset. Migrations performed by the token holder must
+        /// use the rule set of the migration allowlist, and migrations to `NonFungible`
+        /// must not specify a rule set.
+        #[cfg_attr(
+            feature = "serde-feature",
+            serde(with = "As::<Option<DisplayFromStr>>")
+        )]
+        rule_set: Option<Pubkey>,
+    },
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum UpdateMigrationAllowlistArgs {
+    V1 {
+        /// Rule set assigned to the items migrated by their holders.
+        #[cfg_attr(
+            feature = "serde-feature",
+            serde(with = "As::<Option<DisplayFromStr>>")
+        )]
+        rule_set: Option<Pubkey>,
+        /// Indicates whether every item of the collection can be migrated by its holder.
+        allow_all: bool,
+        /// Mints to add to the allowlist.
+        #[cfg_attr(feature = "serde-feature", serde(with = "As::<Vec<DisplayFromStr>>"))]
+        add_mints: Vec<Pubkey>,
+        /// Mints to remove from the allowlist.
+        #[cfg_attr(feature = "serde-feature", serde(with = "As::<Vec<DisplayFromStr>>"))]
+        remove_mints: Vec<Pubkey>,
+    },
+}
+
+/// Migrates an asset between `NonFungible` and `ProgrammableNonFungible`.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Metadata account
+///   1. `[]` Master edition account
+///   2. `[writable]` Token account
+///   3. `[]` Token account owner (signer when migrating to `NonFungible`)
+///   4. `[]` Mint account
+///   5. `[signer, writable]` Payer
+///   6. `[signer]` Update authority or token owner
+///   7. `[optional]` Migration allowlist account
+///   8. `[writable]` Token record account
+///   9. `[]` System program
+///   10. `[]` Instructions sysvar account
+///   11. `[]` SPL Token program
+///   12. `[optional]` Token Authorization Rules program
+///   13. `[optional]` Token Authorization Rules account
+impl InstructionBuilder for super::builders::Migrate {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        // the token owner must consent to migrations to non-programmable assets
+        let is_owner_signer = matches!(
+            self.args,
+            MigrateArgs::V1 {
+                migration_type: MigrationType::NonFungibleV1,
+                ..
+            }
+        );
+
+        let mut accounts = vec![
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.edition, false),
+            AccountMeta::new(self.token, false),
+            AccountMeta::new_readonly(self.token_owner, is_owner_signer),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.migration_allowlist.unwrap_or(crate::ID), false),
+            AccountMeta::new(self.token_record, false),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program, false),
+        ];
+
+        // Optional authorization rules accounts
+        if let Some(rules) = &self.authorization_rules {
+            accounts.push(AccountMeta::new_readonly(mpl_token_auth_rules::ID, false));
+            accounts.push(AccountMeta::new_readonly(*rules, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+        }
+
+        Instruction {
+            program_id: crate::ID,
+            accounts,
+            data: MetadataInstruction::Migrate(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Creates or updates the migration allowlist of a collection.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Migration allowlist account (pda of ['metadata', program id, collection mint id, 'migration_allowlist'])
+///   1. `[]` Collection mint account
+///   2. `[]` Collection metadata account
+///   3. `[signer]` Collection update authority
+///   4. `[signer, writable]` Payer
+///   5. `[]` System program
+impl InstructionBuilder for super::builders::UpdateMigrationAllowlist {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.migration_allowlist, false),
+                AccountMeta::new_readonly(self.collection_mint, false),
+                AccountMeta::new_readonly(self.collection_metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ],
+            data: MetadataInstruction::UpdateMigrationAllowlist(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
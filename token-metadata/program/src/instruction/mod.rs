@@ -1,3 +1,4 @@
+mod attributes;
 mod bubblegum;
 mod burn;
 mod collection;
@@ -7,10 +8,13 @@ pub(crate) mod escrow;
 mod fee;
 mod freeze;
 mod metadata;
+mod migrate;
+mod payload;
 mod state;
 mod uses;
 mod verification;
 
+pub use attributes::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 pub use bubblegum::*;
 pub use burn::*;
@@ -21,7 +25,9 @@ pub use escrow::*;
 pub use fee::collect_fees;
 pub use freeze::*;
 pub use metadata::*;
+pub use migrate::*;
 use mpl_token_metadata_context_derive::AccountContext;
+pub use payload::*;
 
 #[cfg(feature = "serde-feature")]
 use serde::{Deserialize, Serialize};
@@ -184,6 +190,14 @@ pub enum MetadataInstruction {
 
     /// Given a token account containing the master edition token to prove authority, and a brand new non-metadata-ed mint with one token
     /// make a new Metadata + Edition that is a child of the master edition denoted by this authority token.
+    ///
+    /// The URI of the new edition is resolved from the edition URI template of the master edition
+    /// when the template account is passed and has been created; otherwise, the new edition keeps
+    /// the URI of the master edition.
+    ///
+    /// When the master edition has a numbering policy, the edition number in the args is checked
+    /// against it; in the next available mode, the first available number of the edition marker
+    /// from the requested one is minted instead.
     #[account(0, writable, name="new_metadata", desc="New Metadata key (pda of ['metadata', program id, mint id])")]
     #[account(1, writable, name="new_edition", desc="New Edition (pda of ['metadata', program id, mint id, 'edition'])")]
     #[account(2, writable, name="master_edition", desc="Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])")]
@@ -198,6 +212,7 @@ pub enum MetadataInstruction {
     #[account(11, name="token_program", desc="Token program")]
     #[account(12, name="system_program", desc="System program")]
     #[account(13, optional, name="rent", desc="Rent info")]
+    #[account(14, optional, name="edition_uri_template", desc="Edition URI template account (pda of ['metadata', program id, master metadata mint id, 'edition_uri_template'])")]
     MintNewEditionFromMasterEditionViaToken(MintNewEditionFromMasterEditionViaTokenArgs),
 
     /// Converts the Master Edition V1 to a Master Edition V2, draining lamports from the two printing mints
@@ -234,8 +249,12 @@ pub enum MetadataInstruction {
     PuffMetadata,
 
     /// Update a Metadata with is_mutable as a parameter
+    /// 
+    /// The change is recorded in the metadata history account when the account is passed
+    /// and the history of the asset is enabled.
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, signer, name="update_authority", desc="Update authority key")]
+    #[account(2, optional, writable, name="metadata_history", desc="Metadata history account (pda of ['metadata', program id, mint id, 'metadata_history'])")]
     UpdateMetadataAccountV2(UpdateMetadataAccountArgsV2),
 
     /// Create Metadata object.
@@ -274,6 +293,8 @@ pub enum MetadataInstruction {
 
     /// Utilize or Use an NFT , burns the NFT and returns the lamports to the update authority if the use method is burn and its out of uses.
     /// Use Authority can be the Holder of the NFT, or a Delegated Use Authority.
+    /// Omitted optional accounts followed by other optional accounts are set to the program id.
+    /// Programmable assets are used through the `Use` instruction.
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, writable, name="token_account", desc="Token Account Of NFT")]
     #[account(2, writable, name="mint", desc="Mint of the Metadata")]
@@ -287,6 +308,7 @@ pub enum MetadataInstruction {
     #[account(8, name="rent", desc="Rent info")]
     #[account(9, optional, writable, name="use_authority_record", desc="Use Authority Record PDA If present the program Assumes a delegated use authority")]
     #[account(10, optional, name="burner", desc="Program As Signer (Burner)")]
+    #[account(11, optional, writable, name="use_config", desc="Use config PDA, required by the refill use method")]
     Utilize(UtilizeArgs),
 
     /// Approve another account to call [utilize] on this NFT.
@@ -377,6 +399,10 @@ pub enum MetadataInstruction {
     RemoveCreatorVerification,
 
     /// Completely burn a NFT, including closing the metadata account.
+    ///
+    /// The size decrement is propagated to the parent collections of the collection when their
+    /// metadata accounts are passed, in which case the whole chain of verified sized parent
+    /// collections must be passed.
     #[account(0, writable, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
     #[account(1, signer, writable, name="owner", desc="NFT owner")]
     #[account(2, writable, name="mint", desc="Mint of the NFT")]
@@ -384,10 +410,17 @@ pub enum MetadataInstruction {
     #[account(4, writable, name="master_edition_account", desc="MasterEdition2 of the NFT")]
     #[account(5, name="spl_token_program", desc="SPL Token Program")]
     #[account(6, optional, writable, name="collection_metadata", desc="Metadata of the Collection")]
+    #[account(7, optional, writable, name="parent_collection", desc="Metadata account of the parent collection of the collection")]
+    #[account(8, optional, writable, name="grandparent_collection", desc="Metadata account of the parent of the parent collection")]
+    #[account(9, optional, writable, name="great_grandparent_collection", desc="Metadata account of the parent of the grandparent collection")]
     BurnNft,
 
     /// Verify Collection V2, new in v1.3--supports Collection Details.
     /// If a MetadataAccount Has a Collection allow the UpdateAuthority of the Collection to Verify the NFT Belongs in the Collection.
+    ///
+    /// The size increment is propagated to the parent collections of the collection when their
+    /// metadata accounts are passed, in which case the whole chain of verified sized parent
+    /// collections must be passed.
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, signer, name="collection_authority", desc="Collection Update authority")]
     #[account(2, signer, writable, name="payer", desc="payer")]
@@ -395,10 +428,17 @@ pub enum MetadataInstruction {
     #[account(4, writable, name="collection", desc="Metadata Account of the Collection")]
     #[account(5, name="collection_master_edition_account", desc="MasterEdition2 Account of the Collection Token")]
     #[account(6, optional, name="collection_authority_record", desc="Collection Authority Record PDA")]
+    #[account(7, optional, writable, name="parent_collection", desc="Metadata account of the parent collection of the collection")]
+    #[account(8, optional, writable, name="grandparent_collection", desc="Metadata account of the parent of the parent collection")]
+    #[account(9, optional, writable, name="great_grandparent_collection", desc="Metadata account of the parent of the grandparent collection")]
     VerifySizedCollectionItem,
 
     /// Unverify Collection V2, new in v1.3--supports Collection Details.
     /// If a MetadataAccount Has a Collection allow an Authority of the Collection to unverify an NFT in a Collection.
+    ///
+    /// The size decrement is propagated to the parent collections of the collection when their
+    /// metadata accounts are passed, in which case the whole chain of verified sized parent
+    /// collections must be passed.
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, signer, name="collection_authority", desc="Collection Authority")]
     #[account(2, signer, writable, name="payer", desc="payer")]
@@ -406,11 +446,18 @@ pub enum MetadataInstruction {
     #[account(4, writable, name="collection", desc="Metadata Account of the Collection")]
     #[account(5, name="collection_master_edition_account", desc="MasterEdition2 Account of the Collection Token")]
     #[account(6, optional, name="collection_authority_record", desc="Collection Authority Record PDA")]
+    #[account(7, optional, writable, name="parent_collection", desc="Metadata account of the parent collection of the collection")]
+    #[account(8, optional, writable, name="grandparent_collection", desc="Metadata account of the parent of the parent collection")]
+    #[account(9, optional, writable, name="great_grandparent_collection", desc="Metadata account of the parent of the grandparent collection")]
     UnverifySizedCollectionItem,
 
     // Set And Verify V2, new in v1.3--supports Collection Details.
     /// Allows the same Update Authority (Or Delegated Authority) on an NFT and Collection to perform [update_metadata_accounts_v2] 
     /// with collection and [verify_collection] on the NFT/Collection in one instruction.
+    ///
+    /// The size increment is propagated to the parent collections of the collection when their
+    /// metadata accounts are passed, in which case the whole chain of verified sized parent
+    /// collections must be passed.
     #[account(0, writable, name="metadata", desc="Metadata account")]
     #[account(1, signer, name="collection_authority", desc="Collection Update authority")]
     #[account(2, signer, writable, name="payer", desc="payer")]
@@ -419,6 +466,9 @@ pub enum MetadataInstruction {
     #[account(5, writable, name="collection", desc="Metadata Account of the Collection")]
     #[account(6, writable, name="collection_master_edition_account", desc="MasterEdition2 Account of the Collection Token")]
     #[account(7, optional, name="collection_authority_record", desc="Collection Authority Record PDA")]
+    #[account(8, optional, writable, name="parent_collection", desc="Metadata account of the parent collection of the collection")]
+    #[account(9, optional, writable, name="grandparent_collection", desc="Metadata account of the parent of the parent collection")]
+    #[account(10, optional, writable, name="great_grandparent_collection", desc="Metadata account of the parent of the grandparent collection")]
     SetAndVerifySizedCollectionItem,
 
     /// Create Metadata object.
@@ -455,6 +505,10 @@ pub enum MetadataInstruction {
     BubblegumSetCollectionSize(SetCollectionSizeArgs),
 
     /// Completely burn a print edition NFT.
+    ///
+    /// When the print edition is burned from a wallet other than the master edition owner, the
+    /// reprint record of its edition number and the system program can be passed after the SPL
+    /// Token program to allow the master edition owner to reprint the edition number.
     #[account(0, writable, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
     #[account(1, signer, writable, name="owner", desc="NFT owner")]
     #[account(2, writable, name="print_edition_mint", desc="Mint of the print edition NFT")]
@@ -516,10 +570,11 @@ pub enum MetadataInstruction {
     /// - NonFungigbleEdition
     /// - Fungible
     /// - FungibleAsset
+    /// - ProgrammableFungibleAsset
     ///
     /// Parent accounts only required for burning print editions are the accounts for the master edition
     /// associated with the print edition.
-    /// The Token Record account is required for burning a ProgrammableNonFungible asset.
+    /// The Token Record account is required for burning programmable assets.
     ///
     /// This handler closes the following accounts:
     ///
@@ -532,8 +587,22 @@ pub enum MetadataInstruction {
     /// For NonFungibleEdition assets:
     /// - Metadata, Edition, Token, and the EditionMarker, if all prints for it are burned.
     ///
+    /// A print edition burned from a wallet other than the master edition owner keeps its
+    /// edition number taken; passing the reprint record of the edition number as a remaining
+    /// account allows the master edition owner to reprint it.
+    ///
     /// For Fungible assets:
     /// - Only the token account, if all tokens are burned.
+    ///
+    /// For ProgrammableFungibleAsset assets:
+    /// - Token and TokenRecord, if all tokens are burned by the holder.
+    ///
+    /// The configurable `authorization_rules` only apply to programmable assets and
+    /// it may require additional accounts to validate the rules.
+    ///
+    /// When burning a verified item of a sized collection, the size decrement is propagated to
+    /// the parent collections of the collection when their metadata accounts are passed, in
+    /// which case the whole chain of verified sized parent collections must be passed.
     #[account(0, signer, writable, name="authority", desc="Asset owner or Utility delegate")]
     #[account(1, optional, writable, name="collection_metadata", desc="Metadata of the Collection")]
     #[account(2, writable, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
@@ -548,6 +617,11 @@ pub enum MetadataInstruction {
     #[account(11, name="system_program", desc="System program")]
     #[account(12, name="sysvar_instructions", desc="Instructions sysvar account")]
     #[account(13, name="spl_token_program", desc="SPL Token Program")]
+    #[account(14, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(15, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(16, optional, writable, name="parent_collection", desc="Metadata account of the parent collection of the collection")]
+    #[account(17, optional, writable, name="grandparent_collection", desc="Metadata account of the parent of the parent collection")]
+    #[account(18, optional, writable, name="great_grandparent_collection", desc="Metadata account of the parent of the grandparent collection")]
     #[default_optional_accounts]
     Burn(BurnArgs),
 
@@ -603,6 +677,9 @@ pub enum MetadataInstruction {
     ///      `Utility` actions (pda of ["metadata", program id, mint id, "persistent_delegate", token owner id])
     ///   2. Multiple delegates: for `Authority`, `Collection`, `Update` and `Uses` actions (pda of ["metadata",
     ///      program id, mint id, role, update authority id, delegate owner id])
+    /// 
+    /// Metadata delegates can optionally be limited by an expiry timestamp and a maximum number of uses
+    /// (`LimitedMetadataV1` args). Expired or exhausted delegates cannot be used.
     #[account(0, optional, writable, name="delegate_record", desc="Delegate record account")]
     #[account(1, name="delegate", desc="Owner of the delegated account")]
     #[account(2, writable, name="metadata", desc="Metadata account")]
@@ -623,6 +700,8 @@ pub enum MetadataInstruction {
     /// Revokes a delegate.
     /// 
     /// A delegate can revoke itself by signing the transaction as the 'approver'.
+    /// Expired or exhausted metadata delegates can be revoked by anyone, in which case the
+    /// rent is returned to the approver (passed as the payer or as a writable remaining account).
     #[account(0, optional, writable, name="delegate_record", desc="Delegate record account")]
     #[account(1, name="delegate", desc="Owner of the delegated account")]
     #[account(2, writable, name="metadata", desc="Metadata account")]
@@ -642,8 +721,12 @@ pub enum MetadataInstruction {
 
     /// Locks an asset. For non-programmable assets, this will also freeze the token account.
     /// 
-    /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
+    /// The configurable `authorization_rules` only apply to programmable assets and
     /// it may require additional accounts to validate the rules.
+    /// 
+    /// Programmable assets of a verified collection can also be locked by a `CollectionLock`
+    /// delegate of the collection, which passes its (writable) delegate record as a remaining
+    /// account.
     #[account(0, signer, name="authority", desc="Delegate or freeze authority")]
     #[account(1, optional, name="token_owner", desc="Token owner account")]
     #[account(2, writable, name="token", desc="Token account")]
@@ -662,8 +745,12 @@ pub enum MetadataInstruction {
 
     /// Unlocks an asset. For non-programmable assets, this will also thaw the token account.
     /// 
-    /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
+    /// The configurable `authorization_rules` only apply to programmable assets and
     /// it may require additional accounts to validate the rules.
+    /// 
+    /// Programmable assets of a verified collection can also be unlocked by a `CollectionLock`
+    /// delegate of the collection, which passes its (writable) delegate record as a remaining
+    /// account.
     #[account(0, signer, name="authority", desc="Delegate or freeze authority")]
     #[account(1, optional, name="token_owner", desc="Token owner account")]
     #[account(2, writable, name="token", desc="Token account")]
@@ -681,28 +768,43 @@ pub enum MetadataInstruction {
     Unlock(UnlockArgs),
 
     /// Migrates an asset to a ProgrammableAsset type.
+    ///
+    /// The migration can be performed by the update authority or, when the asset is a
+    /// verified item of a collection whose migration allowlist includes it, by the token
+    /// holder. The token record is created and the token account is frozen.
+    ///
+    /// A `ProgrammableNonFungible` can be migrated back to `NonFungible` with the signatures
+    /// of both the update authority and the token owner. The token account is thawed and the
+    /// token record is closed.
     #[account(0, writable, name="metadata", desc="Metadata account")]
-    #[account(1, writable, name="edition", desc="Edition account")]
+    #[account(1, name="edition", desc="Master edition account")]
     #[account(2, writable, name="token", desc="Token account")]
-    #[account(3, name="token_owner", desc="Token account owner")]
+    #[account(3, name="token_owner", desc="Token account owner (signer when migrating to NonFungible)")]
     #[account(4, name="mint", desc="Mint account")]
     #[account(5, writable, signer, name="payer", desc="Payer")]
-    #[account(6, signer, name="authority", desc="Update authority")]
-    #[account(7, name="collection_metadata", desc="Collection metadata account")]
-    #[account(8, name="delegate_record", desc="Delegate record account")]
-    #[account(9, writable, name="token_record", desc="Token record account")]
-    #[account(10, name="system_program", desc="System program")]
-    #[account(11, name="sysvar_instructions", desc="Instruction sysvar account")]
-    #[account(12, name="spl_token_program", desc="SPL Token Program")]
-    #[account(13, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
-    #[account(14, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(6, signer, name="authority", desc="Update authority or token owner")]
+    #[account(7, optional, name="migration_allowlist", desc="Migration allowlist account (pda of ['metadata', program id, collection mint id, 'migration_allowlist'])")]
+    #[account(8, writable, name="token_record", desc="Token record account")]
+    #[account(9, name="system_program", desc="System program")]
+    #[account(10, name="sysvar_instructions", desc="Instruction sysvar account")]
+    #[account(11, name="spl_token_program", desc="SPL Token Program")]
+    #[account(12, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(13, optional, name="authorization_rules", desc="Token Authorization Rules account")]
     #[default_optional_accounts]
-    Migrate,
+    Migrate(MigrateArgs),
 
     /// Transfer an asset.
     /// 
-    /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
+    /// The configurable `authorization_rules` only apply to programmable assets and
     /// it may require additional accounts to validate the rules.
+    ///
+    /// `ProgrammableNonTransferable` assets can only be recovered to a new wallet of the
+    /// holder, which requires both the update authority (as `authority`) and the holder
+    /// (as `token_owner`) to sign.
+    ///
+    /// When a rented token is transferred back to the lender after the rental expires, the
+    /// lender (as `destination_owner`) must be writable to receive the rent of the borrower
+    /// token record.
     #[account(0, writable, name="token", desc="Token account")]
     #[account(1, name="token_owner", desc="Token account owner")]
     #[account(2, writable, name="destination", desc="Destination token account")]
@@ -725,10 +827,22 @@ pub enum MetadataInstruction {
 
     /// Updates the metadata of an asset.
     /// 
-    /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
+    /// The configurable `authorization_rules` only apply to programmable assets and
     /// it may require additional accounts to validate the rules.
+    /// 
+    /// When using the `BatchV1` args, the items to update are passed as remaining accounts
+    /// and referenced by the index of their metadata account in the batch items. Each item
+    /// uses consecutive remaining accounts:
+    /// 
+    ///   0. `[writable]` Metadata account
+    ///   1. `[writable]` Metadata history account, or the program id when the history is not recorded
+    ///   2. `[]` Mint account, only for programmable assets with a rule set, which are validated
+    ///      against the `Update` operation of the rule set (when defined)
+    /// 
+    /// The change is recorded in the metadata history account when the account is passed
+    /// and the history of the asset is enabled.
     #[account(0, signer, name="authority", desc="Update authority or delegate")]
-    #[account(1, optional, name="delegate_record", desc="Delegate record PDA")]
+    #[account(1, optional, writable, name="delegate_record", desc="Delegate record PDA")]
     #[account(2, optional, name="token", desc="Token account")]
     #[account(3, name="mint", desc="Mint account")]
     #[account(4, writable, name="metadata", desc="Metadata account")]
@@ -738,6 +852,7 @@ pub enum MetadataInstruction {
     #[account(8, name="sysvar_instructions", desc="Instructions sysvar account")]
     #[account(9, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
     #[account(10, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(11, optional, writable, name="metadata_history", desc="Metadata history account (pda of ['metadata', program id, mint id, 'metadata_history'])")]
     #[default_optional_accounts]
     Update(UpdateArgs),
 
@@ -745,12 +860,16 @@ pub enum MetadataInstruction {
     /// 
     /// Use Authority can be the owner of the asset or a delegated use authority.
     /// 
-    /// The configurable `authorization_rules` only apply to `ProgrammableNonFungible` assets and
+    /// The configurable `authorization_rules` only apply to programmable assets and
     /// it may require additional accounts to validate the rules.
+    /// 
+    /// Assets with the `Refill` use method require their use config account, and the program
+    /// as burner account is required when a use authority burns the asset on its last use;
+    /// both are passed as remaining accounts.
     #[account(0, signer, name="authority", desc="Token owner or delegate")]
     #[account(1, writable, optional, name="delegate_record", desc="Delegate record PDA")]
     #[account(2, writable, optional, name="token", desc="Token account")]
-    #[account(3, name="mint", desc="Mint account")]
+    #[account(3, writable, name="mint", desc="Mint account")]
     #[account(4, writable, name="metadata", desc="Metadata account")]
     #[account(5, optional, writable, name="edition", desc="Edition account")]
     #[account(6, signer, name="payer", desc="Payer")]
@@ -765,36 +884,268 @@ pub enum MetadataInstruction {
     /// Verifies that an asset was created by a specific creator or belongs in an specified collection.
     ///
     /// Depending on the type of verification (e.g., creator or collection), additional accounts
-    /// are required.
+    /// are required. For sized collections, the size increment is propagated to the parent
+    /// collections of the collection when their metadata accounts are passed, in which case the
+    /// whole chain of verified sized parent collections must be passed.
     #[account(0, signer, name="authority", desc="Creator to verify, collection update authority or delegate")]
-    #[account(1, optional, name="delegate_record", desc="Delegate record PDA")]
+    #[account(1, optional, writable, name="delegate_record", desc="Delegate record PDA")]
     #[account(2, writable, name="metadata", desc="Metadata account")]
     #[account(3, optional, name="collection_mint", desc="Mint of the Collection")]
     #[account(4, optional, writable, name="collection_metadata", desc="Metadata Account of the Collection")]
     #[account(5, optional, name="collection_master_edition", desc="Master Edition Account of the Collection Token")]
     #[account(6, name="system_program", desc="System program")]
     #[account(7, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(8, optional, writable, name="parent_collection", desc="Metadata account of the parent collection of the collection")]
+    #[account(9, optional, writable, name="grandparent_collection", desc="Metadata account of the parent of the parent collection")]
+    #[account(10, optional, writable, name="great_grandparent_collection", desc="Metadata account of the parent of the grandparent collection")]
     #[default_optional_accounts]
     Verify(VerificationArgs),
 
     /// Unverifies that an asset was created by a specific creator or belongs in an specified collection.
     ///
     /// Depending on the type of verification (e.g., creator or collection), additional accounts
-    /// are required.
+    /// are required. For sized collections, the size decrement is propagated to the parent
+    /// collections of the collection when their metadata accounts are passed, in which case the
+    /// whole chain of verified sized parent collections must be passed.
     #[account(0, signer, name="authority", desc="Creator to verify, collection (or metadata if parent burned) update authority or delegate")]
-    #[account(1, optional, name="delegate_record", desc="Delegate record PDA")]
+    #[account(1, optional, writable, name="delegate_record", desc="Delegate record PDA")]
     #[account(2, writable, name="metadata", desc="Metadata account")]
     #[account(3, optional, name="collection_mint", desc="Mint of the Collection")]
     #[account(4, optional, writable, name="collection_metadata", desc="Metadata Account of the Collection")]
     #[account(5, name="system_program", desc="System program")]
     #[account(6, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(7, optional, writable, name="parent_collection", desc="Metadata account of the parent collection of the collection")]
+    #[account(8, optional, writable, name="grandparent_collection", desc="Metadata account of the parent of the parent collection")]
+    #[account(9, optional, writable, name="great_grandparent_collection", desc="Metadata account of the parent of the grandparent collection")]
     #[default_optional_accounts]
     Unverify(VerificationArgs),
 
     /// Collect fees stored on PDA accounts.
+    ///
+    /// Fees are collected from the metadata, edition and master edition accounts passed as
+    /// remaining accounts (writable), so fees of specific account types can be swept separately.
+    /// The total amount collected is logged and set as the return data (little-endian `u64`).
     #[account(0, signer, name="authority", desc="Authority to collect fees")]
     #[account(1, name="pda_account", desc="PDA to retrieve fees from")]
     Collect,
+
+    /// Creates the attributes account of an asset.
+    ///
+    /// The attributes account stores a bounded list of key/value pairs that can be
+    /// modified on-chain by the update authority or an attributes delegate.
+    #[account(0, writable, name="attributes", desc="Attributes account (pda of ['metadata', program id, mint id, 'attributes'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, name="authority", desc="Update authority or attributes delegate")]
+    #[account(4, optional, writable, name="delegate_record", desc="Delegate record PDA")]
+    #[account(5, signer, writable, name="payer", desc="Payer")]
+    #[account(6, name="system_program", desc="System program")]
+    #[default_optional_accounts]
+    CreateAttributes(CreateAttributesArgs),
+
+    /// Adds, modifies or freezes attributes of an asset. Frozen attributes cannot be
+    /// changed or removed.
+    #[account(0, writable, name="attributes", desc="Attributes account")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, name="authority", desc="Update authority or attributes delegate")]
+    #[account(4, optional, writable, name="delegate_record", desc="Delegate record PDA")]
+    #[account(5, signer, writable, name="payer", desc="Payer")]
+    #[account(6, name="system_program", desc="System program")]
+    #[default_optional_accounts]
+    UpdateAttributes(UpdateAttributesArgs),
+
+    /// Removes an attribute of an asset.
+    #[account(0, writable, name="attributes", desc="Attributes account")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, name="authority", desc="Update authority or attributes delegate")]
+    #[account(4, optional, writable, name="delegate_record", desc="Delegate record PDA")]
+    #[account(5, signer, writable, name="payer", desc="Payer")]
+    #[account(6, name="system_program", desc="System program")]
+    #[default_optional_accounts]
+    RemoveAttribute(RemoveAttributeArgs),
+
+    /// Closes the attributes account of an asset. The account cannot be closed while
+    /// it has frozen attributes.
+    #[account(0, writable, name="attributes", desc="Attributes account")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, name="mint", desc="Mint account")]
+    #[account(3, signer, name="authority", desc="Update authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer (receives the account lamports)")]
+    CloseAttributes,
+
+    /// Mints a new edition into the number of a burned print edition of a master edition.
+    ///
+    /// The edition number must have been printed (the bit is set on the edition marker) and the
+    /// burned print is proven by the reprint record created when it was burned, which is closed
+    /// by the reprint. The URI of the new edition is resolved from the edition URI template of
+    /// the master edition when the template account is passed and has been created; otherwise,
+    /// the new edition keeps the URI of the master edition.
+    #[account(0, writable, name="new_metadata", desc="New Metadata key (pda of ['metadata', program id, mint id])")]
+    #[account(1, writable, name="new_edition", desc="New Edition (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(2, writable, name="master_edition", desc="Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])")]
+    #[account(3, writable, name="new_mint", desc="Mint of new token - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY")]
+    #[account(4, name="edition_mark_pda", desc="Edition marker of the burned edition number (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number])")]
+    #[account(5, signer, name="new_mint_authority", desc="Mint authority of new mint")]
+    #[account(6, signer, writable, name="payer", desc="payer")]
+    #[account(7, signer, name="token_account_owner", desc="owner of token account containing master token (#8)")]
+    #[account(8, name="token_account", desc="token account containing token from master metadata mint")]
+    #[account(9, name="new_metadata_update_authority", desc="Update authority info for new metadata")]
+    #[account(10, name="metadata", desc="Master record metadata account")]
+    #[account(11, writable, name="reprint_record", desc="Reprint record of the burned edition number (pda of ['metadata', program id, master metadata mint id, 'edition', edition, 'reprint'])")]
+    #[account(12, name="token_program", desc="Token program")]
+    #[account(13, name="system_program", desc="System program")]
+    #[account(14, optional, name="edition_uri_template", desc="Edition URI template account (pda of ['metadata', program id, master metadata mint id, 'edition_uri_template'])")]
+    ReprintEdition(ReprintEditionArgs),
+
+    /// Updates the creators of an asset, keeping the verified flags of the existing creators.
+    ///
+    /// Every verified creator whose share decreases must sign the transaction; creator
+    /// signers are passed as remaining accounts. The change is recorded in the metadata
+    /// history when its account is passed and the history is enabled.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="authority", desc="Update authority")]
+    #[account(2, optional, writable, name="metadata_history", desc="Metadata history account (pda of ['metadata', program id, mint id, 'metadata_history'])")]
+    UpdateCreators(UpdateCreatorsArgs),
+
+    /// Transfers an asset into the escrow of another asset.
+    ///
+    /// The escrow is the owner of the destination token account. Programmable assets are
+    /// routed through the programmable transfer logic, so their rule set is validated.
+    #[account(0, name="escrow", desc="Token owned escrow account (destination token account owner)")]
+    #[account(1, name="escrow_mint", desc="Mint account that the escrow is attached")]
+    #[account(2, writable, name="token", desc="Token account")]
+    #[account(3, name="token_owner", desc="Token account owner")]
+    #[account(4, writable, name="destination", desc="Destination token account (owned by the escrow)")]
+    #[account(5, name="mint", desc="Mint of token asset")]
+    #[account(6, writable, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
+    #[account(7, optional, name="edition", desc="Edition of token asset")]
+    #[account(8, optional, writable, name="owner_token_record", desc="Owner token record account")]
+    #[account(9, optional, writable, name="destination_token_record", desc="Destination token record account")]
+    #[account(10, signer, name="authority", desc="Transfer authority (token owner or delegate)")]
+    #[account(11, signer, writable, name="payer", desc="Payer")]
+    #[account(12, name="system_program", desc="System Program")]
+    #[account(13, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(14, name="spl_token_program", desc="SPL Token Program")]
+    #[account(15, name="spl_ata_program", desc="SPL Associated Token Account program")]
+    #[account(16, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(17, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[default_optional_accounts]
+    EscrowTransferIn(TransferArgs),
+
+    /// Transfers an asset out of the escrow of another asset.
+    ///
+    /// The escrow signs as the owner of the source token account, which is closed once
+    /// it is empty. Programmable assets are routed through the programmable transfer
+    /// logic, so their rule set is validated.
+    #[account(0, name="escrow", desc="Token owned escrow account (source token account owner)")]
+    #[account(1, name="escrow_mint", desc="Mint account that the escrow is attached")]
+    #[account(2, name="escrow_token", desc="Token account that holds the token the escrow is attached to")]
+    #[account(3, writable, name="token", desc="Token account (owned by the escrow)")]
+    #[account(4, writable, name="destination", desc="Destination token account")]
+    #[account(5, name="destination_owner", desc="Destination token account owner")]
+    #[account(6, name="mint", desc="Mint of token asset")]
+    #[account(7, writable, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
+    #[account(8, optional, name="edition", desc="Edition of token asset")]
+    #[account(9, optional, writable, name="owner_token_record", desc="Owner token record account")]
+    #[account(10, optional, writable, name="destination_token_record", desc="Destination token record account")]
+    #[account(11, signer, name="authority", desc="Authority/creator of the escrow account")]
+    #[account(12, signer, writable, name="payer", desc="Payer")]
+    #[account(13, name="system_program", desc="System Program")]
+    #[account(14, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(15, name="spl_token_program", desc="SPL Token Program")]
+    #[account(16, name="spl_ata_program", desc="SPL Associated Token Account program")]
+    #[account(17, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(18, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[default_optional_accounts]
+    EscrowTransferOut(TransferArgs),
+
+    /// Resets the primary sale flag of an asset to `false`.
+    ///
+    /// The flag can only be reset while the token account holding the asset is owned
+    /// by a verified creator, e.g., when the asset was moved between creator wallets.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="authority", desc="Update authority")]
+    #[account(2, name="token", desc="Token account holding the asset")]
+    ResetPrimarySaleHappened,
+
+    /// Creates or updates the migration allowlist of a collection, which allows holders
+    /// to migrate items of the collection to `ProgrammableNonFungible`.
+    #[account(0, writable, name="migration_allowlist", desc="Migration allowlist account (pda of ['metadata', program id, collection mint id, 'migration_allowlist'])")]
+    #[account(1, name="collection_mint", desc="Collection mint account")]
+    #[account(2, name="collection_metadata", desc="Collection metadata account")]
+    #[account(3, signer, name="authority", desc="Collection update authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    UpdateMigrationAllowlist(UpdateMigrationAllowlistArgs),
+
+    /// Proposes a new update authority for an asset (or a collection and its items). The
+    /// update authority only changes once the proposed authority accepts the handover.
+    #[account(0, writable, name="pending_update_authority", desc="Pending update authority account (pda of ['metadata', program id, mint id, 'pending_update_authority'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="authority", desc="Update authority")]
+    #[account(3, signer, writable, name="payer", desc="Payer")]
+    #[account(4, name="system_program", desc="System program")]
+    ProposeUpdateAuthority(ProposeUpdateAuthorityArgs),
+
+    /// Accepts a proposed update authority handover. For collection handovers, metadata
+    /// accounts of additional collection items can be passed as remaining accounts.
+    #[account(0, writable, name="pending_update_authority", desc="Pending update authority account (pda of ['metadata', program id, mint id, 'pending_update_authority'])")]
+    #[account(1, writable, name="metadata", desc="Metadata account")]
+    #[account(2, signer, writable, name="new_update_authority", desc="Pending update authority")]
+    AcceptUpdateAuthority,
+
+    /// Enables the history of changes to the data of an asset, or updates its capacity.
+    #[account(0, writable, name="metadata_history", desc="Metadata history account (pda of ['metadata', program id, mint id, 'metadata_history'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="authority", desc="Update authority")]
+    #[account(3, signer, writable, name="payer", desc="Payer")]
+    #[account(4, name="system_program", desc="System program")]
+    EnableMetadataHistory(EnableMetadataHistoryArgs),
+
+    /// Sets how the royalty settings of an asset are shared within its collection: a collection
+    /// parent can declare its royalty settings as authoritative and items can inherit them.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    #[account(1, signer, name="authority", desc="Update authority")]
+    SetRoyaltyConfig(SetRoyaltyConfigArgs),
+
+    /// Creates or updates the use config of an asset, which refills its uses periodically
+    /// and sets a minimum interval between uses. The config is required by the `Refill`
+    /// use method and cannot change after the first use.
+    #[account(0, writable, name="use_config", desc="Use config account (pda of ['metadata', program id, mint id, 'use_config'])")]
+    #[account(1, name="metadata", desc="Metadata account")]
+    #[account(2, signer, name="authority", desc="Update authority")]
+    #[account(3, signer, writable, name="payer", desc="Payer")]
+    #[account(4, name="system_program", desc="System program")]
+    SetUseConfig(SetUseConfigArgs),
+
+    /// Creates or updates the edition URI template of a master edition. The `{edition}` placeholder
+    /// of the template is replaced by the edition number when print editions are minted.
+    #[account(0, writable, name="edition_uri_template", desc="Edition URI template account (pda of ['metadata', program id, master mint id, 'edition_uri_template'])")]
+    #[account(1, name="master_edition", desc="Master edition account")]
+    #[account(2, name="metadata", desc="Master metadata account")]
+    #[account(3, signer, name="authority", desc="Update authority")]
+    #[account(4, signer, writable, name="payer", desc="Payer")]
+    #[account(5, name="system_program", desc="System program")]
+    SetEditionUriTemplate(SetEditionUriTemplateArgs),
+
+    /// Sets the URI of a print edition. Print editions are immutable, so their URI can only be
+    /// changed by the update authority of the master edition.
+    #[account(0, writable, name="metadata", desc="Print edition metadata account")]
+    #[account(1, name="edition", desc="Print edition account")]
+    #[account(2, name="master_edition", desc="Master edition account")]
+    #[account(3, name="master_metadata", desc="Master metadata account")]
+    #[account(4, signer, name="authority", desc="Update authority of the master edition")]
+    #[account(5, optional, writable, name="metadata_history", desc="Metadata history account of the print edition (pda of ['metadata', program id, print mint id, 'metadata_history'])")]
+    SetEditionUri(SetEditionUriArgs),
+
+    /// Sets the numbering policy of the print editions of a master edition. Reserved edition
+    /// numbers can only be minted when the update authority signs the mint transaction.
+    #[account(0, writable, name="master_edition", desc="Master edition account")]
+    #[account(1, name="metadata", desc="Master metadata account")]
+    #[account(2, signer, name="authority", desc="Update authority")]
+    SetEditionNumbering(SetEditionNumberingArgs),
 }
 
 pub struct Context<'a, T> {
@@ -0,0 +1,757 @@
+use borsh::BorshDeserialize;
+use mpl_token_auth_rules::{
+    payload::{Payload, PayloadType},
+    state::{
+        ConstraintType, Rule, RuleSetHeader, RuleSetRevisionMapV1, RuleSetV1, Str32,
+        RULE_SET_REV_MAP_VERSION, RULE_SET_SERIALIZED_HEADER_LEN,
+    },
+    types::LibVersion,
+};
+use solana_program::{program_error::ProgramError, pubkey::PUBKEY_BYTES};
+
+use super::{builders, DelegateArgs, TransferArgs};
+use crate::{
+    error::MetadataError,
+    processor::AuthorizationData,
+    state::{Operation, PayloadKey},
+};
+
+/// Size (in bytes) of the header of a serialized (v2) rule.
+const RULE_HEADER_LEN: usize = 8;
+
+/// Size (in bytes) of the `u64` values of a serialized (v2) rule.
+const U64_LEN: usize = 8;
+
+/// Size (in bytes) of the fixed section of a (v2) rule set: header, owner and name.
+const RULE_SET_V2_FIXED_LEN: usize = 8 + PUBKEY_BYTES + Str32::SIZE;
+
+/// Returns the payload keys required by the rules of an operation.
+///
+/// The `rule_set_data` is the data of a rule set account and the `revision` selects the
+/// rule set revision to use (the latest revision when `None`). The lookup follows the
+/// namespace fallback of the rule set (e.g., `Transfer` for `Transfer:Owner`) and returns
+/// an empty list when the rule set does not have rules for the operation.
+///
+/// Since clients cannot know in advance which alternative of an `Any` rule will be
+/// used, the keys of every alternative are included.
+pub fn required_payload_keys(
+    rule_set_data: &[u8],
+    operation: &Operation,
+    revision: Option<usize>,
+) -> Result<Vec<String>, ProgramError> {
+    let (start, end) = revision_location(rule_set_data, revision)?;
+
+    let lib_version = rule_set_data
+        .get(start)
+        .and_then(|version| LibVersion::try_from(*version).ok())
+        .ok_or(MetadataError::InvalidAuthorizationRules)?;
+
+    let mut keys = Vec::new();
+
+    match lib_version {
+        LibVersion::V1 => {
+            let rule_set = rmp_serde::from_slice::<RuleSetV1>(&rule_set_data[start + 1..end])
+                .map_err(|_| MetadataError::InvalidAuthorizationRules)?;
+
+            if let Some(rule) = operation_rule_v1(&rule_set, operation.to_string()) {
+                rule_keys_v1(rule, &mut keys);
+            }
+        }
+        LibVersion::V2 => {
+            // the rule set is read directly from the bytes since the data is not
+            // guaranteed to be aligned outside of the program
+            let data = &rule_set_data[start..end];
+            let size = read_u32(data, 4)? as usize;
+            let mut offset = RULE_SET_V2_FIXED_LEN;
+
+            let mut operations = Vec::with_capacity(size);
+
+            for _ in 0..size {
+                operations.push(read_str32(data, offset)?);
+                offset += Str32::SIZE;
+            }
+            // rules are stored in the same order as the operations
+            let mut rules = Vec::with_capacity(size);
+
+            for _ in 0..size {
+                let rule = data
+                    .get(offset..)
+                    .ok_or(MetadataError::InvalidAuthorizationRules)?;
+                offset += rule_length_v2(rule)?;
+                rules.push(rule);
+            }
+
+            if let Some(index) = operation_index_v2(&operations, &rules, operation.to_string())? {
+                rule_keys_v2(rules[index], &mut keys)?;
+            }
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Returns the start and end location of a rule set revision.
+fn revision_location(
+    rule_set_data: &[u8],
+    revision: Option<usize>,
+) -> Result<(usize, usize), ProgramError> {
+    if rule_set_data.len() < RULE_SET_SERIALIZED_HEADER_LEN {
+        return Err(MetadataError::InvalidAuthorizationRules.into());
+    }
+
+    let header = RuleSetHeader::try_from_slice(&rule_set_data[..RULE_SET_SERIALIZED_HEADER_LEN])
+        .map_err(|_| MetadataError::InvalidAuthorizationRules)?;
+    let rev_map_location = header.rev_map_version_location;
+
+    if rule_set_data.get(rev_map_location) != Some(&RULE_SET_REV_MAP_VERSION) {
+        return Err(MetadataError::InvalidAuthorizationRules.into());
+    }
+
+    let revision_map =
+        RuleSetRevisionMapV1::deserialize(&mut &rule_set_data[rev_map_location + 1..])
+            .map_err(|_| MetadataError::InvalidAuthorizationRules)?;
+
+    // same revision lookup used by the rule set validation
+    let (start, end) = match revision {
+        Some(revision) => {
+            let start = revision_map
+                .rule_set_revisions
+                .get(revision)
+                .ok_or(MetadataError::InvalidAuthorizationRules)?;
+            let end = revision_map
+                .rule_set_revisions
+                .get(revision + 1)
+                .unwrap_or(&rev_map_location);
+            (*start, *end)
+        }
+        None => {
+            let start = revision_map
+                .rule_set_revisions
+                .last()
+                .ok_or(MetadataError::InvalidAuthorizationRules)?;
+            (*start, rev_map_location)
+        }
+    };
+
+    if start >= end || end > rule_set_data.len() {
+        return Err(MetadataError::InvalidAuthorizationRules.into());
+    }
+
+    Ok((start, end))
+}
+
+/// Finds the rule of the operation, following the namespace fallback.
+fn operation_rule_v1(rule_set: &RuleSetV1, operation: String) -> Option<&Rule> {
+    match rule_set.get(operation.clone()) {
+        Some(Rule::Namespace) => operation
+            .split_once(':')
+            .and_then(|(namespace, _)| operation_rule_v1(rule_set, namespace.to_owned())),
+        rule => rule,
+    }
+}
+
+fn rule_keys_v1(rule: &Rule, keys: &mut Vec<String>) {
+    match rule {
+        Rule::All { rules } | Rule::Any { rules } => {
+            rules.iter().for_each(|rule| rule_keys_v1(rule, keys));
+        }
+        Rule::Not { rule } => rule_keys_v1(rule, keys),
+        Rule::PubkeyMatch { field, .. }
+        | Rule::PubkeyListMatch { field, .. }
+        | Rule::ProgramOwned { field, .. }
+        | Rule::ProgramOwnedList { field, .. }
+        | Rule::Amount { field, .. }
+        | Rule::IsWallet { field } => add_key(keys, field.clone()),
+        Rule::PubkeyTreeMatch {
+            pubkey_field,
+            proof_field,
+            ..
+        }
+        | Rule::ProgramOwnedTree {
+            pubkey_field,
+            proof_field,
+            ..
+        } => {
+            add_key(keys, pubkey_field.clone());
+            add_key(keys, proof_field.clone());
+        }
+        Rule::PDAMatch {
+            pda_field,
+            seeds_field,
+            ..
+        } => {
+            add_key(keys, pda_field.clone());
+            add_key(keys, seeds_field.clone());
+        }
+        // remaining rules do not read values from the payload
+        _ => (),
+    }
+}
+
+/// Finds the index of the operation, following the namespace fallback.
+fn operation_index_v2(
+    operations: &[String],
+    rules: &[&[u8]],
+    operation: String,
+) -> Result<Option<usize>, ProgramError> {
+    let index = match operations.iter().position(|name| *name == operation) {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+
+    let (constraint_type, _) = rule_parts_v2(rules[index])?;
+
+    if constraint_type == ConstraintType::Namespace as u32 {
+        match operation.split_once(':') {
+            Some((namespace, _)) => operation_index_v2(operations, rules, namespace.to_owned()),
+            None => Ok(None),
+        }
+    } else {
+        Ok(Some(index))
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, ProgramError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|value| u32::from_le_bytes(value.try_into().unwrap()))
+        .ok_or_else(|| MetadataError::InvalidAuthorizationRules.into())
+}
+
+/// Reads a (null padded) `Str32` value.
+fn read_str32(bytes: &[u8], offset: usize) -> Result<String, ProgramError> {
+    let value = bytes
+        .get(offset..offset + Str32::SIZE)
+        .ok_or(MetadataError::InvalidAuthorizationRules)?;
+    let end = value
+        .iter()
+        .position(|x| *x == b'\0')
+        .unwrap_or(Str32::SIZE);
+
+    Ok(String::from_utf8_lossy(&value[..end]).to_string())
+}
+
+/// Returns the constraint type and data of a serialized rule.
+fn rule_parts_v2(bytes: &[u8]) -> Result<(u32, &[u8]), ProgramError> {
+    let constraint_type = read_u32(bytes, 0)?;
+    let length = read_u32(bytes, 4)? as usize;
+
+    let data = bytes
+        .get(RULE_HEADER_LEN..RULE_HEADER_LEN + length)
+        .ok_or(MetadataError::InvalidAuthorizationRules)?;
+
+    Ok((constraint_type, data))
+}
+
+fn rule_length_v2(bytes: &[u8]) -> Result<usize, ProgramError> {
+    rule_parts_v2(bytes).map(|(_, data)| RULE_HEADER_LEN + data.len())
+}
+
+fn rule_keys_v2(bytes: &[u8], keys: &mut Vec<String>) -> Result<(), ProgramError> {
+    let (constraint_type, data) = rule_parts_v2(bytes)?;
+    let constraint_type = ConstraintType::try_from(constraint_type)
+        .map_err(|_| MetadataError::InvalidAuthorizationRules)?;
+
+    // location of the field names on each constraint
+    let fields: &[usize] = match constraint_type {
+        ConstraintType::All | ConstraintType::Any => {
+            let size = data
+                .get(..U64_LEN)
+                .map(|value| u64::from_le_bytes(value.try_into().unwrap()))
+                .ok_or(MetadataError::InvalidAuthorizationRules)?;
+            let mut offset = U64_LEN;
+
+            for _ in 0..size {
+                let rule = data
+                    .get(offset..)
+                    .ok_or(MetadataError::InvalidAuthorizationRules)?;
+                rule_keys_v2(rule, keys)?;
+                offset += rule_length_v2(rule)?;
+            }
+
+            &[]
+        }
+        ConstraintType::Not => {
+            rule_keys_v2(data, keys)?;
+            &[]
+        }
+        // field
+        ConstraintType::PubkeyListMatch
+        | ConstraintType::ProgramOwnedList
+        | ConstraintType::IsWallet => &[0],
+        // pubkey + field
+        ConstraintType::PubkeyMatch | ConstraintType::ProgramOwned => &[PUBKEY_BYTES],
+        // amount + operator + field
+        ConstraintType::Amount => &[2 * U64_LEN],
+        // pubkey field + proof field + root
+        ConstraintType::PubkeyTreeMatch | ConstraintType::ProgramOwnedTree => &[0, Str32::SIZE],
+        // program + pda field + seeds field
+        ConstraintType::PDAMatch => &[PUBKEY_BYTES, PUBKEY_BYTES + Str32::SIZE],
+        // remaining constraints do not read values from the payload
+        _ => &[],
+    };
+
+    for offset in fields {
+        add_key(keys, read_str32(data, *offset)?);
+    }
+
+    Ok(())
+}
+
+fn add_key(keys: &mut Vec<String>, key: String) {
+    if !keys.contains(&key) {
+        keys.push(key);
+    }
+}
+
+/// Returns the `PayloadKey` with the specified name.
+fn payload_key(name: &str) -> Option<PayloadKey> {
+    [
+        PayloadKey::Amount,
+        PayloadKey::Authority,
+        PayloadKey::AuthoritySeeds,
+        PayloadKey::Delegate,
+        PayloadKey::DelegateSeeds,
+        PayloadKey::Destination,
+        PayloadKey::DestinationSeeds,
+        PayloadKey::Holder,
+        PayloadKey::Source,
+        PayloadKey::SourceSeeds,
+    ]
+    .into_iter()
+    .find(|key| key.to_string() == name)
+}
+
+/// Builds the authorization data payload of an instruction from its accounts and args.
+///
+/// Values that cannot be derived from the instruction (e.g., seeds for PDA matching or
+/// Merkle proofs) must be present in the payload given to `build_payload`.
+pub trait AuthorizationPayload {
+    /// Returns the value of a payload key derived from the instruction, if any.
+    fn payload_value(&self, key: &PayloadKey) -> Option<PayloadType>;
+
+    /// Sets the authorization data of the instruction.
+    fn set_authorization_data(&mut self, authorization_data: AuthorizationData);
+
+    /// Adds the values of the `required_keys` that are not in the `payload`.
+    fn build_payload(
+        &self,
+        required_keys: &[String],
+        mut payload: Payload,
+    ) -> Result<Payload, Box<dyn std::error::Error>> {
+        for key in required_keys {
+            if payload.get(key).is_some() {
+                continue;
+            }
+
+            let value = payload_key(key)
+                .and_then(|payload_key| self.payload_value(&payload_key))
+                .ok_or(format!("{} is not set", key))?;
+
+            payload.insert(key.clone(), value);
+        }
+
+        Ok(payload)
+    }
+
+    /// Builds the payload for the `required_keys` and sets it as the authorization data.
+    fn set_authorization_payload(
+        &mut self,
+        required_keys: &[String],
+        payload: Payload,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let payload = self.build_payload(required_keys, payload)?;
+        self.set_authorization_data(AuthorizationData::new(payload));
+        Ok(())
+    }
+}
+
+impl AuthorizationPayload for builders::Transfer {
+    fn payload_value(&self, key: &PayloadKey) -> Option<PayloadType> {
+        match key {
+            PayloadKey::Amount => {
+                let TransferArgs::V1 { amount, .. } = &self.args;
+                Some(PayloadType::Number(*amount))
+            }
+            PayloadKey::Authority => Some(PayloadType::Pubkey(self.authority)),
+            PayloadKey::Source | PayloadKey::Holder => Some(PayloadType::Pubkey(self.token_owner)),
+            PayloadKey::Destination => Some(PayloadType::Pubkey(self.destination_owner)),
+            _ => None,
+        }
+    }
+
+    fn set_authorization_data(&mut self, authorization_data: AuthorizationData) {
+        let TransferArgs::V1 {
+            authorization_data: data,
+            ..
+        } = &mut self.args;
+        *data = Some(authorization_data);
+    }
+}
+
+impl AuthorizationPayload for builders::Delegate {
+    fn payload_value(&self, key: &PayloadKey) -> Option<PayloadType> {
+        match key {
+            PayloadKey::Amount => match &self.args {
+                DelegateArgs::SaleV1 { amount, .. }
+                | DelegateArgs::TransferV1 { amount, .. }
+                | DelegateArgs::UtilityV1 { amount, .. }
+                | DelegateArgs::StakingV1 { amount, .. }
+                | DelegateArgs::StandardV1 { amount }
+                | DelegateArgs::LockedTransferV1 { amount, .. }
+                | DelegateArgs::RentalV1 { amount, .. } => Some(PayloadType::Number(*amount)),
+                _ => None,
+            },
+            PayloadKey::Authority | PayloadKey::Holder => Some(PayloadType::Pubkey(self.authority)),
+            PayloadKey::Delegate => Some(PayloadType::Pubkey(self.delegate)),
+            _ => None,
+        }
+    }
+
+    fn set_authorization_data(&mut self, authorization_data: AuthorizationData) {
+        match &mut self.args {
+            DelegateArgs::CollectionV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::SaleV1 {
+                authorization_data: data,
+                ..
+            }
+            | DelegateArgs::TransferV1 {
+                authorization_data: data,
+                ..
+            }
+            | DelegateArgs::DataV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::UtilityV1 {
+                authorization_data: data,
+                ..
+            }
+            | DelegateArgs::StakingV1 {
+                authorization_data: data,
+                ..
+            }
+            | DelegateArgs::LockedTransferV1 {
+                authorization_data: data,
+                ..
+            }
+            | DelegateArgs::ProgrammableConfigV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::AuthorityItemV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::DataItemV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::CollectionItemV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::ProgrammableConfigItemV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::AttributesV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::CollectionLockV1 {
+                authorization_data: data,
+            }
+            | DelegateArgs::RentalV1 {
+                authorization_data: data,
+                ..
+            }
+            | DelegateArgs::LimitedMetadataV1 {
+                authorization_data: data,
+                ..
+            } => *data = Some(authorization_data),
+            // standard delegates are not validated against a rule set
+            DelegateArgs::StandardV1 { .. } => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use mpl_token_auth_rules::state::{
+        All, Amount, Any, CompareOp, IsWallet, Namespace, Operator, PDAMatch, RuleSetHeader,
+        RuleSetRevisionMapV1, RuleSetV1, RuleSetV2, RULE_SET_REV_MAP_VERSION,
+        RULE_SET_SERIALIZED_HEADER_LEN,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::{
+        instruction::builders::{DelegateBuilder, TransferBuilder},
+        processor::{DelegateScenario, TransferScenario},
+        state::TokenDelegateRole,
+    };
+
+    /// Stores a (single revision) rule set in the layout of a rule set account.
+    fn rule_set_account(revision: Vec<u8>) -> Vec<u8> {
+        let rev_map_location = RULE_SET_SERIALIZED_HEADER_LEN + revision.len();
+
+        let mut data = RuleSetHeader::new(rev_map_location).try_to_vec().unwrap();
+        data.extend(revision);
+        data.push(RULE_SET_REV_MAP_VERSION);
+        data.extend(
+            RuleSetRevisionMapV1 {
+                rule_set_revisions: vec![RULE_SET_SERIALIZED_HEADER_LEN],
+            }
+            .try_to_vec()
+            .unwrap(),
+        );
+
+        data
+    }
+
+    fn rule_set_v1() -> Vec<u8> {
+        let mut rule_set = RuleSetV1::new("rules".to_string(), Pubkey::new_unique());
+        rule_set
+            .add(
+                "Transfer".to_string(),
+                Rule::All {
+                    rules: vec![
+                        Rule::Amount {
+                            amount: 1,
+                            operator: CompareOp::Eq,
+                            field: PayloadKey::Amount.to_string(),
+                        },
+                        Rule::Any {
+                            rules: vec![
+                                Rule::ProgramOwnedList {
+                                    programs: vec![Pubkey::new_unique()],
+                                    field: PayloadKey::Destination.to_string(),
+                                },
+                                Rule::PDAMatch {
+                                    program: None,
+                                    pda_field: PayloadKey::Destination.to_string(),
+                                    seeds_field: PayloadKey::DestinationSeeds.to_string(),
+                                },
+                            ],
+                        },
+                    ],
+                },
+            )
+            .unwrap();
+        rule_set
+            .add(
+                Operation::Transfer {
+                    scenario: TransferScenario::Holder,
+                }
+                .to_string(),
+                Rule::Namespace,
+            )
+            .unwrap();
+        rule_set
+            .add(
+                Operation::Delegate {
+                    scenario: DelegateScenario::Token(TokenDelegateRole::Utility),
+                }
+                .to_string(),
+                Rule::Not {
+                    rule: Box::new(Rule::IsWallet {
+                        field: PayloadKey::Delegate.to_string(),
+                    }),
+                },
+            )
+            .unwrap();
+
+        let mut revision = vec![LibVersion::V1 as u8];
+        revision.extend(rmp_serde::to_vec(&rule_set).unwrap());
+
+        rule_set_account(revision)
+    }
+
+    fn rule_set_v2() -> Vec<u8> {
+        let amount = Amount::serialize(PayloadKey::Amount.to_string(), Operator::Eq, 1).unwrap();
+        let wallet = IsWallet::serialize(PayloadKey::Destination.to_string()).unwrap();
+        let pda = PDAMatch::serialize(
+            PayloadKey::Source.to_string(),
+            None,
+            PayloadKey::SourceSeeds.to_string(),
+        )
+        .unwrap();
+        let any = Any::serialize(&[&wallet, &pda]).unwrap();
+        let all = All::serialize(&[&amount, &any]).unwrap();
+        let namespace = Namespace::serialize().unwrap();
+
+        rule_set_account(
+            RuleSetV2::serialize(
+                Pubkey::new_unique(),
+                "rules",
+                &[
+                    "Delegate".to_string(),
+                    "Transfer".to_string(),
+                    Operation::Transfer {
+                        scenario: TransferScenario::Holder,
+                    }
+                    .to_string(),
+                ],
+                &[&namespace, &all, &namespace],
+            )
+            .unwrap(),
+        )
+    }
+
+    fn transfer() -> Box<builders::Transfer> {
+        TransferBuilder::new()
+            .token(Pubkey::new_unique())
+            .token_owner(Pubkey::new_unique())
+            .destination(Pubkey::new_unique())
+            .destination_owner(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .authority(Pubkey::new_unique())
+            .payer(Pubkey::new_unique())
+            .build(TransferArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn required_keys_v1() {
+        let data = rule_set_v1();
+        let operation = Operation::Transfer {
+            scenario: TransferScenario::Holder,
+        };
+
+        // follows the namespace fallback and includes every alternative of 'Any'
+        let keys = required_payload_keys(&data, &operation, None).unwrap();
+        assert_eq!(keys, vec!["Amount", "Destination", "DestinationSeeds"]);
+
+        let keys = required_payload_keys(
+            &data,
+            &Operation::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Utility),
+            },
+            Some(0),
+        )
+        .unwrap();
+        assert_eq!(keys, vec!["Delegate"]);
+
+        // operations without rules do not require keys
+        let keys = required_payload_keys(
+            &data,
+            &Operation::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Sale),
+            },
+            None,
+        )
+        .unwrap();
+        assert!(keys.is_empty());
+
+        // invalid revision
+        assert!(required_payload_keys(&data, &operation, Some(1)).is_err());
+    }
+
+    #[test]
+    fn required_keys_v2() {
+        let data = rule_set_v2();
+
+        let keys = required_payload_keys(
+            &data,
+            &Operation::Transfer {
+                scenario: TransferScenario::Holder,
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(keys, vec!["Amount", "Destination", "Source", "SourceSeeds"]);
+
+        // 'Delegate' is a namespace rule without a fallback
+        let keys = required_payload_keys(
+            &data,
+            &Operation::Delegate {
+                scenario: DelegateScenario::Token(TokenDelegateRole::Sale),
+            },
+            None,
+        )
+        .unwrap();
+        assert!(keys.is_empty());
+    }
+
+    #[test]
+    fn build_transfer_payload() {
+        let mut transfer = transfer();
+        let keys = required_payload_keys(
+            &rule_set_v1(),
+            &Operation::Transfer {
+                scenario: TransferScenario::Holder,
+            },
+            None,
+        )
+        .unwrap();
+
+        // seeds are not derived from the instruction
+        let error = transfer.build_payload(&keys, Payload::new()).unwrap_err();
+        assert_eq!(error.to_string(), "DestinationSeeds is not set");
+
+        let mut payload = Payload::new();
+        payload.insert(
+            PayloadKey::DestinationSeeds.to_string(),
+            PayloadType::Seeds(mpl_token_auth_rules::payload::SeedsVec::new(vec![
+                b"seed".to_vec()
+            ])),
+        );
+        transfer.set_authorization_payload(&keys, payload).unwrap();
+
+        let TransferArgs::V1 {
+            authorization_data, ..
+        } = &transfer.args;
+        let payload = &authorization_data.as_ref().unwrap().payload;
+
+        assert_eq!(
+            payload.get(&PayloadKey::Amount.to_string()),
+            Some(&PayloadType::Number(1))
+        );
+        assert_eq!(
+            payload.get(&PayloadKey::Destination.to_string()),
+            Some(&PayloadType::Pubkey(transfer.destination_owner))
+        );
+        assert!(payload
+            .get(&PayloadKey::DestinationSeeds.to_string())
+            .is_some());
+    }
+
+    #[test]
+    fn build_delegate_payload() {
+        let mut delegate = DelegateBuilder::new()
+            .delegate(Pubkey::new_unique())
+            .metadata(Pubkey::new_unique())
+            .mint(Pubkey::new_unique())
+            .authority(Pubkey::new_unique())
+            .payer(Pubkey::new_unique())
+            .build(DelegateArgs::UtilityV1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap();
+
+        let keys = vec![
+            PayloadKey::Amount.to_string(),
+            PayloadKey::Delegate.to_string(),
+        ];
+        delegate
+            .set_authorization_payload(&keys, Payload::new())
+            .unwrap();
+
+        match &delegate.args {
+            DelegateArgs::UtilityV1 {
+                authorization_data: Some(authorization_data),
+                ..
+            } => {
+                assert_eq!(
+                    authorization_data
+                        .payload
+                        .get(&PayloadKey::Delegate.to_string()),
+                    Some(&PayloadType::Pubkey(delegate.delegate))
+                );
+            }
+            _ => panic!("missing authorization data"),
+        }
+    }
+}
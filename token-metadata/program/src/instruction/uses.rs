@@ -7,6 +7,7 @@ use solana_program::{
     system_program,
 };
 
+use super::InstructionBuilder;
 use crate::{instruction::MetadataInstruction, processor::AuthorizationData};
 
 #[repr(C)]
@@ -33,6 +34,20 @@ pub enum UseArgs {
     },
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum SetUseConfigArgs {
+    V1 {
+        /// Number of seconds between refills (`0` disables refills).
+        refill_period: i64,
+        /// Number of uses added on each refill, up to the total uses.
+        refill_amount: u64,
+        /// Minimum number of seconds between uses.
+        min_use_interval: i64,
+    },
+}
+
 ///# Approve Use Authority
 ///
 ///Approve another account to call [utilize] on this NFT
@@ -187,3 +202,88 @@ pub fn utilize(
             .unwrap(),
     }
 }
+
+/// Uses an asset.
+///
+/// # Accounts:
+///
+///   0. `[signer]` Token owner or use authority
+///   1. `[optional, writable]` Use authority record PDA
+///   2. `[writable]` Token account
+///   3. `[]` Mint account
+///   4. `[writable]` Metadata account
+///   5. `[optional, writable]` Edition account
+///   6. `[signer]` Payer
+///   7. `[]` System Program
+///   8. `[]` Instructions sysvar account
+///   9. `[optional]` SPL Token Program
+///   10. `[optional]` Token Authorization Rules program
+///   11. `[optional]` Token Authorization Rules account
+impl InstructionBuilder for super::builders::Use {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.authority, true),
+            if let Some(delegate_record) = self.delegate_record {
+                AccountMeta::new(delegate_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(token) = self.token {
+                AccountMeta::new(token, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            AccountMeta::new(self.mint, false),
+            AccountMeta::new(self.metadata, false),
+            AccountMeta::new_readonly(self.edition.unwrap_or(crate::ID), false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.sysvar_instructions, false),
+            AccountMeta::new_readonly(self.spl_token_program.unwrap_or(crate::ID), false),
+        ];
+
+        // Optional authorization rules accounts
+        if let Some(rules) = &self.authorization_rules {
+            accounts.push(AccountMeta::new_readonly(mpl_token_auth_rules::ID, false));
+            accounts.push(AccountMeta::new_readonly(*rules, false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+            accounts.push(AccountMeta::new_readonly(crate::ID, false));
+        }
+
+        Instruction {
+            program_id: crate::ID,
+            accounts,
+            data: MetadataInstruction::Use(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
+
+/// Creates or updates the use config of an asset.
+///
+/// # Accounts:
+///
+///   0. `[writable]` Use config account
+///   1. `[]` Metadata account
+///   2. `[signer]` Update authority
+///   3. `[signer, writable]` Payer
+///   4. `[]` System program
+impl InstructionBuilder for super::builders::SetUseConfig {
+    fn instruction(&self) -> solana_program::instruction::Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(self.use_config, false),
+                AccountMeta::new_readonly(self.metadata, false),
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ],
+            data: MetadataInstruction::SetUseConfig(self.args.clone())
+                .try_to_vec()
+                .unwrap(),
+        }
+    }
+}
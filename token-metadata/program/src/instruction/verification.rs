@@ -19,18 +19,25 @@ pub enum VerificationArgs {
 /// # Accounts:
 ///
 ///   0. `[signer]` Creator to verify, collection update authority or delegate
-///   1. `[optional]` Delegate record PDA
+///   1. `[optional, writable]` Delegate record PDA
 ///   2. `[writable]` Metadata account
 ///   3. `[optional]` Mint of the Collection
 ///   4. `[optional, writable]` Metadata Account of the Collection
 ///   5. `[optional]` Master Edition Account of the Collection Token
 ///   6. `[]` System program
 ///   7. `[]` Instructions sysvar account
+///   8. `[optional, writable]` Metadata account of the parent collection of the collection
+///   9. `[optional, writable]` Metadata account of the parent of the parent collection
+///  10. `[optional, writable]` Metadata account of the parent of the grandparent collection
 impl InstructionBuilder for super::builders::Verify {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let accounts = vec![
             AccountMeta::new_readonly(self.authority, true),
-            AccountMeta::new_readonly(self.delegate_record.unwrap_or(crate::ID), false),
+            if let Some(delegate_record) = self.delegate_record {
+                AccountMeta::new(delegate_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
             AccountMeta::new(self.metadata, false),
             AccountMeta::new_readonly(self.collection_mint.unwrap_or(crate::ID), false),
             if let Some(collection_metadata) = self.collection_metadata {
@@ -41,6 +48,21 @@ impl InstructionBuilder for super::builders::Verify {
             AccountMeta::new_readonly(self.collection_master_edition.unwrap_or(crate::ID), false),
             AccountMeta::new_readonly(self.system_program, false),
             AccountMeta::new_readonly(self.sysvar_instructions, false),
+            if let Some(parent_collection) = self.parent_collection {
+                AccountMeta::new(parent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(grandparent_collection) = self.grandparent_collection {
+                AccountMeta::new(grandparent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(great_grandparent_collection) = self.great_grandparent_collection {
+                AccountMeta::new(great_grandparent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
         ];
 
         Instruction {
@@ -58,17 +80,24 @@ impl InstructionBuilder for super::builders::Verify {
 /// # Accounts:
 ///
 ///   0. `[signer]` Creator to verify, collection (or metadata if parent burned) update authority or delegate
-///   1. `[optional]` Delegate record PDA
+///   1. `[optional, writable]` Delegate record PDA
 ///   2. `[writable]` Metadata account
 ///   3. `[optional]` Mint of the Collection
 ///   4. `[optional, writable]` Metadata Account of the Collection
 ///   5. `[]` System program
 ///   6. `[]` Instructions sysvar account
+///   7. `[optional, writable]` Metadata account of the parent collection of the collection
+///   8. `[optional, writable]` Metadata account of the parent of the parent collection
+///   9. `[optional, writable]` Metadata account of the parent of the grandparent collection
 impl InstructionBuilder for super::builders::Unverify {
     fn instruction(&self) -> solana_program::instruction::Instruction {
         let accounts = vec![
             AccountMeta::new_readonly(self.authority, true),
-            AccountMeta::new_readonly(self.delegate_record.unwrap_or(crate::ID), false),
+            if let Some(delegate_record) = self.delegate_record {
+                AccountMeta::new(delegate_record, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
             AccountMeta::new(self.metadata, false),
             AccountMeta::new_readonly(self.collection_mint.unwrap_or(crate::ID), false),
             if let Some(collection_metadata) = self.collection_metadata {
@@ -78,6 +107,21 @@ impl InstructionBuilder for super::builders::Unverify {
             },
             AccountMeta::new_readonly(self.system_program, false),
             AccountMeta::new_readonly(self.sysvar_instructions, false),
+            if let Some(parent_collection) = self.parent_collection {
+                AccountMeta::new(parent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(grandparent_collection) = self.grandparent_collection {
+                AccountMeta::new(grandparent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
+            if let Some(great_grandparent_collection) = self.great_grandparent_collection {
+                AccountMeta::new(great_grandparent_collection, false)
+            } else {
+                AccountMeta::new_readonly(crate::ID, false)
+            },
         ];
 
         Instruction {
@@ -1,6 +1,13 @@
 use solana_program::pubkey::Pubkey;
 
-use crate::{instruction::MetadataDelegateRole, state::TOKEN_RECORD_SEED};
+use crate::{
+    instruction::MetadataDelegateRole,
+    state::{
+        ATTRIBUTES_SEED, EDITION_URI_TEMPLATE_SEED, METADATA_HISTORY_SEED,
+        MIGRATION_ALLOWLIST_SEED, PENDING_UPDATE_AUTHORITY_SEED, REPRINT_RECORD_SEED,
+        TOKEN_RECORD_SEED, USE_CONFIG_SEED,
+    },
+};
 
 /// prefix used for PDAs to avoid certain collision attacks:
 /// <https://en.wikipedia.org/wiki/Collision_attack#Chosen-prefix_collision_attack>
@@ -99,6 +106,92 @@ pub fn find_metadata_delegate_record_account(
     )
 }
 
+pub fn find_attributes_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::ID.as_ref(),
+            mint.as_ref(),
+            ATTRIBUTES_SEED.as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_migration_allowlist_account(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::ID.as_ref(),
+            collection_mint.as_ref(),
+            MIGRATION_ALLOWLIST_SEED.as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_pending_update_authority_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::ID.as_ref(),
+            mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY_SEED.as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_metadata_history_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::ID.as_ref(),
+            mint.as_ref(),
+            METADATA_HISTORY_SEED.as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_use_config_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::ID.as_ref(),
+            mint.as_ref(),
+            USE_CONFIG_SEED.as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_edition_uri_template_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::ID.as_ref(),
+            mint.as_ref(),
+            EDITION_URI_TEMPLATE_SEED.as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_reprint_record_account(mint: &Pubkey, edition: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::ID.as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+            edition.to_string().as_bytes(),
+            REPRINT_RECORD_SEED.as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
 pub fn find_token_record_account(mint: &Pubkey, token: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
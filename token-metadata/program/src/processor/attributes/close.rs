@@ -0,0 +1,52 @@
+use mpl_utils::{assert_signer, close_account_raw};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use super::assert_attributes_initialized;
+use crate::{
+    assertions::{assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::CloseAttributes,
+    pda::find_attributes_account,
+    state::{Attributes, Metadata, TokenMetadataAccount},
+};
+
+/// Closes the attributes account of an asset. Only the update authority can close
+/// the account and only when there are no frozen attributes.
+pub fn close_attributes<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    let ctx = CloseAttributes::to_context(accounts)?;
+
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_attributes_initialized(program_id, ctx.accounts.attributes_info)?;
+
+    // key match
+
+    let (attributes_key, _) = find_attributes_account(ctx.accounts.mint_info.key);
+    assert_keys_equal(&attributes_key, ctx.accounts.attributes_info.key)?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    // mint must match mint account key
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if metadata.update_authority != *ctx.accounts.authority_info.key {
+        return Err(MetadataError::UpdateAuthorityIncorrect.into());
+    }
+
+    let attributes = Attributes::from_account_info(ctx.accounts.attributes_info)?;
+    // frozen attributes can never change, so the account cannot be closed
+    if !attributes.frozen_keys.is_empty() {
+        return Err(MetadataError::AttributeFrozen.into());
+    }
+
+    close_account_raw(ctx.accounts.payer_info, ctx.accounts.attributes_info)
+}
@@ -0,0 +1,91 @@
+use mpl_utils::create_or_allocate_account_raw;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use super::{assert_attributes_authority, AttributesAccounts};
+use crate::{
+    error::MetadataError,
+    instruction::{Context, CreateAttributes, CreateAttributesArgs},
+    pda::PREFIX,
+    state::{assert_unique_attribute_keys, Attribute, Attributes, ATTRIBUTES_SEED},
+};
+
+/// Creates the attributes account of an asset.
+pub fn create_attributes<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CreateAttributesArgs,
+) -> ProgramResult {
+    let context = CreateAttributes::to_context(accounts)?;
+
+    match args {
+        CreateAttributesArgs::V1 {
+            attributes,
+            frozen_keys,
+        } => create_attributes_v1(program_id, context, attributes, frozen_keys),
+    }
+}
+
+fn create_attributes_v1(
+    program_id: &Pubkey,
+    ctx: Context<CreateAttributes>,
+    attributes: Vec<Attribute>,
+    frozen_keys: Vec<String>,
+) -> ProgramResult {
+    let bump = assert_attributes_authority(
+        program_id,
+        &AttributesAccounts {
+            attributes_info: ctx.accounts.attributes_info,
+            metadata_info: ctx.accounts.metadata_info,
+            mint_info: ctx.accounts.mint_info,
+            authority_info: ctx.accounts.authority_info,
+            delegate_record_info: ctx.accounts.delegate_record_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+    )?;
+
+    if !ctx.accounts.attributes_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    assert_unique_attribute_keys(&attributes)?;
+
+    let mut pda = Attributes {
+        bump,
+        mint: *ctx.accounts.mint_info.key,
+        ..Default::default()
+    };
+
+    for attribute in attributes {
+        pda.set(attribute)?;
+    }
+
+    for key in &frozen_keys {
+        pda.freeze(key)?;
+    }
+
+    // allocate the attributes account
+
+    let signer_seeds = [
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        ctx.accounts.mint_info.key.as_ref(),
+        ATTRIBUTES_SEED.as_bytes(),
+        &[bump],
+    ];
+
+    create_or_allocate_account_raw(
+        *program_id,
+        ctx.accounts.attributes_info,
+        ctx.accounts.system_program_info,
+        ctx.accounts.payer_info,
+        pda.data_len(),
+        &signer_seeds,
+    )?;
+
+    pda.save(
+        ctx.accounts.attributes_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )
+}
@@ -0,0 +1,111 @@
+mod close;
+mod create;
+mod remove;
+mod update;
+
+pub use close::*;
+pub use create::*;
+pub use remove::*;
+pub use update::*;
+
+use mpl_utils::assert_signer;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::MetadataDelegateRole,
+    pda::find_attributes_account,
+    state::{
+        AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, MetadataDelegateRecord,
+        TokenMetadataAccount,
+    },
+};
+
+/// Accounts shared by the instructions that modify the attributes account.
+pub(crate) struct AttributesAccounts<'a> {
+    attributes_info: &'a AccountInfo<'a>,
+    metadata_info: &'a AccountInfo<'a>,
+    mint_info: &'a AccountInfo<'a>,
+    authority_info: &'a AccountInfo<'a>,
+    delegate_record_info: Option<&'a AccountInfo<'a>>,
+    payer_info: &'a AccountInfo<'a>,
+    system_program_info: &'a AccountInfo<'a>,
+}
+
+/// Validates the accounts of an attributes instruction and checks that the authority is
+/// either the update authority or an `Attributes` delegate.
+///
+/// Returns the bump of the attributes account derivation.
+pub(crate) fn assert_attributes_authority(
+    program_id: &Pubkey,
+    accounts: &AttributesAccounts,
+) -> Result<u8, ProgramError> {
+    // signers
+
+    assert_signer(accounts.authority_info)?;
+    assert_signer(accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(accounts.metadata_info, program_id)?;
+    assert_owned_by(accounts.mint_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(accounts.system_program_info.key, &system_program::ID)?;
+
+    let (attributes_key, bump) = find_attributes_account(accounts.mint_info.key);
+    assert_keys_equal(&attributes_key, accounts.attributes_info.key)?;
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(accounts.metadata_info)?;
+    // mint must match mint account key
+    if metadata.mint != *accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // authority – this can be either:
+    //  1. update authority
+    //  2. attributes delegate
+
+    let AuthorityResponse { authority_type, .. } =
+        AuthorityType::get_authority_type(AuthorityRequest {
+            authority: accounts.authority_info.key,
+            update_authority: &metadata.update_authority,
+            mint: accounts.mint_info.key,
+            metadata_delegate_record_info: accounts.delegate_record_info,
+            metadata_delegate_roles: vec![MetadataDelegateRole::Attributes],
+            precedence: &[AuthorityType::Metadata, AuthorityType::MetadataDelegate],
+            ..Default::default()
+        })?;
+
+    match authority_type {
+        AuthorityType::Metadata => (),
+        AuthorityType::MetadataDelegate => {
+            // the delegate record was validated by the authority check
+            if let Some(delegate_record_info) = accounts.delegate_record_info {
+                MetadataDelegateRecord::consume_use(delegate_record_info)?;
+            }
+        }
+        _ => return Err(MetadataError::InvalidAuthorityType.into()),
+    }
+
+    Ok(bump)
+}
+
+/// Checks that the attributes account exists and is owned by the program.
+pub(crate) fn assert_attributes_initialized(
+    program_id: &Pubkey,
+    attributes_info: &AccountInfo,
+) -> ProgramResult {
+    if attributes_info.data_is_empty() {
+        return Err(MetadataError::Uninitialized.into());
+    }
+
+    assert_owned_by(attributes_info, program_id)
+}
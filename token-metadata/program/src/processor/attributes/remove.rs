@@ -0,0 +1,50 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use super::{assert_attributes_authority, assert_attributes_initialized, AttributesAccounts};
+use crate::{
+    instruction::{Context, RemoveAttribute, RemoveAttributeArgs},
+    state::{Attributes, TokenMetadataAccount},
+};
+
+/// Removes an attribute of an asset.
+pub fn remove_attribute<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RemoveAttributeArgs,
+) -> ProgramResult {
+    let context = RemoveAttribute::to_context(accounts)?;
+
+    match args {
+        RemoveAttributeArgs::V1 { key } => remove_attribute_v1(program_id, context, key),
+    }
+}
+
+fn remove_attribute_v1(
+    program_id: &Pubkey,
+    ctx: Context<RemoveAttribute>,
+    key: String,
+) -> ProgramResult {
+    assert_attributes_authority(
+        program_id,
+        &AttributesAccounts {
+            attributes_info: ctx.accounts.attributes_info,
+            metadata_info: ctx.accounts.metadata_info,
+            mint_info: ctx.accounts.mint_info,
+            authority_info: ctx.accounts.authority_info,
+            delegate_record_info: ctx.accounts.delegate_record_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+    )?;
+    assert_attributes_initialized(program_id, ctx.accounts.attributes_info)?;
+
+    let mut pda = Attributes::from_account_info(ctx.accounts.attributes_info)?;
+    pda.remove(&key)?;
+
+    // shrinks the account to the new data length
+    pda.save(
+        ctx.accounts.attributes_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )
+}
@@ -0,0 +1,62 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use super::{assert_attributes_authority, assert_attributes_initialized, AttributesAccounts};
+use crate::{
+    instruction::{Context, UpdateAttributes, UpdateAttributesArgs},
+    state::{assert_unique_attribute_keys, Attribute, Attributes, TokenMetadataAccount},
+};
+
+/// Adds, modifies or freezes attributes of an asset.
+pub fn update_attributes<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UpdateAttributesArgs,
+) -> ProgramResult {
+    let context = UpdateAttributes::to_context(accounts)?;
+
+    match args {
+        UpdateAttributesArgs::V1 {
+            attributes,
+            freeze_keys,
+        } => update_attributes_v1(program_id, context, attributes, freeze_keys),
+    }
+}
+
+fn update_attributes_v1(
+    program_id: &Pubkey,
+    ctx: Context<UpdateAttributes>,
+    attributes: Vec<Attribute>,
+    freeze_keys: Vec<String>,
+) -> ProgramResult {
+    assert_attributes_authority(
+        program_id,
+        &AttributesAccounts {
+            attributes_info: ctx.accounts.attributes_info,
+            metadata_info: ctx.accounts.metadata_info,
+            mint_info: ctx.accounts.mint_info,
+            authority_info: ctx.accounts.authority_info,
+            delegate_record_info: ctx.accounts.delegate_record_info,
+            payer_info: ctx.accounts.payer_info,
+            system_program_info: ctx.accounts.system_program_info,
+        },
+    )?;
+    assert_attributes_initialized(program_id, ctx.accounts.attributes_info)?;
+
+    assert_unique_attribute_keys(&attributes)?;
+
+    let mut pda = Attributes::from_account_info(ctx.accounts.attributes_info)?;
+
+    for attribute in attributes {
+        pda.set(attribute)?;
+    }
+
+    for key in &freeze_keys {
+        pda.freeze(key)?;
+    }
+
+    pda.save(
+        ctx.accounts.attributes_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )
+}
@@ -1,14 +1,36 @@
+use std::fmt::Display;
+
 use solana_program::program_option::COption;
 
 use super::*;
 
 use crate::{
     pda::find_token_record_account,
-    processor::burn::{fungible::burn_fungible, nonfungible_edition::burn_nonfungible_edition},
-    state::{AuthorityRequest, AuthorityType, TokenDelegateRole, TokenRecord, TokenState},
-    utils::{check_token_standard, thaw},
+    processor::burn::{
+        fungible::{burn_fungible, burn_programmable_fungible, BurnProgrammableFungibleArgs},
+        nonfungible_edition::burn_nonfungible_edition,
+    },
+    state::{
+        AuthorityRequest, AuthorityType, Operation, TokenDelegateRole, TokenRecord, TokenState,
+    },
+    utils::{auth_rules_validate, check_token_standard, thaw, AuthRulesValidateParams},
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BurnScenario {
+    Holder,
+    UtilityDelegate,
+}
+
+impl Display for BurnScenario {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Holder => write!(f, "Owner"),
+            Self::UtilityDelegate => write!(f, "UtilityDelegate"),
+        }
+    }
+}
+
 /// Burn an asset, closing associated accounts.
 ///
 /// Supports burning the following asset types:
@@ -17,14 +39,16 @@ use crate::{
 /// - NonFungigbleEdition
 /// - Fungible
 /// - FungibleAsset
+/// - ProgrammableFungibleAsset
+/// - ProgrammableNonTransferable
 ///
 /// Parent accounts only required for burning print editions are the accounts for the master edition
 /// associated with the print edition.
-/// The Token Record account is required for burning a ProgrammableNonFungible asset.
+/// The Token Record account is required for burning programmable assets.
 ///
 /// This handler closes the following accounts:
 ///
-/// For ProgrammableNonFungible assets:
+/// For ProgrammableNonFungible and ProgrammableNonTransferable assets:
 /// - Metadata, Edition, Token, TokenRecord
 ///
 /// For NonFungible assets:
@@ -35,6 +59,9 @@ use crate::{
 ///
 /// For Fungible assets:
 /// - Only the token account, if all tokens are burned.
+///
+/// For ProgrammableFungibleAsset assets:
+/// - Token and TokenRecord, if all tokens are burned by the holder.
 pub fn burn<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -48,7 +75,7 @@ pub fn burn<'a>(
 }
 
 // V1 implementation of the burn instruction.
-fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramResult {
+fn burn_v1<'a>(program_id: &Pubkey, ctx: Context<'a, Burn<'a>>, args: BurnArgs) -> ProgramResult {
     msg!("Burn V1");
     let BurnArgs::V1 { amount } = args;
 
@@ -57,10 +84,21 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
     // Assert signer
     assert_signer(ctx.accounts.authority_info)?;
 
+    // The mint can belong to either SPL Token or Token-2022.
+    if !is_token_program(ctx.accounts.spl_token_program_info.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
     // Assert program ownership.
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
-    assert_owned_by(ctx.accounts.token_info, &spl_token::ID)?;
+    assert_owned_by(
+        ctx.accounts.mint_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
+    assert_owned_by(
+        ctx.accounts.token_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
 
     if let Some(edition_info) = ctx.accounts.edition_info {
         assert_owned_by(edition_info, program_id)?;
@@ -69,10 +107,10 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
         assert_owned_by(master_edition, program_id)?;
     }
     if let Some(master_edition_mint) = ctx.accounts.master_edition_mint_info {
-        assert_owned_by(master_edition_mint, &spl_token::ID)?;
+        assert_owned_by_token_program(master_edition_mint)?;
     }
     if let Some(master_edition_token) = ctx.accounts.master_edition_token_info {
-        assert_owned_by(master_edition_token, &spl_token::ID)?;
+        assert_owned_by_token_program(master_edition_token)?;
     }
     if let Some(edition_marker) = ctx.accounts.edition_marker_info {
         assert_owned_by(edition_marker, program_id)?;
@@ -90,8 +128,10 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    if ctx.accounts.spl_token_program_info.key != &spl_token::ID {
-        return Err(ProgramError::IncorrectProgramId);
+    if let Some(auth_rules_program) = ctx.accounts.authorization_rules_program_info {
+        if auth_rules_program.key != &mpl_token_auth_rules::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
     }
 
     // Deserialize accounts.
@@ -111,12 +151,11 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
     })?;
 
     // Must be either the holder or a token delegate.
-    if !matches!(
-        authority_response.authority_type,
-        AuthorityType::Holder | AuthorityType::TokenDelegate
-    ) {
-        return Err(MetadataError::InvalidAuthorityType.into());
-    }
+    let scenario = match authority_response.authority_type {
+        AuthorityType::Holder => BurnScenario::Holder,
+        AuthorityType::TokenDelegate => BurnScenario::UtilityDelegate,
+        _ => return Err(MetadataError::InvalidAuthorityType.into()),
+    };
 
     // Validate relationships between accounts.
 
@@ -148,6 +187,7 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
         TokenStandard::NonFungibleEdition
             | TokenStandard::NonFungible
             | TokenStandard::ProgrammableNonFungible
+            | TokenStandard::ProgrammableNonTransferable
     ) {
         if amount != 1 {
             return Err(MetadataError::InvalidAmount.into());
@@ -172,7 +212,7 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
         TokenStandard::NonFungibleEdition => {
             burn_nonfungible_edition(&ctx)?;
         }
-        TokenStandard::ProgrammableNonFungible => {
+        TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonTransferable => {
             // All the checks are the same as burning a NonFungible token
             // except we also have to check the token state and derivation.
             let token_record = match ctx.accounts.token_record_info {
@@ -196,6 +236,13 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
                 return Err(MetadataError::IncorrectTokenState.into());
             }
 
+            // Rented tokens cannot be burned by the borrower.
+            if token_record.is_rented(&token.owner) {
+                return Err(MetadataError::TokenIsRented.into());
+            }
+
+            validate_burn(&ctx, &metadata, &token_record, scenario, amount)?;
+
             thaw(
                 ctx.accounts.mint_info.clone(),
                 ctx.accounts.token_info.clone(),
@@ -232,6 +279,53 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
                 Key::TokenRecord,
             )?;
         }
+        TokenStandard::ProgrammableFungibleAsset => {
+            // Same checks as ProgrammableNonFungible regarding the token record and
+            // token state, but any amount can be burned.
+            let token_record_info = match ctx.accounts.token_record_info {
+                Some(token_record_info) => {
+                    let (pda_key, _) = find_token_record_account(
+                        ctx.accounts.mint_info.key,
+                        ctx.accounts.token_info.key,
+                    );
+
+                    if pda_key != *token_record_info.key {
+                        return Err(MetadataError::InvalidTokenRecord.into());
+                    }
+
+                    token_record_info
+                }
+                None => return Err(MetadataError::MissingTokenRecord.into()),
+            };
+
+            let token_record = TokenRecord::from_account_info(token_record_info)?;
+
+            // Locked and Listed states cannot be burned.
+            if token_record.state != TokenState::Unlocked {
+                return Err(MetadataError::IncorrectTokenState.into());
+            }
+
+            // Rented tokens cannot be burned by the borrower.
+            if token_record.is_rented(&token.owner) {
+                return Err(MetadataError::TokenIsRented.into());
+            }
+
+            validate_burn(&ctx, &metadata, &token_record, scenario, amount)?;
+
+            let edition_info = ctx
+                .accounts
+                .edition_info
+                .ok_or(MetadataError::MissingEditionAccount)?;
+
+            let args = BurnProgrammableFungibleArgs {
+                token,
+                edition_info,
+                token_record_info,
+                is_holder: matches!(authority_response.authority_type, AuthorityType::Holder),
+            };
+
+            burn_programmable_fungible(&ctx, args, amount)?;
+        }
         TokenStandard::Fungible | TokenStandard::FungibleAsset => {
             burn_fungible(&ctx, amount)?;
         }
@@ -239,3 +333,36 @@ fn burn_v1(program_id: &Pubkey, ctx: Context<Burn>, args: BurnArgs) -> ProgramRe
 
     Ok(())
 }
+
+/// Validates the burn of a programmable asset against its rule set (if any).
+fn validate_burn<'a>(
+    ctx: &Context<'a, Burn<'a>>,
+    metadata: &Metadata,
+    token_record: &TokenRecord,
+    scenario: BurnScenario,
+    amount: u64,
+) -> ProgramResult {
+    // the token owner account is only part of the instruction when it is the authority
+    let owner_info = if matches!(scenario, BurnScenario::Holder) {
+        Some(ctx.accounts.authority_info)
+    } else {
+        None
+    };
+
+    auth_rules_validate(AuthRulesValidateParams {
+        mint_info: ctx.accounts.mint_info,
+        owner_info,
+        authority_info: Some(ctx.accounts.authority_info),
+        source_info: None,
+        destination_info: None,
+        programmable_config: metadata.programmable_config.clone(),
+        amount,
+        auth_data: None,
+        auth_rules_info: ctx.accounts.authorization_rules_info,
+        operation: Operation::Burn { scenario },
+        is_wallet_to_wallet: false,
+        rule_set_revision: token_record
+            .rule_set_revision
+            .map(|revision| revision as usize),
+    })
+}
@@ -3,6 +3,7 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
+    system_program,
 };
 use spl_token::state::Account as TokenAccount;
 
@@ -32,6 +33,14 @@ pub fn process_burn_edition_nft<'a>(
     let print_edition_info = next_account_info(account_info_iter)?;
     let edition_marker_info = next_account_info(account_info_iter)?;
     let spl_token_program_info = next_account_info(account_info_iter)?;
+    // optional reprint record and system program, used when the print edition is burned
+    // from a wallet other than the master edition owner
+    let remaining_accounts = account_info_iter.collect::<Vec<_>>();
+    let system_program_info = remaining_accounts
+        .iter()
+        .copied()
+        .find(|account_info| system_program::check_id(account_info.key))
+        .unwrap_or(spl_token_program_info);
 
     // Validate accounts
     // Owner is a signer.
@@ -86,15 +95,21 @@ pub fn process_burn_edition_nft<'a>(
         master_edition_token_info: Some(master_edition_token_info),
         edition_marker_info: Some(edition_marker_info),
         token_record_info: None,
-        // This handler doesn't get system program and sysvars instructions
-        // but we need them to create the Burn struct. They are not used in the burn_nonfungible_edition handler.
-        system_program_info: spl_token_program_info,
+        // This handler doesn't get sysvars instructions (and the system program is optional)
+        // but we need them to create the Burn struct. The system program is only used to
+        // create the reprint record in the burn_nonfungible_edition handler.
+        system_program_info,
         sysvar_instructions_info: spl_token_program_info,
         spl_token_program_info,
+        authorization_rules_program_info: None,
+        authorization_rules_info: None,
+        parent_collection_info: None,
+        grandparent_collection_info: None,
+        great_grandparent_collection_info: None,
     };
     let context = Context {
         accounts,
-        remaining_accounts: vec![],
+        remaining_accounts,
     };
 
     burn_nonfungible_edition(&context)
@@ -11,6 +11,7 @@ use solana_program::{
 use crate::{
     assertions::assert_owned_by,
     instruction::{Burn, Context},
+    processor::next_trailing_optional_account_info,
     state::{Metadata, TokenMetadataAccount},
 };
 
@@ -26,7 +27,10 @@ pub fn process_burn_nft<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]
     let edition_info = next_account_info(account_info_iter)?;
     let spl_token_program_info = next_account_info(account_info_iter)?;
 
-    let collection_metadata_info = account_info_iter.next();
+    let collection_metadata_info = next_trailing_optional_account_info(account_info_iter);
+    let parent_collection_info = next_trailing_optional_account_info(account_info_iter);
+    let grandparent_collection_info = next_trailing_optional_account_info(account_info_iter);
+    let great_grandparent_collection_info = next_trailing_optional_account_info(account_info_iter);
 
     // Validate accounts
 
@@ -88,6 +92,11 @@ pub fn process_burn_nft<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]
         system_program_info: spl_token_program_info,
         sysvar_instructions_info: spl_token_program_info,
         spl_token_program_info,
+        authorization_rules_program_info: None,
+        authorization_rules_info: None,
+        parent_collection_info,
+        grandparent_collection_info,
+        great_grandparent_collection_info,
     };
     let context = Context {
         accounts,
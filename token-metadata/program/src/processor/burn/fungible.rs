@@ -1,7 +1,9 @@
 use super::*;
 
+use crate::utils::{freeze, thaw};
+
 pub(crate) fn burn_fungible(ctx: &Context<Burn>, amount: u64) -> ProgramResult {
-    let token = TokenAccount::unpack(&ctx.accounts.token_info.data.borrow())?;
+    let token: TokenAccount = unpack(&ctx.accounts.token_info.data.borrow())?;
 
     if amount > token.amount {
         return Err(MetadataError::InsufficientTokenBalance.into());
@@ -32,3 +34,75 @@ pub(crate) fn burn_fungible(ctx: &Context<Burn>, amount: u64) -> ProgramResult {
 
     Ok(())
 }
+
+pub(crate) struct BurnProgrammableFungibleArgs<'a> {
+    pub(crate) token: TokenAccount,
+    pub(crate) edition_info: &'a AccountInfo<'a>,
+    pub(crate) token_record_info: &'a AccountInfo<'a>,
+    pub(crate) is_holder: bool,
+}
+
+pub(crate) fn burn_programmable_fungible<'a>(
+    ctx: &Context<'_, Burn<'a>>,
+    args: BurnProgrammableFungibleArgs<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let BurnProgrammableFungibleArgs {
+        token,
+        edition_info,
+        token_record_info,
+        is_holder,
+    } = args;
+
+    if amount > token.amount {
+        return Err(MetadataError::InsufficientTokenBalance.into());
+    }
+
+    // programmable assets are always frozen, so we need to thaw the
+    // token account before burning
+    thaw(
+        ctx.accounts.mint_info.clone(),
+        ctx.accounts.token_info.clone(),
+        edition_info.clone(),
+        ctx.accounts.spl_token_program_info.clone(),
+    )?;
+
+    // Burn the SPL tokens
+    let params = TokenBurnParams {
+        mint: ctx.accounts.mint_info.clone(),
+        source: ctx.accounts.token_info.clone(),
+        authority: ctx.accounts.authority_info.clone(),
+        token_program: ctx.accounts.spl_token_program_info.clone(),
+        amount,
+        authority_signer_seeds: None,
+    };
+    spl_token_burn(params)?;
+
+    // the token account can only be closed by the holder when all tokens are burned
+    // and the close authority has not been assigned to a (utility) delegate
+    if amount == token.amount && is_holder && token.close_authority.is_none() {
+        let params = TokenCloseParams {
+            token_program: ctx.accounts.spl_token_program_info.clone(),
+            account: ctx.accounts.token_info.clone(),
+            destination: ctx.accounts.authority_info.clone(),
+            owner: ctx.accounts.authority_info.clone(),
+            authority_signer_seeds: None,
+        };
+        spl_token_close(params)?;
+
+        close_program_account(
+            token_record_info,
+            ctx.accounts.authority_info,
+            Key::TokenRecord,
+        )?;
+    } else {
+        freeze(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.token_info.clone(),
+            edition_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -4,13 +4,13 @@ use arrayref::array_ref;
 use mpl_utils::{
     assert_signer,
     token::{
-        get_mint_decimals, get_mint_supply, spl_token_burn, spl_token_close, TokenBurnParams,
-        TokenCloseParams,
+        get_mint_decimals, get_mint_supply, is_token_program, spl_token_burn, spl_token_close,
+        unpack, TokenBurnParams, TokenCloseParams,
     },
 };
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
-    program_pack::Pack, pubkey::Pubkey, system_program, sysvar,
+    pubkey::Pubkey, system_program, sysvar,
 };
 use spl_token::state::Account as TokenAccount;
 
@@ -23,9 +23,9 @@ use crate::{
         Collection, Edition, EditionMarker, Key, Metadata, TokenMetadataAccount, TokenStandard,
     },
     utils::{
-        assert_derivation, assert_initialized, assert_owned_by,
+        assert_derivation, assert_initialized, assert_owned_by, assert_owned_by_token_program,
         assert_verified_member_of_collection, close_program_account, decrement_collection_size,
-        is_master_edition, is_print_edition,
+        is_master_edition, is_print_edition, propagate_collection_size_change,
     },
 };
 
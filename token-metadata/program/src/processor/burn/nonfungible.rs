@@ -132,9 +132,24 @@ pub(crate) fn burn_nonfungible(ctx: &Context<Burn>, args: BurnNonFungibleArgs) -
             // NFT is actually a verified member of the specified collection.
             assert_verified_member_of_collection(&args.metadata, &collection_metadata)?;
 
-            // Update collection size if it's sized.
+            // Metadata accounts of the parent collections of the collection, if any.
+            let ancestors = [
+                ctx.accounts.parent_collection_info,
+                ctx.accounts.grandparent_collection_info,
+                ctx.accounts.great_grandparent_collection_info,
+            ];
+
+            // Update collection size if it's sized, along with its parent collections.
             if collection_metadata.collection_details.is_some() {
                 decrement_collection_size(&mut collection_metadata, collection_metadata_info)?;
+                propagate_collection_size_change(
+                    &crate::ID,
+                    &collection_metadata,
+                    &ancestors,
+                    false,
+                )?;
+            } else if ancestors.iter().any(Option::is_some) {
+                return Err(MetadataError::UnsizedCollection.into());
             }
         }
     }
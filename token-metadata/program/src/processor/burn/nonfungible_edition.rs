@@ -1,8 +1,13 @@
-use crate::state::{MasterEdition, MasterEditionV2, EDITION_MARKER_BIT_SIZE};
+use borsh::BorshSerialize;
+use mpl_utils::create_or_allocate_account_raw;
+
+use crate::state::{
+    MasterEdition, MasterEditionV2, ReprintRecord, EDITION_MARKER_BIT_SIZE, REPRINT_RECORD_SEED,
+};
 
 use super::*;
 
-pub(crate) fn burn_nonfungible_edition(ctx: &Context<Burn>) -> ProgramResult {
+pub(crate) fn burn_nonfungible_edition<'a>(ctx: &Context<'a, Burn<'a>>) -> ProgramResult {
     let edition_info = ctx.accounts.edition_info.unwrap();
 
     let master_edition_mint_info = ctx
@@ -142,6 +147,10 @@ pub(crate) fn burn_nonfungible_edition(ctx: &Context<Burn>) -> ProgramResult {
     if owner_is_the_same {
         let (index, mask) = EditionMarker::get_index_and_mask(print_edition.edition)?;
         edition_marker.ledger[index] ^= mask;
+    } else {
+        // The reprint record of the edition number, if passed as a remaining account,
+        // allows the master edition owner to reprint it.
+        record_burned_print(ctx, master_edition_mint_info, print_edition.edition)?;
     }
 
     // If the entire edition marker is empty, then we can close the account.
@@ -168,3 +177,61 @@ pub(crate) fn burn_nonfungible_edition(ctx: &Context<Burn>) -> ProgramResult {
 
     Ok(())
 }
+
+/// Creates the reprint record of a print edition burned from a wallet other than the
+/// master edition owner, when the record is passed as a remaining account.
+fn record_burned_print<'a>(
+    ctx: &Context<'a, Burn<'a>>,
+    master_edition_mint_info: &AccountInfo<'a>,
+    edition: u64,
+) -> ProgramResult {
+    let edition_str = edition.to_string();
+    let mut seeds = Vec::from([
+        PREFIX.as_bytes(),
+        crate::ID.as_ref(),
+        master_edition_mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+        edition_str.as_bytes(),
+        REPRINT_RECORD_SEED.as_bytes(),
+    ]);
+    let (pda_key, bump) = Pubkey::find_program_address(&seeds, &crate::ID);
+
+    let reprint_record_info = match ctx
+        .remaining_accounts
+        .iter()
+        .find(|account_info| *account_info.key == pda_key)
+    {
+        Some(account_info) => *account_info,
+        None => return Ok(()),
+    };
+
+    if !reprint_record_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    if !system_program::check_id(ctx.accounts.system_program_info.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let bump_seed = [bump];
+    seeds.push(&bump_seed);
+
+    create_or_allocate_account_raw(
+        crate::ID,
+        reprint_record_info,
+        ctx.accounts.system_program_info,
+        ctx.accounts.authority_info,
+        ReprintRecord::size(),
+        &seeds,
+    )?;
+
+    let record = ReprintRecord {
+        bump,
+        edition,
+        mint: *ctx.accounts.mint_info.key,
+        ..ReprintRecord::default()
+    };
+    record.serialize(&mut *reprint_record_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
@@ -11,8 +11,9 @@ use crate::{
         collection::{assert_collection_verify_is_valid, assert_has_collection_authority},
     },
     error::MetadataError,
+    processor::next_trailing_optional_account_info,
     state::{Collection, Metadata, TokenMetadataAccount},
-    utils::{clean_write_metadata, increment_collection_size},
+    utils::{clean_write_metadata, increment_collection_size, propagate_collection_size_change},
 };
 
 pub fn set_and_verify_sized_collection_item(
@@ -53,7 +54,12 @@ pub fn set_and_verify_sized_collection_item(
         return Err(MetadataError::UpdateAuthorityIncorrect.into());
     }
 
-    let delegated_collection_authority_opt = account_info_iter.next();
+    let delegated_collection_authority_opt = next_trailing_optional_account_info(account_info_iter);
+    let ancestors = [
+        next_trailing_optional_account_info(account_info_iter),
+        next_trailing_optional_account_info(account_info_iter),
+        next_trailing_optional_account_info(account_info_iter),
+    ];
 
     assert_has_collection_authority(
         collection_authority_info,
@@ -75,6 +81,7 @@ pub fn set_and_verify_sized_collection_item(
 
     // Update the collection size if this is a valid parent collection NFT.
     increment_collection_size(&mut collection_metadata, collection_info)?;
+    propagate_collection_size_change(program_id, &collection_metadata, &ancestors, true)?;
 
     clean_write_metadata(&mut metadata, metadata_info)
 }
@@ -11,8 +11,9 @@ use crate::{
         metadata::assert_metadata_derivation,
     },
     error::MetadataError,
+    processor::next_trailing_optional_account_info,
     state::{Metadata, TokenMetadataAccount},
-    utils::{clean_write_metadata, decrement_collection_size},
+    utils::{clean_write_metadata, decrement_collection_size, propagate_collection_size_change},
 };
 
 pub fn unverify_sized_collection_item(
@@ -81,7 +82,13 @@ pub fn unverify_sized_collection_item(
         // Now we can deserialize the collection metadata account.
         let mut collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
 
-        let delegated_collection_authority_opt = account_info_iter.next();
+        let delegated_collection_authority_opt =
+            next_trailing_optional_account_info(account_info_iter);
+        let ancestors = [
+            next_trailing_optional_account_info(account_info_iter),
+            next_trailing_optional_account_info(account_info_iter),
+            next_trailing_optional_account_info(account_info_iter),
+        ];
 
         assert_has_collection_authority(
             collection_authority_info,
@@ -90,6 +97,7 @@ pub fn unverify_sized_collection_item(
             delegated_collection_authority_opt,
         )?;
         decrement_collection_size(&mut collection_metadata, collection_metadata_info)?;
+        propagate_collection_size_change(program_id, &collection_metadata, &ancestors, false)?;
     }
 
     collection.verified = false;
@@ -11,8 +11,9 @@ use crate::{
         collection::{assert_collection_verify_is_valid, assert_has_collection_authority},
     },
     error::MetadataError,
+    processor::next_trailing_optional_account_info,
     state::{Metadata, TokenMetadataAccount},
-    utils::{clean_write_metadata, increment_collection_size},
+    utils::{clean_write_metadata, increment_collection_size, propagate_collection_size_change},
 };
 
 pub fn verify_sized_collection_item(
@@ -53,7 +54,12 @@ pub fn verify_sized_collection_item(
         edition_account_info,
     )?;
 
-    let delegated_collection_authority_opt = account_info_iter.next();
+    let delegated_collection_authority_opt = next_trailing_optional_account_info(account_info_iter);
+    let ancestors = [
+        next_trailing_optional_account_info(account_info_iter),
+        next_trailing_optional_account_info(account_info_iter),
+        next_trailing_optional_account_info(account_info_iter),
+    ];
 
     assert_has_collection_authority(
         collection_authority_info,
@@ -66,6 +72,7 @@ pub fn verify_sized_collection_item(
     // size on the Collection Parent.
     if let Some(collection) = &mut metadata.collection {
         increment_collection_size(&mut collection_metadata, collection_info)?;
+        propagate_collection_size_change(program_id, &collection_metadata, &ancestors, true)?;
 
         collection.verified = true;
         clean_write_metadata(&mut metadata, metadata_info)?;
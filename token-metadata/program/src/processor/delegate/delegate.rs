@@ -2,16 +2,23 @@ use std::fmt::Display;
 
 use borsh::BorshSerialize;
 use mpl_token_auth_rules::utils::get_latest_revision;
-use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use mpl_utils::{assert_signer, create_or_allocate_account_raw, token::unpack};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, program_option::COption,
-    program_pack::Pack, pubkey::Pubkey, system_program, sysvar,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_option::COption,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, Sysvar},
 };
-use spl_token::{instruction::AuthorityType as SplAuthorityType, state::Account};
+use spl_token::state::Account;
+use spl_token_2022::instruction::AuthorityType as SplAuthorityType;
 
 use crate::{
     assertions::{
-        assert_derivation, assert_keys_equal, assert_owned_by,
+        assert_derivation, assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
         metadata::assert_update_authority_is_correct,
     },
     error::MetadataError,
@@ -45,6 +52,8 @@ impl Display for DelegateScenario {
                 MetadataDelegateRole::ProgrammableConfigItem => {
                     "ProgrammableConfigItem".to_string()
                 }
+                MetadataDelegateRole::Attributes => "Attributes".to_string(),
+                MetadataDelegateRole::CollectionLock => "CollectionLock".to_string(),
             },
             Self::Token(role) => match role {
                 TokenDelegateRole::Sale => "Sale".to_string(),
@@ -52,6 +61,7 @@ impl Display for DelegateScenario {
                 TokenDelegateRole::Utility => "Utility".to_string(),
                 TokenDelegateRole::Staking => "Staking".to_string(),
                 TokenDelegateRole::LockedTransfer => "LockedTransfer".to_string(),
+                TokenDelegateRole::Rental => "Rental".to_string(),
                 _ => panic!("Invalid delegate role"),
             },
         };
@@ -102,6 +112,12 @@ pub fn delegate<'a>(
             amount,
             authorization_data,
         )),
+        // Rental
+        DelegateArgs::RentalV1 {
+            amount,
+            authorization_data,
+            ..
+        } => Some((TokenDelegateRole::Rental, amount, authorization_data)),
 
         // we don't need to fail if did not find a match at this point
         _ => None,
@@ -143,6 +159,17 @@ pub fn delegate<'a>(
             MetadataDelegateRole::ProgrammableConfigItem,
             authorization_data,
         )),
+        DelegateArgs::AttributesV1 { authorization_data } => {
+            Some((MetadataDelegateRole::Attributes, authorization_data))
+        }
+        DelegateArgs::CollectionLockV1 { authorization_data } => {
+            Some((MetadataDelegateRole::CollectionLock, authorization_data))
+        }
+        DelegateArgs::LimitedMetadataV1 {
+            role,
+            authorization_data,
+            ..
+        } => Some((*role, authorization_data)),
 
         // we don't need to fail if did not find a match at this point
         _ => None,
@@ -159,10 +186,13 @@ pub fn delegate<'a>(
 /// Creates a `DelegateRole::Collection` delegate.
 ///
 /// There can be multiple collections delegates set at any time.
+///
+/// When using `DelegateArgs::LimitedMetadataV1`, the delegate can be created with an
+/// expiry timestamp and/or a maximum number of uses.
 fn create_delegate_v1(
     program_id: &Pubkey,
     ctx: Context<Delegate>,
-    _args: DelegateArgs,
+    args: DelegateArgs,
     role: MetadataDelegateRole,
 ) -> ProgramResult {
     // signers
@@ -173,7 +203,7 @@ fn create_delegate_v1(
     // ownership
 
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
 
     // key match
 
@@ -200,6 +230,31 @@ fn create_delegate_v1(
         }
     };
 
+    // validates the (optional) delegate limits
+
+    let (expires_at, max_uses) = match args {
+        DelegateArgs::LimitedMetadataV1 {
+            expires_at,
+            max_uses,
+            ..
+        } => (expires_at, max_uses),
+        _ => (None, None),
+    };
+
+    if matches!(role, MetadataDelegateRole::Use) {
+        return Err(MetadataError::InvalidDelegateRole.into());
+    }
+
+    if let Some(expires_at) = expires_at {
+        if expires_at <= Clock::get()?.unix_timestamp {
+            return Err(MetadataError::InvalidDelegateArgs.into());
+        }
+    }
+
+    if max_uses == Some(0) {
+        return Err(MetadataError::InvalidDelegateArgs.into());
+    }
+
     // process the delegation creation (the derivation is checked
     // by the create helper)
 
@@ -214,6 +269,8 @@ fn create_delegate_v1(
         ctx.accounts.payer_info,
         ctx.accounts.system_program_info,
         &delegate_role,
+        expires_at,
+        max_uses,
     )
 }
 
@@ -253,8 +310,8 @@ fn create_persistent_delegate_v1(
     // ownership
 
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
-    assert_owned_by(token_info, &spl_token::ID)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+    assert_owned_by(token_info, ctx.accounts.mint_info.owner)?;
 
     // key match
 
@@ -263,7 +320,8 @@ fn create_persistent_delegate_v1(
         ctx.accounts.sysvar_instructions_info.key,
         &sysvar::instructions::ID,
     )?;
-    assert_keys_equal(spl_token_program_info.key, &spl_token::ID)?;
+    // the token program must be the owner of the mint (SPL Token or Token-2022)
+    assert_keys_equal(spl_token_program_info.key, ctx.accounts.mint_info.owner)?;
 
     // account relationships
 
@@ -274,7 +332,7 @@ fn create_persistent_delegate_v1(
 
     // authority must be the owner of the token account: spl-token required the
     // token owner to set a delegate
-    let token = Account::unpack(&token_info.try_borrow_data()?).unwrap();
+    let token: Account = unpack(&token_info.try_borrow_data()?)?;
     if token.owner != *ctx.accounts.authority_info.key {
         return Err(MetadataError::IncorrectOwner.into());
     }
@@ -283,11 +341,28 @@ fn create_persistent_delegate_v1(
 
     // programmables assets can have delegates from any role apart from `Standard`
     match metadata.token_standard {
-        Some(TokenStandard::ProgrammableNonFungible) => {
+        Some(TokenStandard::ProgrammableNonFungible)
+        | Some(TokenStandard::ProgrammableFungibleAsset)
+        | Some(TokenStandard::ProgrammableNonTransferable) => {
             if matches!(role, TokenDelegateRole::Standard) {
                 return Err(MetadataError::InvalidDelegateRole.into());
             }
 
+            // non-transferable assets cannot have delegates that are able to transfer them
+            if matches!(
+                metadata.token_standard,
+                Some(TokenStandard::ProgrammableNonTransferable)
+            ) && matches!(
+                role,
+                TokenDelegateRole::Sale
+                    | TokenDelegateRole::Transfer
+                    | TokenDelegateRole::LockedTransfer
+                    | TokenDelegateRole::Migration
+                    | TokenDelegateRole::Rental
+            ) {
+                return Err(MetadataError::InvalidDelegateRole.into());
+            }
+
             let (mut token_record, token_record_info) = match ctx.accounts.token_record_info {
                 Some(token_record_info) => {
                     let (pda_key, _) =
@@ -380,6 +455,22 @@ fn create_persistent_delegate_v1(
                 None
             };
 
+            // the token owner is the lender of a rental; the rental expiry must be
+            // in the future
+            (token_record.rental_expiry, token_record.lender) =
+                if matches!(role, TokenDelegateRole::Rental) {
+                    if let DelegateArgs::RentalV1 { expiry, .. } = args {
+                        if *expiry <= Clock::get()?.unix_timestamp {
+                            return Err(MetadataError::InvalidDelegateArgs.into());
+                        }
+                        (Some(*expiry), Some(token.owner))
+                    } else {
+                        return Err(MetadataError::InvalidDelegateArgs.into());
+                    }
+                } else {
+                    (None, None)
+                };
+
             token_record.delegate = Some(*ctx.accounts.delegate_info.key);
             token_record.delegate_role = Some(role);
             token_record.save(
@@ -410,7 +501,7 @@ fn create_persistent_delegate_v1(
 
     // creates the spl-token delegate
     invoke(
-        &spl_token::instruction::approve(
+        &spl_token_2022::instruction::approve(
             spl_token_program_info.key,
             token_info.key,
             ctx.accounts.delegate_info.key,
@@ -442,7 +533,7 @@ fn create_persistent_delegate_v1(
             }
         } else {
             invoke(
-                &spl_token::instruction::set_authority(
+                &spl_token_2022::instruction::set_authority(
                     spl_token_program_info.key,
                     token_info.key,
                     Some(master_edition_info.key),
@@ -462,6 +553,8 @@ fn create_persistent_delegate_v1(
     if matches!(
         metadata.token_standard,
         Some(TokenStandard::ProgrammableNonFungible)
+            | Some(TokenStandard::ProgrammableFungibleAsset)
+            | Some(TokenStandard::ProgrammableNonTransferable)
     ) {
         if let Some(master_edition_info) = ctx.accounts.master_edition_info {
             freeze(
@@ -489,6 +582,8 @@ fn create_pda_account<'a>(
     payer_info: &'a AccountInfo<'a>,
     system_program_info: &'a AccountInfo<'a>,
     delegate_role: &str,
+    expires_at: Option<i64>,
+    max_uses: Option<u64>,
 ) -> ProgramResult {
     // validates the delegate derivation
 
@@ -527,6 +622,8 @@ fn create_pda_account<'a>(
         mint: *mint_info.key,
         delegate: *delegate_info.key,
         update_authority: *authority_info.key,
+        expires_at,
+        remaining_uses: max_uses,
         ..Default::default()
     };
     pda.serialize(&mut *delegate_record_info.try_borrow_mut_data()?)?;
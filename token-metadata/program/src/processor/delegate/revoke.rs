@@ -1,13 +1,21 @@
-use mpl_utils::{assert_signer, close_account_raw, cmp_pubkeys};
+use mpl_utils::{assert_signer, close_account_raw, cmp_pubkeys, token::unpack};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, program_option::COption,
-    program_pack::Pack, pubkey::Pubkey, system_program, sysvar,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{self, Sysvar},
 };
 use spl_token::state::Account;
 
 use crate::{
     assertions::{
-        assert_keys_equal, assert_owned_by, metadata::assert_update_authority_is_correct,
+        assert_keys_equal, assert_owned_by, assert_owned_by_token_program,
+        metadata::assert_update_authority_is_correct,
     },
     error::MetadataError,
     instruction::{Context, MetadataDelegateRole, Revoke, RevokeArgs},
@@ -43,6 +51,8 @@ pub fn revoke<'a>(
         RevokeArgs::LockedTransferV1 => Some(TokenDelegateRole::LockedTransfer),
         // Migration
         RevokeArgs::MigrationV1 => Some(TokenDelegateRole::Migration),
+        // Rental
+        RevokeArgs::RentalV1 => Some(TokenDelegateRole::Rental),
         // we don't need to fail if did not find a match at this point
         _ => None,
     };
@@ -61,6 +71,8 @@ pub fn revoke<'a>(
         RevokeArgs::DataItemV1 => Some(MetadataDelegateRole::DataItem),
         RevokeArgs::CollectionItemV1 => Some(MetadataDelegateRole::CollectionItem),
         RevokeArgs::ProgrammableConfigItemV1 => Some(MetadataDelegateRole::ProgrammableConfigItem),
+        RevokeArgs::AttributesV1 => Some(MetadataDelegateRole::Attributes),
+        RevokeArgs::CollectionLockV1 => Some(MetadataDelegateRole::CollectionLock),
         // we don't need to fail if did not find a match at this point
         _ => None,
     };
@@ -73,9 +85,9 @@ pub fn revoke<'a>(
     Err(MetadataError::InvalidDelegateArgs.into())
 }
 
-fn revoke_delegate_v1(
+fn revoke_delegate_v1<'a>(
     program_id: &Pubkey,
-    ctx: Context<Revoke>,
+    ctx: Context<'a, Revoke<'a>>,
     role: MetadataDelegateRole,
 ) -> ProgramResult {
     // signers
@@ -86,7 +98,7 @@ fn revoke_delegate_v1(
     // ownership
 
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
 
     // key match
 
@@ -106,17 +118,19 @@ fn revoke_delegate_v1(
     };
 
     let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
-    // there are two scenarios here:
+    // there are three scenarios here:
     //   1. authority is equal to delegate: delegate as a signer is self-revoking
-    //   2. otherwise we need the update authority as a signer
-    let approver = if cmp_pubkeys(
+    //   2. the delegate has expired or has no remaining uses: anyone can close it, but
+    //      the rent goes back to the approver
+    //   3. otherwise we need the update authority as a signer
+    let (approver, destination_info) = if cmp_pubkeys(
         ctx.accounts.delegate_info.key,
         ctx.accounts.authority_info.key,
     ) {
         match MetadataDelegateRecord::from_account_info(delegate_record_info) {
             Ok(delegate_record) => {
                 if cmp_pubkeys(&delegate_record.delegate, ctx.accounts.authority_info.key) {
-                    delegate_record.update_authority
+                    (delegate_record.update_authority, ctx.accounts.payer_info)
                 } else {
                     return Err(MetadataError::InvalidDelegate.into());
                 }
@@ -125,9 +139,18 @@ fn revoke_delegate_v1(
                 return Err(MetadataError::DelegateNotFound.into());
             }
         }
+    } else if let Some(delegate_record) = inactive_delegate_record(delegate_record_info)? {
+        // the derivation of the record (approver and delegate) is checked when closing it;
+        // the approver account is either the payer or passed as a remaining account
+        let approver_info = std::iter::once(ctx.accounts.payer_info)
+            .chain(ctx.remaining_accounts.iter().copied())
+            .find(|info| cmp_pubkeys(info.key, &delegate_record.update_authority))
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        (delegate_record.update_authority, approver_info)
     } else {
         assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
-        *ctx.accounts.authority_info.key
+        (*ctx.accounts.authority_info.key, ctx.accounts.payer_info)
     };
 
     if metadata.mint != *ctx.accounts.mint_info.key {
@@ -142,7 +165,7 @@ fn revoke_delegate_v1(
         ctx.accounts.delegate_info.key,
         ctx.accounts.mint_info.key,
         &approver,
-        ctx.accounts.payer_info,
+        destination_info,
     )
 }
 
@@ -175,8 +198,8 @@ fn revoke_persistent_delegate_v1(
     // ownership
 
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
-    assert_owned_by(token_info, &spl_token::ID)?;
+    assert_owned_by_token_program(ctx.accounts.mint_info)?;
+    assert_owned_by(token_info, ctx.accounts.mint_info.owner)?;
 
     // key match
 
@@ -185,7 +208,8 @@ fn revoke_persistent_delegate_v1(
         ctx.accounts.sysvar_instructions_info.key,
         &sysvar::instructions::ID,
     )?;
-    assert_keys_equal(spl_token_program_info.key, &spl_token::ID)?;
+    // the token program must be the owner of the mint (SPL Token or Token-2022)
+    assert_keys_equal(spl_token_program_info.key, ctx.accounts.mint_info.owner)?;
 
     // account relationships
 
@@ -196,7 +220,7 @@ fn revoke_persistent_delegate_v1(
 
     // authority must be the owner of the token account: spl-token required the
     // token owner to revoke a delegate
-    let token = Account::unpack(&token_info.try_borrow_data()?).unwrap();
+    let token: Account = unpack(&token_info.try_borrow_data()?)?;
     if token.owner != *ctx.accounts.authority_info.key {
         return Err(MetadataError::IncorrectOwner.into());
     }
@@ -213,7 +237,9 @@ fn revoke_persistent_delegate_v1(
 
     // programmables assets can have delegates from any role apart from `Standard`
     match metadata.token_standard {
-        Some(TokenStandard::ProgrammableNonFungible) => {
+        Some(TokenStandard::ProgrammableNonFungible)
+        | Some(TokenStandard::ProgrammableFungibleAsset)
+        | Some(TokenStandard::ProgrammableNonTransferable) => {
             if matches!(role, TokenDelegateRole::Standard) {
                 return Err(MetadataError::InvalidDelegateRole.into());
             }
@@ -237,6 +263,12 @@ fn revoke_persistent_delegate_v1(
                 }
             };
 
+            // the borrower of a rented token cannot remove the rental; the token returns
+            // to the lender through a transfer once the rental expires
+            if token_record.is_rented(&token.owner) {
+                return Err(MetadataError::TokenIsRented.into());
+            }
+
             if let Some(delegate) = token_record.delegate {
                 assert_keys_equal(&delegate, ctx.accounts.delegate_info.key)?;
 
@@ -289,7 +321,7 @@ fn revoke_persistent_delegate_v1(
 
     // revokes the spl-token delegate
     invoke(
-        &spl_token::instruction::revoke(
+        &spl_token_2022::instruction::revoke(
             spl_token_program_info.key,
             token_info.key,
             ctx.accounts.authority_info.key,
@@ -301,6 +333,8 @@ fn revoke_persistent_delegate_v1(
     if matches!(
         metadata.token_standard,
         Some(TokenStandard::ProgrammableNonFungible)
+            | Some(TokenStandard::ProgrammableFungibleAsset)
+            | Some(TokenStandard::ProgrammableNonTransferable)
     ) {
         if let Some(master_edition_info) = ctx.accounts.master_edition_info {
             freeze(
@@ -319,17 +353,38 @@ fn revoke_persistent_delegate_v1(
     Ok(())
 }
 
+/// Returns the delegate record if it has expired or has no remaining uses.
+fn inactive_delegate_record(
+    delegate_record_info: &AccountInfo,
+) -> Result<Option<MetadataDelegateRecord>, ProgramError> {
+    if delegate_record_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let delegate_record = MetadataDelegateRecord::from_account_info(delegate_record_info)?;
+
+    let inactive = delegate_record.is_exhausted()
+        || (delegate_record.expires_at.is_some()
+            && delegate_record.is_expired(Clock::get()?.unix_timestamp));
+
+    Ok(if inactive {
+        Some(delegate_record)
+    } else {
+        None
+    })
+}
+
 /// Closes a delegate PDA.
 ///
 /// It checks that the derivation is correct before closing
-/// the delegate record account.
+/// the delegate record account; the rent is sent to `destination_info`.
 fn close_delegate_record<'a>(
     role: MetadataDelegateRole,
     delegate_record_info: &'a AccountInfo<'a>,
     delegate: &Pubkey,
     mint: &Pubkey,
     approver: &Pubkey,
-    payer_info: &'a AccountInfo<'a>,
+    destination_info: &'a AccountInfo<'a>,
 ) -> ProgramResult {
     if delegate_record_info.data_is_empty() {
         return Err(MetadataError::Uninitialized.into());
@@ -341,6 +396,6 @@ fn close_delegate_record<'a>(
         Err(MetadataError::DerivedKeyInvalid.into())
     } else {
         // closes the delegate account
-        close_account_raw(payer_info, delegate_record_info)
+        close_account_raw(destination_info, delegate_record_info)
     }
 }
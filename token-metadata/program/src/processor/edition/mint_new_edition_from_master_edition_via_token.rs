@@ -2,6 +2,8 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
+    rent::Rent,
+    sysvar::SysvarId,
 };
 
 use crate::utils::{
@@ -15,7 +17,7 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
     edition: u64,
     ignore_owner_signer: bool,
 ) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
+    let account_info_iter = &mut accounts.iter().peekable();
 
     let new_metadata_account_info = next_account_info(account_info_iter)?;
     let new_edition_account_info = next_account_info(account_info_iter)?;
@@ -31,6 +33,12 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
     let token_program_account_info = next_account_info(account_info_iter)?;
     let system_account_info = next_account_info(account_info_iter)?;
 
+    // consume the next account only if it is Rent
+    account_info_iter.next_if(|info| info.key == &Rent::id());
+
+    // the edition URI template account is optional
+    let edition_uri_template_info = next_account_info(account_info_iter).ok();
+
     process_mint_new_edition_from_master_edition_via_token_logic(
         program_id,
         MintNewEditionFromMasterEditionViaTokenLogicArgs {
@@ -47,6 +55,7 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
             master_metadata_account_info,
             token_program_account_info,
             system_account_info,
+            edition_uri_template_info,
         },
         edition,
         ignore_owner_signer,
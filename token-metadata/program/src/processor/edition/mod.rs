@@ -1,7 +1,15 @@
 mod convert_master_edition_v1_to_v2;
 mod create_master_edition_v3;
 mod mint_new_edition_from_master_edition_via_token;
+mod reprint_edition;
+mod set_edition_numbering;
+mod set_edition_uri;
+mod set_edition_uri_template;
 
 pub use convert_master_edition_v1_to_v2::*;
 pub use create_master_edition_v3::*;
 pub use mint_new_edition_from_master_edition_via_token::*;
+pub use reprint_edition::*;
+pub use set_edition_numbering::*;
+pub use set_edition_uri::*;
+pub use set_edition_uri_template::*;
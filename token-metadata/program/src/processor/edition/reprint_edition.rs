@@ -0,0 +1,164 @@
+use mpl_utils::assert_signer;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+use spl_token::state::Account;
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_initialized, assert_owned_by,
+        assert_token_program_matches_package,
+    },
+    error::MetadataError,
+    state::{
+        EditionMarker, Key, Metadata, ReprintRecord, TokenMetadataAccount, EDITION,
+        EDITION_MARKER_BIT_SIZE, PREFIX, REPRINT_RECORD_SEED,
+    },
+    utils::{apply_edition_uri_template, close_program_account, mint_limited_edition},
+};
+
+/// Mints a new edition into the number of a burned print edition.
+///
+/// Burning a print edition from a wallet other than the master edition owner keeps the
+/// edition number taken on the edition marker, so it cannot be printed again through
+/// `MintNewEditionFromMasterEditionViaToken`. The burn is proven by the reprint record of
+/// the edition number, created when the print is burned. The record is closed once the
+/// reprint is complete, so each burned print can be reprinted only once.
+pub fn process_reprint_edition<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    edition: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let new_metadata_account_info = next_account_info(account_info_iter)?;
+    let new_edition_account_info = next_account_info(account_info_iter)?;
+    let master_edition_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_marker_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let payer_account_info = next_account_info(account_info_iter)?;
+    let owner_account_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let master_metadata_account_info = next_account_info(account_info_iter)?;
+    let reprint_record_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    // the edition URI template account is optional
+    let edition_uri_template_info = next_account_info(account_info_iter).ok();
+
+    assert_token_program_matches_package(token_program_account_info)?;
+    assert_owned_by(mint_info, &spl_token::ID)?;
+    assert_owned_by(token_account_info, &spl_token::ID)?;
+    assert_owned_by(master_edition_account_info, program_id)?;
+    assert_owned_by(master_metadata_account_info, program_id)?;
+
+    // only the holder of the master edition can reprint editions
+
+    let mut master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
+    let token_account: Account = assert_initialized(token_account_info)?;
+
+    assert_signer(owner_account_info)?;
+
+    if token_account.owner != *owner_account_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    if token_account.mint != master_metadata.mint {
+        return Err(MetadataError::TokenAccountMintMismatchV2.into());
+    }
+
+    if token_account.amount < 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    if !new_metadata_account_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    if !new_edition_account_info.data_is_empty() {
+        return Err(MetadataError::AlreadyInitialized.into());
+    }
+
+    // the edition number must have been printed
+
+    let edition_number = edition
+        .checked_div(EDITION_MARKER_BIT_SIZE)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    let as_string = edition_number.to_string();
+
+    assert_derivation(
+        program_id,
+        edition_marker_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+            as_string.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidEditionMarker)?;
+
+    if edition_marker_info.data_is_empty() {
+        return Err(MetadataError::EditionNumberNotPrinted.into());
+    }
+
+    let edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
+
+    if !edition_marker.edition_taken(edition)? {
+        return Err(MetadataError::EditionNumberNotPrinted.into());
+    }
+
+    // the burned print must have been recorded by the burn
+
+    let edition_str = edition.to_string();
+
+    assert_derivation(
+        program_id,
+        reprint_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+            edition_str.as_bytes(),
+            REPRINT_RECORD_SEED.as_bytes(),
+        ],
+    )?;
+
+    if reprint_record_info.data_is_empty() {
+        return Err(MetadataError::PrintEditionNotBurned.into());
+    }
+
+    let reprint_record = ReprintRecord::from_account_info(reprint_record_info)?;
+
+    if reprint_record.edition != edition {
+        return Err(MetadataError::PrintEditionNotBurned.into());
+    }
+
+    apply_edition_uri_template(&mut master_metadata, edition_uri_template_info, edition)?;
+
+    mint_limited_edition(
+        program_id,
+        master_metadata,
+        new_metadata_account_info,
+        new_edition_account_info,
+        master_edition_account_info,
+        mint_info,
+        mint_authority_info,
+        payer_account_info,
+        update_authority_info,
+        token_program_account_info,
+        system_account_info,
+        None,
+        Some(edition),
+    )?;
+
+    // closes the reprint record so the burned print cannot be used again
+
+    close_program_account(reprint_record_info, payer_account_info, Key::ReprintRecord)
+}
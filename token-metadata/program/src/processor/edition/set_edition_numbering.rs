@@ -0,0 +1,82 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_owned_by, metadata::assert_update_authority_is_correct,
+    },
+    error::MetadataError,
+    instruction::{Context, SetEditionNumbering, SetEditionNumberingArgs},
+    pda::{EDITION, PREFIX},
+    state::{
+        EditionNumbering, EditionNumberingMode, MasterEditionV2, Metadata, ReservedRange,
+        TokenMetadataAccount,
+    },
+};
+
+/// Sets the numbering policy of the print editions of a master edition.
+pub fn set_edition_numbering<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetEditionNumberingArgs,
+) -> ProgramResult {
+    let context = SetEditionNumbering::to_context(accounts)?;
+
+    match args {
+        SetEditionNumberingArgs::V1 {
+            mode,
+            reserved_ranges,
+        } => set_edition_numbering_v1(program_id, context, mode, reserved_ranges),
+    }
+}
+
+fn set_edition_numbering_v1(
+    program_id: &Pubkey,
+    ctx: Context<SetEditionNumbering>,
+    mode: EditionNumberingMode,
+    reserved_ranges: Vec<ReservedRange>,
+) -> ProgramResult {
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.master_edition_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidMasterEdition)?;
+    // only master edition v2 accounts can print editions
+    let master_edition = MasterEditionV2::from_account_info(ctx.accounts.master_edition_info)?;
+
+    // authority must be the update authority (this also checks that it is a signer)
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    // the sequence carries on from the current supply unless it was already sequential
+    let next_edition =
+        match EditionNumbering::from_master_edition(ctx.accounts.master_edition_info)? {
+            Some(numbering) if numbering.mode == EditionNumberingMode::Sequential => {
+                numbering.next_edition
+            }
+            _ => master_edition
+                .supply
+                .checked_add(1)
+                .ok_or(MetadataError::NumericalOverflowError)?,
+        };
+
+    let numbering = EditionNumbering {
+        mode,
+        next_edition,
+        reserved_ranges,
+    };
+    numbering.validate(master_edition.max_supply)?;
+
+    numbering.save(ctx.accounts.master_edition_info)
+}
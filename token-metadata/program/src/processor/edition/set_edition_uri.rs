@@ -0,0 +1,99 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_owned_by, metadata::assert_update_authority_is_correct,
+    },
+    error::MetadataError,
+    instruction::{Context, SetEditionUri, SetEditionUriArgs},
+    pda::{EDITION, PREFIX},
+    state::{Edition, Metadata, TokenMetadataAccount, MAX_URI_LENGTH},
+    utils::{clean_write_metadata, puffed_out_string, record_metadata_history},
+};
+
+/// Sets the URI of a print edition.
+///
+/// Print editions are immutable, so the update authority of the master edition is the only
+/// one that can change their URI, as long as the master edition is mutable.
+pub fn set_edition_uri<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetEditionUriArgs,
+) -> ProgramResult {
+    let context = SetEditionUri::to_context(accounts)?;
+
+    match args {
+        SetEditionUriArgs::V1 { uri } => set_edition_uri_v1(program_id, context, uri),
+    }
+}
+
+fn set_edition_uri_v1(
+    program_id: &Pubkey,
+    ctx: Context<SetEditionUri>,
+    uri: String,
+) -> ProgramResult {
+    if uri.len() > MAX_URI_LENGTH {
+        return Err(MetadataError::UriTooLong.into());
+    }
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.edition_info, program_id)?;
+    assert_owned_by(ctx.accounts.master_edition_info, program_id)?;
+    assert_owned_by(ctx.accounts.master_metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    let master_metadata = Metadata::from_account_info(ctx.accounts.master_metadata_info)?;
+
+    // master and print editions are valid PDAs for their given mints
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidMasterEdition)?;
+
+    assert_derivation(
+        program_id,
+        ctx.accounts.edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidPrintEdition)?;
+
+    let print_edition = Edition::from_account_info(ctx.accounts.edition_info)
+        .map_err(|_| MetadataError::NotAPrintEdition)?;
+
+    // the print edition actually belongs to the master edition
+    if print_edition.parent != *ctx.accounts.master_edition_info.key {
+        return Err(MetadataError::PrintEditionDoesNotMatchMasterEdition.into());
+    }
+
+    // authority must be the update authority of the master edition (this also checks
+    // that it is a signer)
+    assert_update_authority_is_correct(&master_metadata, ctx.accounts.authority_info)?;
+
+    if !master_metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    metadata.data.uri = puffed_out_string(&uri, MAX_URI_LENGTH);
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    record_metadata_history(
+        &metadata,
+        ctx.accounts.authority_info.key,
+        ctx.accounts.metadata_history_info,
+    )
+}
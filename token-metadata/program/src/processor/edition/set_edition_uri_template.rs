@@ -0,0 +1,110 @@
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_keys_equal, assert_owned_by,
+        metadata::assert_update_authority_is_correct,
+    },
+    error::MetadataError,
+    instruction::{Context, SetEditionUriTemplate, SetEditionUriTemplateArgs},
+    pda::{find_edition_uri_template_account, EDITION, PREFIX},
+    state::{
+        get_master_edition, EditionUriTemplate, Metadata, TokenMetadataAccount,
+        EDITION_URI_TEMPLATE_SEED, EDITION_URI_TEMPLATE_SIZE,
+    },
+};
+
+/// Creates or updates the edition URI template of a master edition.
+pub fn set_edition_uri_template<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetEditionUriTemplateArgs,
+) -> ProgramResult {
+    let context = SetEditionUriTemplate::to_context(accounts)?;
+
+    match args {
+        SetEditionUriTemplateArgs::V1 { uri_template } => {
+            set_edition_uri_template_v1(program_id, context, uri_template)
+        }
+    }
+}
+
+fn set_edition_uri_template_v1(
+    program_id: &Pubkey,
+    ctx: Context<SetEditionUriTemplate>,
+    uri_template: String,
+) -> ProgramResult {
+    EditionUriTemplate::validate(&uri_template)?;
+
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.master_edition_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    // the template belongs to the master edition of the asset
+    assert_derivation(
+        program_id,
+        ctx.accounts.master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidMasterEdition)?;
+    get_master_edition(ctx.accounts.master_edition_info)?;
+
+    let (template_key, bump) = find_edition_uri_template_account(&metadata.mint);
+    assert_keys_equal(&template_key, ctx.accounts.edition_uri_template_info.key)?;
+
+    // authority must be the update authority (this also checks that it is a signer)
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    let mut template = if ctx.accounts.edition_uri_template_info.data_is_empty() {
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION_URI_TEMPLATE_SEED.as_bytes(),
+            &[bump],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.edition_uri_template_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            EDITION_URI_TEMPLATE_SIZE,
+            &signer_seeds,
+        )?;
+
+        EditionUriTemplate {
+            bump,
+            mint: metadata.mint,
+            ..Default::default()
+        }
+    } else {
+        EditionUriTemplate::from_account_info(ctx.accounts.edition_uri_template_info)?
+    };
+
+    template.uri_template = uri_template;
+    template.save(ctx.accounts.edition_uri_template_info)
+}
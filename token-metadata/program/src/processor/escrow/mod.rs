@@ -1,9 +1,11 @@
 mod close_escrow_account;
 mod create_escrow_account;
 mod pda;
+mod transfer;
 mod transfer_out;
 
 pub use close_escrow_account::*;
 pub use create_escrow_account::*;
 pub use pda::*;
+pub use transfer::*;
 pub use transfer_out::*;
@@ -0,0 +1,172 @@
+use mpl_utils::{
+    assert_signer,
+    token::{spl_token_close, unpack, TokenCloseParams},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+use spl_token::state::Account;
+
+use super::find_escrow_seeds;
+use crate::{
+    assertions::{assert_derivation, assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::{Context, EscrowTransferIn, EscrowTransferOut, Transfer, TransferArgs},
+    processor::metadata::transfer_v1,
+    state::{EscrowAuthority, TokenMetadataAccount, TokenOwnedEscrow},
+};
+
+/// Transfers an asset into an escrow.
+pub fn escrow_transfer_in<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: TransferArgs,
+) -> ProgramResult {
+    let ctx = EscrowTransferIn::to_context(accounts)?;
+
+    assert_escrow(ctx.accounts.escrow_info, ctx.accounts.escrow_mint_info)?;
+
+    if ctx.accounts.mint_info.key == ctx.accounts.escrow_mint_info.key {
+        return Err(MetadataError::EscrowCannotHoldItself.into());
+    }
+
+    let accounts = ctx.accounts;
+
+    // the escrow is the owner of the destination token account
+    let transfer_ctx = Context {
+        accounts: Transfer {
+            token_info: accounts.token_info,
+            token_owner_info: accounts.token_owner_info,
+            destination_info: accounts.destination_info,
+            destination_owner_info: accounts.escrow_info,
+            mint_info: accounts.mint_info,
+            metadata_info: accounts.metadata_info,
+            edition_info: accounts.edition_info,
+            owner_token_record_info: accounts.owner_token_record_info,
+            destination_token_record_info: accounts.destination_token_record_info,
+            authority_info: accounts.authority_info,
+            payer_info: accounts.payer_info,
+            system_program_info: accounts.system_program_info,
+            sysvar_instructions_info: accounts.sysvar_instructions_info,
+            spl_token_program_info: accounts.spl_token_program_info,
+            spl_ata_program_info: accounts.spl_ata_program_info,
+            authorization_rules_program_info: accounts.authorization_rules_program_info,
+            authorization_rules_info: accounts.authorization_rules_info,
+        },
+        remaining_accounts: ctx.remaining_accounts,
+    };
+
+    transfer_v1(program_id, transfer_ctx, args, None)
+}
+
+/// Transfers an asset out of an escrow, with the escrow signing as the token owner.
+pub fn escrow_transfer_out<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: TransferArgs,
+) -> ProgramResult {
+    let ctx = EscrowTransferOut::to_context(accounts)?;
+
+    let toe = assert_escrow(ctx.accounts.escrow_info, ctx.accounts.escrow_mint_info)?;
+
+    // the authority must be the escrow authority: the holder of the token the escrow
+    // is attached to or the creator of the escrow
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_owned_by(ctx.accounts.escrow_token_info, &spl_token::ID)?;
+
+    let escrow_token = Account::unpack(&ctx.accounts.escrow_token_info.try_borrow_data()?)?;
+
+    if escrow_token.mint != *ctx.accounts.escrow_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if escrow_token.amount != 1 {
+        return Err(MetadataError::InsufficientTokenBalance.into());
+    }
+
+    let escrow_authority = match toe.authority {
+        EscrowAuthority::TokenOwner => escrow_token.owner,
+        EscrowAuthority::Creator(creator) => creator,
+    };
+
+    if escrow_authority != *ctx.accounts.authority_info.key {
+        return Err(MetadataError::MustBeEscrowAuthority.into());
+    }
+
+    let bump = [toe.bump];
+    let mut escrow_signer_seeds =
+        find_escrow_seeds(ctx.accounts.escrow_mint_info.key, &toe.authority);
+    escrow_signer_seeds.push(&bump);
+
+    let accounts = ctx.accounts;
+
+    // the escrow is the owner of the source token account and the transfer authority
+    let transfer_ctx = Context {
+        accounts: Transfer {
+            token_info: accounts.token_info,
+            token_owner_info: accounts.escrow_info,
+            destination_info: accounts.destination_info,
+            destination_owner_info: accounts.destination_owner_info,
+            mint_info: accounts.mint_info,
+            metadata_info: accounts.metadata_info,
+            edition_info: accounts.edition_info,
+            owner_token_record_info: accounts.owner_token_record_info,
+            destination_token_record_info: accounts.destination_token_record_info,
+            authority_info: accounts.escrow_info,
+            payer_info: accounts.payer_info,
+            system_program_info: accounts.system_program_info,
+            sysvar_instructions_info: accounts.sysvar_instructions_info,
+            spl_token_program_info: accounts.spl_token_program_info,
+            spl_ata_program_info: accounts.spl_ata_program_info,
+            authorization_rules_program_info: accounts.authorization_rules_program_info,
+            authorization_rules_info: accounts.authorization_rules_info,
+        },
+        remaining_accounts: ctx.remaining_accounts,
+    };
+
+    transfer_v1(
+        program_id,
+        transfer_ctx,
+        args,
+        Some(escrow_signer_seeds.as_slice()),
+    )?;
+
+    // closes the escrow token account once it is empty and returns the rent to the payer
+
+    let token = unpack::<Account>(&accounts.token_info.try_borrow_data()?)?;
+
+    if token.amount == 0 {
+        spl_token_close(TokenCloseParams {
+            account: accounts.token_info.clone(),
+            destination: accounts.payer_info.clone(),
+            owner: accounts.escrow_info.clone(),
+            authority_signer_seeds: Some(escrow_signer_seeds.as_slice()),
+            token_program: accounts.spl_token_program_info.clone(),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Validates the escrow account against the mint it is attached to.
+fn assert_escrow(
+    escrow_info: &AccountInfo,
+    escrow_mint_info: &AccountInfo,
+) -> Result<TokenOwnedEscrow, ProgramError> {
+    assert_owned_by(escrow_info, &crate::ID)?;
+    assert_owned_by(escrow_mint_info, &spl_token::ID)?;
+
+    let toe = TokenOwnedEscrow::from_account_info(escrow_info)?;
+    assert_keys_equal(&toe.base_token, escrow_mint_info.key)?;
+
+    let escrow_seeds = find_escrow_seeds(escrow_mint_info.key, &toe.authority);
+    let bump = assert_derivation(&crate::ID, escrow_info, &escrow_seeds)?;
+
+    if bump != toe.bump {
+        return Err(MetadataError::InvalidEscrowBumpSeed.into());
+    }
+
+    Ok(toe)
+}
@@ -1,14 +1,23 @@
 use mpl_utils::assert_signer;
 use num_traits::FromPrimitive;
-use solana_program::{account_info::next_account_info, rent::Rent, system_program, sysvar::Sysvar};
+use solana_program::{
+    account_info::next_account_info, program::set_return_data, rent::Rent, system_program,
+    sysvar::Sysvar,
+};
 
 use crate::{
-    state::{fee::FEE_AUTHORITY, MAX_METADATA_LEN},
+    state::fee::{split_fee_balance, FEE_AUTHORITY},
     utils::fee::clear_fee_flag,
 };
 
 use super::*;
 
+/// Collects the fees from the accounts passed after the recipient. Metadata, edition and
+/// master edition accounts can be collected from in any combination, so the fees of specific
+/// account types can be swept by only passing accounts of those types.
+///
+/// The total amount of lamports collected is logged and set as the return data (as a
+/// little-endian `u64`).
 pub(crate) fn process_collect_fees(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -22,18 +31,38 @@ pub(crate) fn process_collect_fees(program_id: &Pubkey, accounts: &[AccountInfo]
 
     let recipient_info = next_account_info(account_info_iter)?;
 
+    let rent = Rent::get()?;
+    let mut total: u64 = 0;
+    let mut count: usize = 0;
+
     for account_info in account_info_iter {
         if account_info.owner != program_id {
             return Err(MetadataError::InvalidFeeAccount.into());
         }
 
-        collect_fee_from_account(account_info, recipient_info)?;
+        let fee_amount = collect_fee_from_account(account_info, recipient_info, &rent)?;
+
+        total = total
+            .checked_add(fee_amount)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+        count += 1;
     }
 
+    msg!(
+        "Collected {} lamports in fees from {} accounts",
+        total,
+        count
+    );
+    set_return_data(&total.to_le_bytes());
+
     Ok(())
 }
 
-fn collect_fee_from_account(account_info: &AccountInfo, dest_info: &AccountInfo) -> ProgramResult {
+fn collect_fee_from_account(
+    account_info: &AccountInfo,
+    dest_info: &AccountInfo,
+    rent: &Rent,
+) -> Result<u64, ProgramError> {
     // Scope refcell borrow
     let account_key = {
         let data = account_info.data.borrow();
@@ -44,25 +73,11 @@ fn collect_fee_from_account(account_info: &AccountInfo, dest_info: &AccountInfo)
         FromPrimitive::from_u8(*key_byte).ok_or(MetadataError::InvalidFeeAccount)?
     };
 
-    let rent = Rent::get()?;
-    let metadata_rent = rent.minimum_balance(MAX_METADATA_LEN);
-
-    let (fee_amount, rent_amount) = match account_key {
-        Key::Uninitialized => {
-            account_info.assign(&system_program::ID);
+    let (fee_amount, rent_amount) = split_fee_balance(account_key, account_info.lamports(), rent)?;
 
-            (account_info.lamports(), 0)
-        }
-        Key::MetadataV1 => {
-            let fee_amount = account_info
-                .lamports()
-                .checked_sub(metadata_rent)
-                .ok_or(MetadataError::NumericalOverflowError)?;
-
-            (fee_amount, metadata_rent)
-        }
-        _ => return Err(MetadataError::InvalidFeeAccount.into()),
-    };
+    if account_key == Key::Uninitialized {
+        account_info.assign(&system_program::ID);
+    }
 
     let dest_starting_lamports = dest_info.lamports();
     **dest_info.lamports.borrow_mut() = dest_starting_lamports
@@ -71,7 +86,7 @@ fn collect_fee_from_account(account_info: &AccountInfo, dest_info: &AccountInfo)
     **account_info.lamports.borrow_mut() = rent_amount;
 
     // Clear fee flag.
-    clear_fee_flag(account_info)?;
+    clear_fee_flag(account_info, account_key)?;
 
-    Ok(())
+    Ok(fee_amount)
 }
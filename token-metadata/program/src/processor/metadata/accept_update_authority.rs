@@ -0,0 +1,97 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    assertions::{assert_keys_equal, assert_owned_by},
+    error::MetadataError,
+    instruction::AcceptUpdateAuthority,
+    pda::find_pending_update_authority_account,
+    state::{Key, Metadata, PendingUpdateAuthority, TokenMetadataAccount},
+    utils::{clean_write_metadata, close_program_account},
+};
+
+/// Accepts a proposed update authority handover.
+///
+/// The metadata account (and any metadata accounts passed as remaining accounts) must
+/// either belong to the proposed mint or, for collection handovers, be verified items of
+/// the collection. The pending account is closed once the metadata of the proposed mint
+/// is handed over, so collection items should be processed before the collection parent.
+pub fn accept_update_authority<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let ctx = AcceptUpdateAuthority::to_context(accounts)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.pending_update_authority_info, program_id)?;
+
+    let pending =
+        PendingUpdateAuthority::from_account_info(ctx.accounts.pending_update_authority_info)?;
+
+    let (pending_key, _) = find_pending_update_authority_account(&pending.mint);
+    assert_keys_equal(&pending_key, ctx.accounts.pending_update_authority_info.key)?;
+
+    // the proposed authority must sign to accept the handover
+
+    if !ctx.accounts.new_update_authority_info.is_signer
+        || pending.pending_update_authority != *ctx.accounts.new_update_authority_info.key
+    {
+        return Err(MetadataError::InvalidPendingUpdateAuthority.into());
+    }
+
+    let mut handover_complete = false;
+
+    for metadata_info in std::iter::once(ctx.accounts.metadata_info).chain(ctx.remaining_accounts) {
+        if accept_metadata(program_id, metadata_info, &pending)? {
+            handover_complete = true;
+        }
+    }
+
+    if handover_complete {
+        close_program_account(
+            ctx.accounts.pending_update_authority_info,
+            ctx.accounts.new_update_authority_info,
+            Key::PendingUpdateAuthority,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Hands over the update authority of a single metadata account, returning whether it is
+/// the metadata of the proposed mint.
+fn accept_metadata(
+    program_id: &Pubkey,
+    metadata_info: &AccountInfo,
+    pending: &PendingUpdateAuthority,
+) -> Result<bool, ProgramError> {
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+
+    // the update authority must not have changed since the proposal
+    if metadata.update_authority != pending.update_authority {
+        return Err(MetadataError::UpdateAuthorityIncorrect.into());
+    }
+
+    let is_proposed_mint = metadata.mint == pending.mint;
+
+    if !is_proposed_mint {
+        if !pending.collection {
+            return Err(MetadataError::MintMismatch.into());
+        }
+
+        match &metadata.collection {
+            Some(collection) if collection.verified && collection.key == pending.mint => (),
+            _ => return Err(MetadataError::NotAMemberOfCollection.into()),
+        }
+    }
+
+    metadata.update_authority = pending.pending_update_authority;
+    clean_write_metadata(&mut metadata, metadata_info)?;
+
+    Ok(is_proposed_mint)
+}
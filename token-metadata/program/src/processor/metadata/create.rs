@@ -1,4 +1,3 @@
-use mpl_utils::assert_initialized;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke, program_pack::Pack,
     pubkey::Pubkey, rent::Rent, system_instruction, sysvar::Sysvar,
@@ -6,14 +5,15 @@ use solana_program::{
 use spl_token::{native_mint::DECIMALS, state::Mint};
 
 use crate::{
+    assertions::{assert_initialized, assert_owned_by, assert_token_program_matches_package},
     error::MetadataError,
     instruction::{Context, Create, CreateArgs},
     state::{
-        Metadata, ProgrammableConfig, TokenMetadataAccount, TokenStandard, MAX_MASTER_EDITION_LEN,
-        TOKEN_STANDARD_INDEX,
+        Key, Metadata, PrintSupply, ProgrammableConfig, TokenMetadataAccount, TokenStandard,
+        MAX_MASTER_EDITION_LEN, TOKEN_STANDARD_INDEX,
     },
     utils::{
-        create_master_edition,
+        create_master_edition, create_programmable_fungible_edition,
         fee::{levy, set_fee_flag, LevyArgs},
         process_create_metadata_accounts_logic, CreateMetadataAccountsLogicArgs,
     },
@@ -22,7 +22,8 @@ use crate::{
 /// Create the associated metadata accounts for a mint.
 ///
 /// The instruction will also initialize the mint if the account does not
-/// exist. For `NonFungible` assets, a `master_edition` account is required.
+/// exist. For `NonFungible` and `ProgrammableFungibleAsset` assets, a `master_edition`
+/// account is required.
 pub fn create<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -53,9 +54,15 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
     levy(LevyArgs {
         payer_account_info: ctx.accounts.payer_info,
         token_metadata_pda_info: ctx.accounts.metadata_info,
+        account_size: Metadata::size(),
     })?;
 
-    // if the account does not exist, we will allocate a new mint
+    // the mint can belong to either SPL Token or Token-2022
+
+    assert_token_program_matches_package(ctx.accounts.spl_token_program_info)?;
+
+    // if the account does not exist, we will allocate a new mint (without extensions);
+    // Token-2022 mints with extensions must be created and initialized beforehand
 
     if ctx.accounts.mint_info.data_is_empty() {
         // mint account must be a signer in the transaction
@@ -69,9 +76,9 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
             &system_instruction::create_account(
                 ctx.accounts.payer_info.key,
                 ctx.accounts.mint_info.key,
-                Rent::get()?.minimum_balance(spl_token::state::Mint::LEN),
-                spl_token::state::Mint::LEN as u64,
-                &spl_token::ID,
+                Rent::get()?.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                ctx.accounts.spl_token_program_info.key,
             ),
             &[
                 ctx.accounts.payer_info.clone(),
@@ -82,10 +89,14 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
         let decimals = match asset_data.token_standard {
             // for NonFungible variants, we ignore the argument and
             // always use 0 decimals
-            TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible => 0,
+            TokenStandard::NonFungible
+            | TokenStandard::ProgrammableNonFungible
+            | TokenStandard::ProgrammableNonTransferable => 0,
             // for Fungile variants, we either use the specified decimals or the default
             // DECIMALS from spl-token
-            TokenStandard::FungibleAsset | TokenStandard::Fungible => match decimals {
+            TokenStandard::FungibleAsset
+            | TokenStandard::Fungible
+            | TokenStandard::ProgrammableFungibleAsset => match decimals {
                 Some(decimals) => decimals,
                 // if decimals not provided, use the default
                 None => DECIMALS,
@@ -97,7 +108,7 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
 
         // initializing the mint account
         invoke(
-            &spl_token::instruction::initialize_mint2(
+            &spl_token_2022::instruction::initialize_mint2(
                 ctx.accounts.spl_token_program_info.key,
                 ctx.accounts.mint_info.key,
                 ctx.accounts.authority_info.key,
@@ -112,11 +123,17 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
     } else {
         // validates the existing mint account
 
-        let mint: Mint = assert_initialized(ctx.accounts.mint_info, MetadataError::Uninitialized)?;
+        assert_owned_by(
+            ctx.accounts.mint_info,
+            ctx.accounts.spl_token_program_info.key,
+        )?;
+        let mint: Mint = assert_initialized(ctx.accounts.mint_info)?;
         // NonFungible assets must have decimals == 0 and supply no greater than 1
         if matches!(
             asset_data.token_standard,
-            TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible
+            TokenStandard::NonFungible
+                | TokenStandard::ProgrammableNonFungible
+                | TokenStandard::ProgrammableNonTransferable
         ) && (mint.decimals > 0 || mint.supply > 1)
         {
             return Err(MetadataError::InvalidMintForTokenStandard.into());
@@ -125,6 +142,8 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
         if matches!(
             asset_data.token_standard,
             TokenStandard::ProgrammableNonFungible
+                | TokenStandard::ProgrammableFungibleAsset
+                | TokenStandard::ProgrammableNonTransferable
         ) && (mint.supply > 0)
         {
             return Err(MetadataError::MintSupplyMustBeZero.into());
@@ -155,10 +174,21 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
 
     if matches!(
         asset_data.token_standard,
-        TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible
+        TokenStandard::NonFungible
+            | TokenStandard::ProgrammableNonFungible
+            | TokenStandard::ProgrammableNonTransferable
     ) {
         let print_supply = print_supply.ok_or(MetadataError::MissingPrintSupply)?;
 
+        // prints would not inherit the transfer restriction
+        if matches!(
+            asset_data.token_standard,
+            TokenStandard::ProgrammableNonTransferable
+        ) && !matches!(print_supply, PrintSupply::Zero)
+        {
+            return Err(MetadataError::NonTransferablePrintsNotSupported.into());
+        }
+
         if let Some(master_edition) = ctx.accounts.master_edition_info {
             create_master_edition(
                 program_id,
@@ -177,7 +207,7 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
             // master edition account
             if matches!(
                 asset_data.token_standard,
-                TokenStandard::ProgrammableNonFungible
+                TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonTransferable
             ) {
                 let mut data = master_edition.data.borrow_mut();
 
@@ -185,11 +215,34 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
                     return Err(MetadataError::InvalidMasterEditionAccountLength.into());
                 }
 
-                data[TOKEN_STANDARD_INDEX] = TokenStandard::ProgrammableNonFungible as u8;
+                data[TOKEN_STANDARD_INDEX] = asset_data.token_standard as u8;
             }
         } else {
             return Err(MetadataError::MissingMasterEditionAccount.into());
         }
+    } else if matches!(
+        asset_data.token_standard,
+        TokenStandard::ProgrammableFungibleAsset
+    ) {
+        // programmable fungible assets use the master edition as the mint and
+        // freeze authority, but do not support prints
+        if print_supply.is_some() {
+            msg!("Ignoring print supply for selected token standard");
+        }
+
+        if let Some(master_edition) = ctx.accounts.master_edition_info {
+            create_programmable_fungible_edition(
+                program_id,
+                master_edition,
+                ctx.accounts.mint_info,
+                ctx.accounts.authority_info,
+                ctx.accounts.payer_info,
+                ctx.accounts.spl_token_program_info,
+                ctx.accounts.system_program_info,
+            )?;
+        } else {
+            return Err(MetadataError::MissingMasterEditionAccount.into());
+        }
     } else if print_supply.is_some() {
         msg!("Ignoring print supply for selected token standard");
     }
@@ -203,6 +256,8 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
     if matches!(
         asset_data.token_standard,
         TokenStandard::ProgrammableNonFungible
+            | TokenStandard::ProgrammableFungibleAsset
+            | TokenStandard::ProgrammableNonTransferable
     ) {
         metadata.programmable_config = Some(ProgrammableConfig::V1 {
             rule_set: asset_data.rule_set,
@@ -213,5 +268,5 @@ fn create_v1(program_id: &Pubkey, ctx: Context<Create>, args: CreateArgs) -> Pro
     metadata.save(&mut ctx.accounts.metadata_info.try_borrow_mut_data()?)?;
 
     // Set fee flag after metadata account is created.
-    set_fee_flag(ctx.accounts.metadata_info)
+    set_fee_flag(ctx.accounts.metadata_info, Key::MetadataV1)
 }
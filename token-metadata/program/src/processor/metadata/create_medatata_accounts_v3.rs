@@ -5,7 +5,7 @@ use solana_program::{
 };
 
 use crate::{
-    state::{CollectionDetails, DataV2},
+    state::{CollectionDetails, DataV2, Key, Metadata, TokenMetadataAccount},
     utils::{
         fee::{levy, set_fee_flag, LevyArgs},
         process_create_metadata_accounts_logic, CreateMetadataAccountsLogicArgs,
@@ -31,6 +31,7 @@ pub fn process_create_metadata_accounts_v3<'a>(
     levy(LevyArgs {
         payer_account_info,
         token_metadata_pda_info: metadata_account_info,
+        account_size: Metadata::size(),
     })?;
 
     process_create_metadata_accounts_logic(
@@ -52,5 +53,5 @@ pub fn process_create_metadata_accounts_v3<'a>(
     )?;
 
     // Set fee flag after metadata account is created.
-    set_fee_flag(metadata_account_info)
+    set_fee_flag(metadata_account_info, Key::MetadataV1)
 }
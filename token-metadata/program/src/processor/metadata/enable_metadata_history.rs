@@ -0,0 +1,116 @@
+use mpl_utils::{assert_signer, create_or_allocate_account_raw, resize_or_reallocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, pubkey::Pubkey,
+    system_program, sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{
+        assert_keys_equal, assert_owned_by, metadata::assert_update_authority_is_correct,
+    },
+    error::MetadataError,
+    instruction::{Context, EnableMetadataHistory, EnableMetadataHistoryArgs},
+    pda::{find_metadata_history_account, PREFIX},
+    state::{Metadata, MetadataHistory, TokenMetadataAccount, METADATA_HISTORY_SEED},
+};
+
+/// Enables the history of changes of an asset, or updates its capacity if it is
+/// already enabled.
+pub fn enable_metadata_history<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: EnableMetadataHistoryArgs,
+) -> ProgramResult {
+    let context = EnableMetadataHistory::to_context(accounts)?;
+
+    match args {
+        EnableMetadataHistoryArgs::V1 { capacity } => {
+            enable_metadata_history_v1(program_id, context, capacity)
+        }
+    }
+}
+
+fn enable_metadata_history_v1(
+    program_id: &Pubkey,
+    ctx: Context<EnableMetadataHistory>,
+    capacity: u8,
+) -> ProgramResult {
+    if capacity == 0 {
+        return Err(MetadataError::InvalidMetadataHistoryCapacity.into());
+    }
+
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    let (history_key, bump) = find_metadata_history_account(&metadata.mint);
+    assert_keys_equal(&history_key, ctx.accounts.metadata_history_info.key)?;
+
+    // authority must be the update authority (this also checks that it is a signer)
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    let history = if ctx.accounts.metadata_history_info.data_is_empty() {
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            METADATA_HISTORY_SEED.as_bytes(),
+            &[bump],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.metadata_history_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            MetadataHistory::data_len(capacity),
+            &signer_seeds,
+        )?;
+
+        let mut history = MetadataHistory {
+            bump,
+            mint: metadata.mint,
+            capacity,
+            ..Default::default()
+        };
+        // the current data is the first entry of the history
+        history.push(
+            &metadata.data,
+            Clock::get()?.slot,
+            *ctx.accounts.authority_info.key,
+        );
+
+        history
+    } else {
+        let mut history = MetadataHistory::from_account_info(ctx.accounts.metadata_history_info)?;
+        history.capacity = capacity;
+        history.truncate();
+
+        // clears the data first, since the account might be shrinking
+        ctx.accounts
+            .metadata_history_info
+            .try_borrow_mut_data()?
+            .fill(0);
+
+        resize_or_reallocate_account_raw(
+            ctx.accounts.metadata_history_info,
+            ctx.accounts.payer_info,
+            ctx.accounts.system_program_info,
+            MetadataHistory::data_len(capacity),
+        )?;
+
+        history
+    };
+
+    history.save(ctx.accounts.metadata_history_info)
+}
@@ -1,4 +1,6 @@
-use mpl_utils::{assert_signer, cmp_pubkeys};
+use std::fmt::Display;
+
+use mpl_utils::{assert_signer, cmp_pubkeys, token::is_token_program};
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
@@ -17,16 +19,31 @@ use crate::{
     error::MetadataError,
     instruction::{Context, Mint, MintArgs},
     pda::{find_token_record_account, EDITION, PREFIX},
-    state::{Metadata, TokenMetadataAccount, TokenStandard},
-    utils::{create_token_record_account, freeze, thaw},
+    state::{Metadata, Operation, TokenMetadataAccount, TokenStandard},
+    utils::{
+        auth_rules_validate, create_token_record_account, freeze, thaw, AuthRulesValidateParams,
+    },
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintScenario {
+    MetadataAuth,
+}
+
+impl Display for MintScenario {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MetadataAuth => write!(f, "MetadataAuth"),
+        }
+    }
+}
+
 /// Mints tokens from a mint account.
 ///
 /// This instruction will also initialized the associated token account if it does not exist – in
-/// this case the `token_owner` will be required. When minting `*NonFungible` assets, the `authority`
-/// must be the update authority; in all other cases, it must be the mint authority from the mint
-/// account.
+/// this case the `token_owner` will be required. When minting `*NonFungible` or
+/// `ProgrammableFungibleAsset` assets, the `authority` must be the update authority; in all other
+/// cases, it must be the mint authority from the mint account.
 pub fn mint<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -38,9 +55,16 @@ pub fn mint<'a>(
     }
 }
 
-pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> ProgramResult {
+pub fn mint_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Mint<'a>>,
+    args: MintArgs,
+) -> ProgramResult {
     // get the args for the instruction
-    let MintArgs::V1 { amount, .. } = args;
+    let MintArgs::V1 {
+        amount,
+        authorization_data: auth_data,
+    } = args;
 
     if amount == 0 {
         return Err(MetadataError::AmountMustBeGreaterThanZero.into());
@@ -68,11 +92,21 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
         return Err(MetadataError::MintMismatch.into());
     }
 
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+    // the mint can belong to either SPL Token or Token-2022
+    if !is_token_program(ctx.accounts.spl_token_program_info.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    assert_owned_by(
+        ctx.accounts.mint_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
     let mint: MintAccount = assert_initialized(ctx.accounts.mint_info)?;
 
-    if !cmp_pubkeys(ctx.accounts.spl_token_program_info.key, &spl_token::ID) {
-        return Err(ProgramError::IncorrectProgramId);
+    if let Some(auth_rules_program) = ctx.accounts.authorization_rules_program_info {
+        if !cmp_pubkeys(auth_rules_program.key, &mpl_token_auth_rules::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
     }
 
     // validates the authority:
@@ -80,7 +114,9 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
     // - Fungible must have the authority as the mint_authority
 
     match metadata.token_standard {
-        Some(TokenStandard::ProgrammableNonFungible) | Some(TokenStandard::NonFungible) => {
+        Some(TokenStandard::ProgrammableNonFungible)
+        | Some(TokenStandard::ProgrammableNonTransferable)
+        | Some(TokenStandard::NonFungible) => {
             // for NonFungible assets, the mint authority is the master edition
             if let Some(master_edition_info) = ctx.accounts.master_edition_info {
                 assert_derivation(
@@ -106,6 +142,17 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
                 return Err(MetadataError::UpdateAuthorityIncorrect.into());
             }
         }
+        Some(TokenStandard::ProgrammableFungibleAsset) => {
+            // the master edition is the mint authority, but there is no supply restriction
+            if ctx.accounts.master_edition_info.is_none() {
+                return Err(MetadataError::MissingMasterEditionAccount.into());
+            }
+
+            // authority must be the update_authority of the metadata account
+            if !cmp_pubkeys(&metadata.update_authority, ctx.accounts.authority_info.key) {
+                return Err(MetadataError::UpdateAuthorityIncorrect.into());
+            }
+        }
         _ => {
             assert_mint_authority_matches_mint(&mint.mint_authority, ctx.accounts.authority_info)?;
         }
@@ -128,7 +175,7 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
             ctx.accounts.token_info,
             &[
                 token_owner_info.key.as_ref(),
-                spl_token::ID.as_ref(),
+                ctx.accounts.spl_token_program_info.key.as_ref(),
                 ctx.accounts.mint_info.key.as_ref(),
             ],
         )?;
@@ -141,7 +188,7 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
                 ctx.accounts.payer_info.key,
                 token_owner_info.key,
                 ctx.accounts.mint_info.key,
-                &spl_token::ID,
+                ctx.accounts.spl_token_program_info.key,
             ),
             &[
                 ctx.accounts.payer_info.clone(),
@@ -151,18 +198,28 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
             ],
         )?;
     } else {
-        assert_owned_by(ctx.accounts.token_info, &spl_token::ID)?;
+        assert_owned_by(
+            ctx.accounts.token_info,
+            ctx.accounts.spl_token_program_info.key,
+        )?;
     }
 
     let token: Account = assert_initialized(ctx.accounts.token_info)?;
 
+    let is_programmable = matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+            | Some(TokenStandard::ProgrammableFungibleAsset)
+            | Some(TokenStandard::ProgrammableNonTransferable)
+    );
+
     match metadata.token_standard {
-        Some(TokenStandard::NonFungible) | Some(TokenStandard::ProgrammableNonFungible) => {
-            // for pNFTs, we require the token record account
-            if matches!(
-                metadata.token_standard,
-                Some(TokenStandard::ProgrammableNonFungible)
-            ) {
+        Some(TokenStandard::NonFungible)
+        | Some(TokenStandard::ProgrammableNonFungible)
+        | Some(TokenStandard::ProgrammableFungibleAsset)
+        | Some(TokenStandard::ProgrammableNonTransferable) => {
+            // for programmable assets, we require the token record account
+            if is_programmable {
                 // we always need the token_record_info
                 let token_record_info = ctx
                     .accounts
@@ -190,6 +247,24 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
                 } else {
                     assert_owned_by(token_record_info, &crate::ID)?;
                 }
+
+                // only the update authority can mint programmable assets
+                auth_rules_validate(AuthRulesValidateParams {
+                    mint_info: ctx.accounts.mint_info,
+                    owner_info: None,
+                    authority_info: Some(ctx.accounts.authority_info),
+                    source_info: None,
+                    destination_info: ctx.accounts.token_owner_info,
+                    programmable_config: metadata.programmable_config.clone(),
+                    amount,
+                    auth_data: auth_data.clone(),
+                    auth_rules_info: ctx.accounts.authorization_rules_info,
+                    operation: Operation::Mint {
+                        scenario: MintScenario::MetadataAuth,
+                    },
+                    is_wallet_to_wallet: false,
+                    rule_set_revision: None,
+                })?;
             }
 
             let mut signer_seeds = vec![
@@ -215,11 +290,7 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
 
             // thaw the token account for programmable assets; the account
             // is not frozen if we just initialized it
-            if matches!(
-                metadata.token_standard,
-                Some(TokenStandard::ProgrammableNonFungible)
-            ) && token.is_frozen()
-            {
+            if is_programmable && token.is_frozen() {
                 thaw(
                     ctx.accounts.mint_info.clone(),
                     ctx.accounts.token_info.clone(),
@@ -229,7 +300,7 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
             }
 
             invoke_signed(
-                &spl_token::instruction::mint_to(
+                &spl_token_2022::instruction::mint_to(
                     ctx.accounts.spl_token_program_info.key,
                     ctx.accounts.mint_info.key,
                     ctx.accounts.token_info.key,
@@ -246,10 +317,7 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
             )?;
 
             // programmable assets are always in a frozen state
-            if matches!(
-                metadata.token_standard,
-                Some(TokenStandard::ProgrammableNonFungible)
-            ) {
+            if is_programmable {
                 freeze(
                     ctx.accounts.mint_info.clone(),
                     ctx.accounts.token_info.clone(),
@@ -260,7 +328,7 @@ pub fn mint_v1(program_id: &Pubkey, ctx: Context<Mint>, args: MintArgs) -> Progr
         }
         _ => {
             invoke(
-                &spl_token::instruction::mint_to(
+                &spl_token_2022::instruction::mint_to(
                     ctx.accounts.spl_token_program_info.key,
                     ctx.accounts.mint_info.key,
                     ctx.accounts.token_info.key,
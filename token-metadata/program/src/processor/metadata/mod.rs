@@ -1,23 +1,35 @@
+mod accept_update_authority;
 mod create;
 mod create_medatata_accounts_v3;
+mod enable_metadata_history;
 mod mint;
+mod propose_update_authority;
 mod puff_metadata;
 mod remove_creator_verification;
+mod reset_primary_sale_happened;
+mod set_royalty_config;
 mod set_token_standard;
 mod sign_metadata;
 mod transfer;
 mod update;
+mod update_creators;
 mod update_metadata_account_v2;
 mod update_primary_sale_happened_via_token;
 
+pub use accept_update_authority::*;
 pub use create::*;
 pub use create_medatata_accounts_v3::*;
+pub use enable_metadata_history::*;
 pub use mint::*;
+pub use propose_update_authority::*;
 pub use puff_metadata::*;
 pub use remove_creator_verification::*;
+pub use reset_primary_sale_happened::*;
+pub use set_royalty_config::*;
 pub use set_token_standard::*;
 pub use sign_metadata::*;
 pub use transfer::*;
 pub use update::*;
+pub use update_creators::*;
 pub use update_metadata_account_v2::*;
 pub use update_primary_sale_happened_via_token::*;
@@ -0,0 +1,100 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{
+        assert_keys_equal, assert_owned_by, metadata::assert_update_authority_is_correct,
+    },
+    instruction::{Context, ProposeUpdateAuthority, ProposeUpdateAuthorityArgs},
+    pda::{find_pending_update_authority_account, PREFIX},
+    state::{
+        Metadata, PendingUpdateAuthority, TokenMetadataAccount, PENDING_UPDATE_AUTHORITY_SEED,
+    },
+};
+
+/// Proposes a new update authority, which takes effect once the proposed authority
+/// accepts it.
+pub fn propose_update_authority<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ProposeUpdateAuthorityArgs,
+) -> ProgramResult {
+    let context = ProposeUpdateAuthority::to_context(accounts)?;
+
+    match args {
+        ProposeUpdateAuthorityArgs::V1 {
+            new_update_authority,
+            collection,
+        } => propose_update_authority_v1(program_id, context, new_update_authority, collection),
+    }
+}
+
+fn propose_update_authority_v1(
+    program_id: &Pubkey,
+    ctx: Context<ProposeUpdateAuthority>,
+    new_update_authority: Pubkey,
+    collection: bool,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    let (pending_key, bump) = find_pending_update_authority_account(&metadata.mint);
+    assert_keys_equal(&pending_key, ctx.accounts.pending_update_authority_info.key)?;
+
+    // authority must be the update authority (this also checks that it is a signer)
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    // a new proposal replaces any existing one
+    if ctx.accounts.pending_update_authority_info.data_is_empty() {
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY_SEED.as_bytes(),
+            &[bump],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.pending_update_authority_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            PendingUpdateAuthority::size(),
+            &signer_seeds,
+        )?;
+    } else {
+        assert_owned_by(ctx.accounts.pending_update_authority_info, program_id)?;
+    }
+
+    let pending = PendingUpdateAuthority {
+        bump,
+        mint: metadata.mint,
+        update_authority: metadata.update_authority,
+        pending_update_authority: new_update_authority,
+        collection,
+        ..Default::default()
+    };
+
+    pending.serialize(
+        &mut *ctx
+            .accounts
+            .pending_update_authority_info
+            .try_borrow_mut_data()?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+use spl_token::state::Account;
+
+use crate::{
+    assertions::{
+        assert_initialized, assert_owned_by, metadata::assert_update_authority_is_correct,
+    },
+    error::MetadataError,
+    instruction::ResetPrimarySaleHappened,
+    state::{Metadata, TokenMetadataAccount},
+    utils::clean_write_metadata,
+};
+
+/// Resets the primary sale flag of an asset while it is held by one of its verified
+/// creators, so royalties apply to the actual primary sale.
+pub fn reset_primary_sale_happened<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let ctx = ResetPrimarySaleHappened::to_context(accounts)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.token_info, &spl_token::ID)?;
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    // authority must be the update authority (this also checks that it is a signer)
+
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    // the token account must hold the asset
+
+    let token: Account = assert_initialized(ctx.accounts.token_info)?;
+
+    if token.mint != metadata.mint {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token.amount == 0 {
+        return Err(MetadataError::NoBalanceInAccountForAuthorization.into());
+    }
+
+    // and be owned by a verified creator
+
+    let is_verified_creator = metadata
+        .data
+        .creators
+        .as_ref()
+        .map(|creators| {
+            creators
+                .iter()
+                .any(|c| c.verified && c.address == token.owner)
+        })
+        .unwrap_or(false);
+
+    if !is_verified_creator {
+        return Err(MetadataError::HolderNotVerifiedCreator.into());
+    }
+
+    metadata.primary_sale_happened = false;
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    Ok(())
+}
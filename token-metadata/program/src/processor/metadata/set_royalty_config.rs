@@ -0,0 +1,51 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::{
+    assertions::{assert_owned_by, metadata::assert_update_authority_is_correct},
+    error::MetadataError,
+    instruction::{SetRoyaltyConfig, SetRoyaltyConfigArgs},
+    state::{Metadata, RoyaltyConfig, TokenMetadataAccount},
+    utils::clean_write_metadata,
+};
+
+/// Sets how the royalty settings of an asset are shared within its collection.
+pub fn set_royalty_config<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetRoyaltyConfigArgs,
+) -> ProgramResult {
+    let ctx = SetRoyaltyConfig::to_context(accounts)?;
+
+    let SetRoyaltyConfigArgs::V1 { royalty_config } = args;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    // authority must be the update authority (this also checks that it is a signer)
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    // changing the royalty config changes the effective royalties of the asset
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    // a collection parent declares the royalties of its items and an item inherits them
+    // from its collection
+    match royalty_config {
+        Some(RoyaltyConfig::CollectionAuthoritative) if metadata.collection_details.is_none() => {
+            return Err(MetadataError::NotACollectionParent.into());
+        }
+        Some(RoyaltyConfig::InheritFromCollection) if metadata.collection.is_none() => {
+            return Err(MetadataError::CollectionNotFound.into());
+        }
+        _ => (),
+    }
+
+    metadata.royalty_config = royalty_config;
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    Ok(())
+}
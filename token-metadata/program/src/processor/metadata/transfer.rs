@@ -1,16 +1,22 @@
 use std::fmt::Display;
 
-use mpl_utils::{assert_signer, cmp_pubkeys, token::TokenTransferParams};
+use mpl_utils::{
+    assert_signer, cmp_pubkeys,
+    token::{
+        get_mint_decimals, is_token_program, spl_token_transfer_checked, unpack,
+        TokenTransferParams,
+    },
+};
 use solana_program::{
     account_info::AccountInfo,
+    clock::Clock,
     entrypoint::ProgramResult,
-    program::invoke,
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
     program_option::COption,
-    program_pack::Pack,
     pubkey::Pubkey,
     system_program,
-    sysvar::{self, instructions::get_instruction_relative},
+    sysvar::{self, instructions::get_instruction_relative, Sysvar},
 };
 use spl_token::state::Account;
 
@@ -19,16 +25,18 @@ use crate::{
         assert_keys_equal, assert_owned_by, assert_token_matches_owner_and_mint,
         metadata::assert_holding_amount,
     },
+    edition_seeds,
     error::MetadataError,
     instruction::{Context, Transfer, TransferArgs},
     pda::find_token_record_account,
+    processor::AuthorizationData,
     state::{
-        AuthorityRequest, AuthorityResponse, AuthorityType, Key, Metadata, Operation,
+        AuthorityRequest, AuthorityResponse, AuthorityType, Key, Metadata, Operation, Resizable,
         TokenDelegateRole, TokenMetadataAccount, TokenRecord, TokenStandard,
     },
     utils::{
         assert_derivation, auth_rules_validate, clear_close_authority, close_program_account,
-        create_token_record_account, frozen_transfer, AuthRulesValidateParams,
+        create_token_record_account, freeze, frozen_transfer, thaw, AuthRulesValidateParams,
         ClearCloseAuthorityParams,
     },
 };
@@ -39,6 +47,7 @@ pub enum TransferScenario {
     TransferDelegate,
     SaleDelegate,
     MigrationDelegate,
+    RentalDelegate,
 }
 
 impl Display for TransferScenario {
@@ -48,6 +57,7 @@ impl Display for TransferScenario {
             Self::TransferDelegate => write!(f, "TransferDelegate"),
             Self::SaleDelegate => write!(f, "SaleDelegate"),
             Self::MigrationDelegate => write!(f, "MigrationDelegate"),
+            Self::RentalDelegate => write!(f, "RentalDelegate"),
         }
     }
 }
@@ -58,6 +68,7 @@ impl From<TransferScenario> for TokenDelegateRole {
             TransferScenario::TransferDelegate => TokenDelegateRole::Transfer,
             TransferScenario::SaleDelegate => TokenDelegateRole::Sale,
             TransferScenario::MigrationDelegate => TokenDelegateRole::Migration,
+            TransferScenario::RentalDelegate => TokenDelegateRole::Rental,
             _ => panic!("Invalid delegate role"),
         }
     }
@@ -69,6 +80,7 @@ impl From<TokenDelegateRole> for TransferScenario {
             TokenDelegateRole::Transfer => TransferScenario::TransferDelegate,
             TokenDelegateRole::Sale => TransferScenario::SaleDelegate,
             TokenDelegateRole::Migration => TransferScenario::MigrationDelegate,
+            TokenDelegateRole::Rental => TransferScenario::RentalDelegate,
             _ => panic!("Invalid delegate role"),
         }
     }
@@ -82,11 +94,20 @@ pub fn transfer<'a>(
     let context = Transfer::to_context(accounts)?;
 
     match args {
-        TransferArgs::V1 { .. } => transfer_v1(program_id, context, args),
+        TransferArgs::V1 { .. } => transfer_v1(program_id, context, args, None),
     }
 }
 
-fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs) -> ProgramResult {
+/// Transfers the asset.
+///
+/// The signer seeds are used when the authority is a PDA of this program (e.g., an escrow
+/// transferring its holdings), in which case the authority does not need to be a signer.
+pub(crate) fn transfer_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Transfer<'a>>,
+    args: TransferArgs,
+    authority_signer_seeds: Option<&[&[u8]]>,
+) -> ProgramResult {
     let TransferArgs::V1 {
         authorization_data: auth_data,
         amount,
@@ -96,13 +117,26 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
 
     // This authority must always be a signer, regardless of if it's the
     // actual token owner, a delegate or some other authority authorized
-    // by a rule set.
-    assert_signer(ctx.accounts.authority_info)?;
+    // by a rule set. PDA authorities sign through the program.
+    if authority_signer_seeds.is_none() {
+        assert_signer(ctx.accounts.authority_info)?;
+    }
+
+    // The mint can belong to either SPL Token or Token-2022.
+    if !is_token_program(ctx.accounts.spl_token_program_info.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
 
     // Assert program ownership.
     assert_owned_by(ctx.accounts.metadata_info, program_id)?;
-    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
-    assert_owned_by(ctx.accounts.token_info, &spl_token::ID)?;
+    assert_owned_by(
+        ctx.accounts.mint_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
+    assert_owned_by(
+        ctx.accounts.token_info,
+        ctx.accounts.spl_token_program_info.key,
+    )?;
     if let Some(owner_token_record_info) = ctx.accounts.owner_token_record_info {
         assert_owned_by(owner_token_record_info, program_id)?;
     }
@@ -122,7 +156,7 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
             ctx.accounts.destination_info,
             &[
                 ctx.accounts.destination_owner_info.key.as_ref(),
-                spl_token::ID.as_ref(),
+                ctx.accounts.spl_token_program_info.key.as_ref(),
                 ctx.accounts.mint_info.key.as_ref(),
             ],
         )?;
@@ -133,7 +167,7 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 ctx.accounts.payer_info.key,
                 ctx.accounts.destination_owner_info.key,
                 ctx.accounts.mint_info.key,
-                &spl_token::ID,
+                ctx.accounts.spl_token_program_info.key,
             ),
             &[
                 ctx.accounts.payer_info.clone(),
@@ -143,7 +177,10 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
             ],
         )?;
     } else {
-        assert_owned_by(ctx.accounts.destination_info, &spl_token::ID)?;
+        assert_owned_by(
+            ctx.accounts.destination_info,
+            ctx.accounts.spl_token_program_info.key,
+        )?;
         assert_token_matches_owner_and_mint(
             ctx.accounts.destination_info,
             ctx.accounts.destination_owner_info.key,
@@ -153,10 +190,6 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
 
     // Check program IDs.
 
-    if ctx.accounts.spl_token_program_info.key != &spl_token::ID {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
     if ctx.accounts.spl_ata_program_info.key != &spl_associated_token_account::ID {
         return Err(ProgramError::IncorrectProgramId);
     }
@@ -198,12 +231,43 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
         destination: ctx.accounts.destination_info.clone(),
         amount,
         authority: ctx.accounts.authority_info.clone(),
-        authority_signer_seeds: None,
+        authority_signer_seeds,
         token_program: ctx.accounts.spl_token_program_info.clone(),
     };
 
     let token_standard = metadata.token_standard;
-    let token = Account::unpack(&ctx.accounts.token_info.try_borrow_data()?)?;
+    let token = unpack::<Account>(&ctx.accounts.token_info.try_borrow_data()?)?;
+
+    // non-transferable assets can only be moved by a recovery transfer
+    if matches!(
+        token_standard,
+        Some(TokenStandard::ProgrammableNonTransferable)
+    ) {
+        return recover(&ctx, &metadata, token, amount);
+    }
+
+    // a rented token can only be transferred back to the lender once the rental expires,
+    // which can be done by any signer (e.g., the lender or a crank)
+    if matches!(
+        token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+            | Some(TokenStandard::ProgrammableFungibleAsset)
+    ) {
+        if let Some(owner_token_record_info) = ctx.accounts.owner_token_record_info {
+            let owner_token_record = TokenRecord::from_account_info(owner_token_record_info)?;
+
+            if owner_token_record.is_rented(&token.owner) {
+                return reclaim_rental(
+                    &ctx,
+                    metadata,
+                    owner_token_record,
+                    token,
+                    amount,
+                    auth_data,
+                );
+            }
+        }
+    }
 
     let AuthorityResponse { authority_type, .. } =
         AuthorityType::get_authority_type(AuthorityRequest {
@@ -218,6 +282,7 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 TokenDelegateRole::Transfer,
                 TokenDelegateRole::LockedTransfer,
                 TokenDelegateRole::Migration,
+                TokenDelegateRole::Rental,
             ],
             ..Default::default()
         })?;
@@ -262,7 +327,11 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
             }
         }
         _ => {
-            if matches!(token_standard, Some(TokenStandard::ProgrammableNonFungible)) {
+            if matches!(
+                token_standard,
+                Some(TokenStandard::ProgrammableNonFungible)
+                    | Some(TokenStandard::ProgrammableFungibleAsset)
+            ) {
                 return Err(MetadataError::InvalidAuthorityType.into());
             }
 
@@ -283,8 +352,9 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
     }
 
     match token_standard {
-        Some(TokenStandard::ProgrammableNonFungible) => {
-            // All pNFTs should have a token record passed in and existing.
+        Some(TokenStandard::ProgrammableNonFungible)
+        | Some(TokenStandard::ProgrammableFungibleAsset) => {
+            // All programmable assets should have a token record passed in and existing.
             // The token delegate role may not be populated, however.
             let owner_token_record_info =
                 if let Some(record_info) = ctx.accounts.owner_token_record_info {
@@ -316,7 +386,7 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
             // we do not allow the transfer to proceed since we do not know the type of the delegate
             // to complete the information on the token record.
             let destination_token =
-                Account::unpack(&ctx.accounts.destination_info.try_borrow_data()?)?;
+                unpack::<Account>(&ctx.accounts.destination_info.try_borrow_data()?)?;
 
             if let COption::Some(delegate) = destination_token.delegate {
                 if destination_token_record_info.data_is_empty() {
@@ -343,6 +413,11 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 .map(|role| role == TokenDelegateRole::LockedTransfer)
                 .unwrap_or(false);
 
+            let is_rental_delegate = owner_token_record
+                .delegate_role
+                .map(|role| role == TokenDelegateRole::Rental)
+                .unwrap_or(false);
+
             let scenario = match authority_type {
                 AuthorityType::Holder => {
                     if is_sale_delegate {
@@ -368,6 +443,21 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                         // locked transfer is a special case of the transfer restricted to a specific
                         // address, so after validating the address we proceed as a 'normal' transfer
                         TokenDelegateRole::Transfer.into()
+                    } else if is_rental_delegate {
+                        // the borrower can only transfer the token to itself while the
+                        // rental is active
+                        if !cmp_pubkeys(
+                            ctx.accounts.authority_info.key,
+                            ctx.accounts.destination_owner_info.key,
+                        ) {
+                            return Err(MetadataError::InvalidRentalDestination.into());
+                        }
+
+                        if owner_token_record.is_rental_expired(Clock::get()?.unix_timestamp) {
+                            return Err(MetadataError::RentalExpired.into());
+                        }
+
+                        TransferScenario::RentalDelegate
                     } else {
                         owner_token_record.delegate_role.unwrap().into()
                     }
@@ -401,14 +491,30 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 .edition_info
                 .ok_or(MetadataError::MissingEditionAccount)?;
 
-            clear_close_authority(ClearCloseAuthorityParams {
-                token_info: ctx.accounts.token_info,
-                mint_info: ctx.accounts.mint_info,
-                token,
-                master_edition_info,
-                authority_info: master_edition_info,
-                spl_token_program_info: ctx.accounts.spl_token_program_info,
-            })?;
+            // programmable fungible assets can be partially transferred, in which case
+            // the source account keeps its token record and needs to be frozen again
+            let is_partial_transfer = token.amount > amount;
+
+            if is_partial_transfer {
+                // self transfers leave the source frozen already
+                if ctx.accounts.token_info.key != ctx.accounts.destination_info.key {
+                    freeze(
+                        ctx.accounts.mint_info.clone(),
+                        ctx.accounts.token_info.clone(),
+                        master_edition_info.clone(),
+                        ctx.accounts.spl_token_program_info.clone(),
+                    )?;
+                }
+            } else {
+                clear_close_authority(ClearCloseAuthorityParams {
+                    token_info: ctx.accounts.token_info,
+                    mint_info: ctx.accounts.mint_info,
+                    token,
+                    master_edition_info,
+                    authority_info: master_edition_info,
+                    spl_token_program_info: ctx.accounts.spl_token_program_info,
+                })?;
+            }
 
             // If the token record account for the destination owner doesn't exist,
             // we create it.
@@ -423,16 +529,31 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 )?;
             }
 
-            // Don't close token record if it's a self transfer.
-            if owner_token_record_info.key != destination_token_record_info.key {
+            // The borrower holds the token with the (master) edition as its delegate, which
+            // allows the token to be transferred back to the lender once the rental expires.
+            if matches!(scenario, TransferScenario::RentalDelegate) {
+                start_rental(
+                    &ctx,
+                    destination_token_record_info,
+                    master_edition_info,
+                    &owner_token_record,
+                    amount,
+                )?;
+            }
+
+            // Don't close token record if it's a self transfer or if the source
+            // token account still holds tokens.
+            if owner_token_record_info.key != destination_token_record_info.key
+                && !is_partial_transfer
+            {
                 // If the transfer authority is the holder, we need to manually clear the
                 // token delegate since it does not get cleared by the SPL token program
                 // on transfer.
                 if matches!(scenario, TransferScenario::Holder)
                     && owner_token_record.delegate.is_some()
                 {
-                    invoke(
-                        &spl_token::instruction::revoke(
+                    invoke_signed(
+                        &spl_token_2022::instruction::revoke(
                             ctx.accounts.spl_token_program_info.key,
                             ctx.accounts.token_info.key,
                             ctx.accounts.authority_info.key,
@@ -442,6 +563,7 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                             ctx.accounts.token_info.clone(),
                             ctx.accounts.authority_info.clone(),
                         ],
+                        &authority_signer_seeds.into_iter().collect::<Vec<_>>(),
                     )?;
                 }
 
@@ -455,8 +577,329 @@ fn transfer_v1(program_id: &Pubkey, ctx: Context<Transfer>, args: TransferArgs)
                 )?;
             }
         }
-        _ => mpl_utils::token::spl_token_transfer(token_transfer_params).unwrap(),
+        _ => {
+            // `transfer_checked` is required by Token-2022 mints with transfer fees
+            let decimals = get_mint_decimals(ctx.accounts.mint_info)?;
+            spl_token_transfer_checked(token_transfer_params, decimals).unwrap()
+        }
     }
 
     Ok(())
 }
+
+/// Sets up the token record of the borrower of a rental.
+///
+/// The (master) edition is approved as the delegate of the borrower token account so the
+/// program can transfer the token back to the lender when the rental expires.
+fn start_rental<'a>(
+    ctx: &Context<'a, Transfer<'a>>,
+    destination_token_record_info: &'a AccountInfo<'a>,
+    master_edition_info: &'a AccountInfo<'a>,
+    lender_token_record: &TokenRecord,
+    amount: u64,
+) -> ProgramResult {
+    let mut destination_token_record =
+        TokenRecord::from_account_info(destination_token_record_info)?;
+
+    if destination_token_record.delegate.is_some() {
+        return Err(MetadataError::DelegateAlreadyExists.into());
+    }
+
+    destination_token_record.delegate = Some(*master_edition_info.key);
+    destination_token_record.delegate_role = Some(TokenDelegateRole::Rental);
+    destination_token_record.rental_expiry = lender_token_record.rental_expiry;
+    destination_token_record.lender = lender_token_record.lender;
+    destination_token_record.rule_set_revision = lender_token_record.rule_set_revision;
+    destination_token_record.save(
+        destination_token_record_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )?;
+
+    thaw(
+        ctx.accounts.mint_info.clone(),
+        ctx.accounts.destination_info.clone(),
+        master_edition_info.clone(),
+        ctx.accounts.spl_token_program_info.clone(),
+    )?;
+
+    // the borrower (authority) is the owner of the destination token account
+    invoke(
+        &spl_token_2022::instruction::approve(
+            ctx.accounts.spl_token_program_info.key,
+            ctx.accounts.destination_info.key,
+            master_edition_info.key,
+            ctx.accounts.authority_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            ctx.accounts.destination_info.clone(),
+            master_edition_info.clone(),
+            ctx.accounts.authority_info.clone(),
+        ],
+    )?;
+
+    freeze(
+        ctx.accounts.mint_info.clone(),
+        ctx.accounts.destination_info.clone(),
+        master_edition_info.clone(),
+        ctx.accounts.spl_token_program_info.clone(),
+    )
+}
+
+/// Transfers a rented token back to the lender once the rental expires.
+///
+/// The transfer is signed by the (master) edition, which is the delegate of the borrower
+/// token account, so the borrower signature is not required. The borrower token record is
+/// closed and its rent is returned to the lender.
+fn reclaim_rental<'a>(
+    ctx: &Context<'a, Transfer<'a>>,
+    metadata: Metadata,
+    owner_token_record: TokenRecord,
+    token: Account,
+    amount: u64,
+    auth_data: Option<AuthorizationData>,
+) -> ProgramResult {
+    let owner_token_record_info = ctx
+        .accounts
+        .owner_token_record_info
+        .ok_or(MetadataError::MissingTokenRecord)?;
+    let destination_token_record_info = ctx
+        .accounts
+        .destination_token_record_info
+        .ok_or(MetadataError::MissingTokenRecord)?;
+    let master_edition_info = ctx
+        .accounts
+        .edition_info
+        .ok_or(MetadataError::MissingEditionAccount)?;
+
+    let (pda_key, _) =
+        find_token_record_account(ctx.accounts.mint_info.key, ctx.accounts.token_info.key);
+    // validates the derivation
+    assert_keys_equal(&pda_key, owner_token_record_info.key)?;
+
+    let (new_pda_key, _) = find_token_record_account(
+        ctx.accounts.mint_info.key,
+        ctx.accounts.destination_info.key,
+    );
+    // validates the derivation
+    assert_keys_equal(&new_pda_key, destination_token_record_info.key)?;
+
+    if !owner_token_record.is_rental_expired(Clock::get()?.unix_timestamp) {
+        return Err(
+            if cmp_pubkeys(&token.owner, ctx.accounts.authority_info.key) {
+                MetadataError::TokenIsRented
+            } else {
+                MetadataError::RentalNotExpired
+            }
+            .into(),
+        );
+    }
+
+    if owner_token_record.lender.as_ref() != Some(ctx.accounts.destination_owner_info.key) {
+        return Err(MetadataError::InvalidRentalDestination.into());
+    }
+
+    // the lender receives the rent of the borrower token record
+    if !ctx.accounts.destination_owner_info.is_writable {
+        return Err(MetadataError::LenderNotWritable.into());
+    }
+
+    // the whole rented amount is returned to the lender
+    if amount != token.amount {
+        return Err(MetadataError::InvalidAmount.into());
+    }
+
+    let destination_token = unpack::<Account>(&ctx.accounts.destination_info.try_borrow_data()?)?;
+
+    if destination_token.delegate.is_some() {
+        return Err(MetadataError::DelegateAlreadyExists.into());
+    }
+
+    auth_rules_validate(AuthRulesValidateParams {
+        mint_info: ctx.accounts.mint_info,
+        owner_info: None,
+        authority_info: Some(ctx.accounts.authority_info),
+        source_info: Some(ctx.accounts.token_owner_info),
+        destination_info: Some(ctx.accounts.destination_owner_info),
+        programmable_config: metadata.programmable_config,
+        amount,
+        auth_data,
+        auth_rules_info: ctx.accounts.authorization_rules_info,
+        operation: Operation::Transfer {
+            scenario: TransferScenario::RentalDelegate,
+        },
+        is_wallet_to_wallet: false,
+        rule_set_revision: owner_token_record
+            .rule_set_revision
+            .map(|revision| revision as usize),
+    })?;
+
+    let seeds = edition_seeds!(ctx.accounts.mint_info.key);
+
+    frozen_transfer(
+        TokenTransferParams {
+            mint: ctx.accounts.mint_info.clone(),
+            source: ctx.accounts.token_info.clone(),
+            destination: ctx.accounts.destination_info.clone(),
+            amount,
+            authority: master_edition_info.clone(),
+            authority_signer_seeds: Some(seeds.as_slice()),
+            token_program: ctx.accounts.spl_token_program_info.clone(),
+        },
+        Some(master_edition_info),
+    )?;
+
+    clear_close_authority(ClearCloseAuthorityParams {
+        token_info: ctx.accounts.token_info,
+        mint_info: ctx.accounts.mint_info,
+        token,
+        master_edition_info,
+        authority_info: master_edition_info,
+        spl_token_program_info: ctx.accounts.spl_token_program_info,
+    })?;
+
+    if destination_token_record_info.data_is_empty() {
+        create_token_record_account(
+            &crate::ID,
+            destination_token_record_info,
+            ctx.accounts.mint_info,
+            ctx.accounts.destination_info,
+            ctx.accounts.payer_info,
+            ctx.accounts.system_program_info,
+        )?;
+    }
+
+    // the delegate of the borrower token account is cleared by the spl-token program
+    // since the whole delegated amount is transferred
+    close_program_account(
+        owner_token_record_info,
+        ctx.accounts.destination_owner_info,
+        Key::TokenRecord,
+    )
+}
+
+/// Transfers a non-transferable asset to a new wallet of the same holder.
+///
+/// The recovery must be signed by both the update authority (as the `authority`) and the
+/// holder (as the `token_owner`). The rule set of the asset does not apply to recoveries.
+fn recover<'a>(
+    ctx: &Context<'a, Transfer<'a>>,
+    metadata: &Metadata,
+    token: Account,
+    amount: u64,
+) -> ProgramResult {
+    // PDA authorities cannot initiate a recovery
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.token_owner_info)?;
+
+    if !cmp_pubkeys(&metadata.update_authority, ctx.accounts.authority_info.key) {
+        return Err(MetadataError::NonTransferableAsset.into());
+    }
+
+    if cmp_pubkeys(
+        ctx.accounts.token_owner_info.key,
+        ctx.accounts.destination_owner_info.key,
+    ) {
+        return Err(MetadataError::NonTransferableAsset.into());
+    }
+
+    if amount != token.amount {
+        return Err(MetadataError::InvalidAmount.into());
+    }
+
+    let owner_token_record_info = ctx
+        .accounts
+        .owner_token_record_info
+        .ok_or(MetadataError::MissingTokenRecord)?;
+    let destination_token_record_info = ctx
+        .accounts
+        .destination_token_record_info
+        .ok_or(MetadataError::MissingTokenRecord)?;
+    let master_edition_info = ctx
+        .accounts
+        .edition_info
+        .ok_or(MetadataError::MissingEditionAccount)?;
+
+    let (pda_key, _) =
+        find_token_record_account(ctx.accounts.mint_info.key, ctx.accounts.token_info.key);
+    // validates the derivation
+    assert_keys_equal(&pda_key, owner_token_record_info.key)?;
+
+    let (new_pda_key, _) = find_token_record_account(
+        ctx.accounts.mint_info.key,
+        ctx.accounts.destination_info.key,
+    );
+    // validates the derivation
+    assert_keys_equal(&new_pda_key, destination_token_record_info.key)?;
+
+    let owner_token_record = TokenRecord::from_account_info(owner_token_record_info)?;
+
+    // locked assets stay with the holder until they are unlocked
+    if owner_token_record.is_locked() {
+        return Err(MetadataError::IncorrectTokenState.into());
+    }
+
+    let destination_token = unpack::<Account>(&ctx.accounts.destination_info.try_borrow_data()?)?;
+
+    if destination_token.delegate.is_some() {
+        return Err(MetadataError::DelegateAlreadyExists.into());
+    }
+
+    // the holder is the owner of the source token account
+    frozen_transfer(
+        TokenTransferParams {
+            mint: ctx.accounts.mint_info.clone(),
+            source: ctx.accounts.token_info.clone(),
+            destination: ctx.accounts.destination_info.clone(),
+            amount,
+            authority: ctx.accounts.token_owner_info.clone(),
+            authority_signer_seeds: None,
+            token_program: ctx.accounts.spl_token_program_info.clone(),
+        },
+        Some(master_edition_info),
+    )?;
+
+    clear_close_authority(ClearCloseAuthorityParams {
+        token_info: ctx.accounts.token_info,
+        mint_info: ctx.accounts.mint_info,
+        token,
+        master_edition_info,
+        authority_info: master_edition_info,
+        spl_token_program_info: ctx.accounts.spl_token_program_info,
+    })?;
+
+    if destination_token_record_info.data_is_empty() {
+        create_token_record_account(
+            &crate::ID,
+            destination_token_record_info,
+            ctx.accounts.mint_info,
+            ctx.accounts.destination_info,
+            ctx.accounts.payer_info,
+            ctx.accounts.system_program_info,
+        )?;
+    }
+
+    // the token delegate does not get cleared by the spl-token program on transfer
+    if owner_token_record.delegate.is_some() {
+        invoke(
+            &spl_token_2022::instruction::revoke(
+                ctx.accounts.spl_token_program_info.key,
+                ctx.accounts.token_info.key,
+                ctx.accounts.token_owner_info.key,
+                &[],
+            )?,
+            &[
+                ctx.accounts.token_info.clone(),
+                ctx.accounts.token_owner_info.clone(),
+            ],
+        )?;
+    }
+
+    close_program_account(
+        owner_token_record_info,
+        ctx.accounts.payer_info,
+        Key::TokenRecord,
+    )
+}
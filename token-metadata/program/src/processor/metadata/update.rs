@@ -1,6 +1,6 @@
 use std::fmt::{Display, Formatter};
 
-use mpl_utils::assert_signer;
+use mpl_utils::{assert_signer, cmp_pubkeys};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
     program_pack::Pack, pubkey::Pubkey, sysvar,
@@ -8,18 +8,21 @@ use solana_program::{
 use spl_token::state::Account;
 
 use crate::{
-    assertions::{assert_owned_by, programmable::assert_valid_authorization},
+    assertions::{assert_keys_equal, assert_owned_by, programmable::assert_valid_authorization},
     error::MetadataError,
     instruction::{
         CollectionDetailsToggle, CollectionToggle, Context, MetadataDelegateRole, Update,
         UpdateArgs, UsesToggle,
     },
-    pda::{EDITION, PREFIX},
+    pda::{find_metadata_history_account, EDITION, PREFIX},
     state::{
         AuthorityRequest, AuthorityResponse, AuthorityType, Collection, Metadata,
-        ProgrammableConfig, TokenMetadataAccount, TokenStandard,
+        MetadataDelegateRecord, Operation, ProgrammableConfig, TokenMetadataAccount, TokenStandard,
+    },
+    utils::{
+        assert_derivation, auth_rules_validate, check_token_standard, record_metadata_history,
+        AuthRulesValidateParams,
     },
-    utils::{assert_derivation, check_token_standard},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -46,7 +49,10 @@ pub fn update<'a>(
 ) -> ProgramResult {
     let context = Update::to_context(accounts)?;
 
-    update_v1(program_id, context, args)
+    match args {
+        UpdateArgs::BatchV1 { .. } => batch_update_v1(program_id, context, args),
+        _ => update_v1(program_id, context, args),
+    }
 }
 
 fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> ProgramResult {
@@ -204,6 +210,13 @@ fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> Pro
     // Validate that authority has permission to use the update args that were provided.
     validate_update(&args, &authority_type, metadata_delegate_role)?;
 
+    // limited delegates consume one use per instruction
+    if let (AuthorityType::MetadataDelegate, Some(delegate_record_info)) =
+        (&authority_type, ctx.accounts.delegate_record_info)
+    {
+        MetadataDelegateRecord::consume_use(delegate_record_info)?;
+    }
+
     // See if caller passed in a desired token standard.
     let desired_token_standard = match args {
         UpdateArgs::AsUpdateAuthorityV2 { token_standard, .. }
@@ -228,8 +241,13 @@ fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> Pro
         None => existing_or_inferred_token_std,
     };
 
-    // For pNFTs, we need to validate the authorization rules.
-    if matches!(token_standard, TokenStandard::ProgrammableNonFungible) {
+    // For programmable assets, we need to validate the authorization rules.
+    if matches!(
+        token_standard,
+        TokenStandard::ProgrammableNonFungible
+            | TokenStandard::ProgrammableFungibleAsset
+            | TokenStandard::ProgrammableNonTransferable
+    ) {
         // If the metadata account has a current rule set, we validate that
         // the current rule set account is passed in and matches value on the
         // metadata.
@@ -248,9 +266,239 @@ fn update_v1(program_id: &Pubkey, ctx: Context<Update>, args: UpdateArgs) -> Pro
         ctx.accounts.authority_info,
         ctx.accounts.metadata_info,
         token,
-        token_standard,
+        Some(token_standard),
     )?;
 
+    record_metadata_history(
+        &metadata,
+        ctx.accounts.authority_info.key,
+        ctx.accounts.metadata_history_info,
+    )
+}
+
+/// Updates the data of multiple metadata accounts in a single instruction.
+///
+/// The authority is validated once against the `metadata` and `mint` accounts of the
+/// instruction, and then each metadata account referenced by the batch items (passed
+/// as remaining accounts) must be under the scope of the same authority:
+///   - update authority: the item must have the same update authority
+///   - data delegate: the item must match the mint of the delegate record, or belong
+///     to the verified collection of the delegate record (for collection-level delegates)
+///
+/// Programmable items with a rule set are also validated against the `Update` operation
+/// of the rule set.
+fn batch_update_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Update<'a>>,
+    args: UpdateArgs,
+) -> ProgramResult {
+    let (items, authorization_data) = match args {
+        UpdateArgs::BatchV1 {
+            items,
+            authorization_data,
+        } => (items, authorization_data),
+        _ => return Err(MetadataError::InvalidUpdateArgs.into()),
+    };
+
+    // Assert signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // Assert program ownership
+
+    if let Some(delegate_record_info) = ctx.accounts.delegate_record_info {
+        assert_owned_by(delegate_record_info, &crate::ID)?;
+    }
+
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    // Check program IDs
+
+    if ctx.accounts.system_program_info.key != &solana_program::system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if ctx.accounts.sysvar_instructions_info.key != &sysvar::instructions::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if ctx.accounts.authorization_rules_info.is_some() {
+        let authorization_rules_program = ctx
+            .accounts
+            .authorization_rules_program_info
+            .ok_or(MetadataError::MissingAuthorizationRulesProgram)?;
+
+        if authorization_rules_program.key != &mpl_token_auth_rules::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+
+    // Metadata
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+    // Metadata mint must match mint account key.
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // Check authority (only once for the whole batch).
+
+    let AuthorityResponse {
+        authority_type,
+        metadata_delegate_role,
+        ..
+    } = AuthorityType::get_authority_type(AuthorityRequest {
+        authority: ctx.accounts.authority_info.key,
+        update_authority: &metadata.update_authority,
+        mint: ctx.accounts.mint_info.key,
+        // only a verified collection brings the asset under a collection-level delegate
+        collection_mint: metadata
+            .collection
+            .as_ref()
+            .filter(|collection| collection.verified)
+            .map(|Collection { key, .. }| key),
+        metadata_delegate_record_info: ctx.accounts.delegate_record_info,
+        metadata_delegate_roles: vec![MetadataDelegateRole::Data, MetadataDelegateRole::DataItem],
+        collection_metadata_delegate_roles: vec![MetadataDelegateRole::Data],
+        precedence: &[AuthorityType::Metadata, AuthorityType::MetadataDelegate],
+        ..Default::default()
+    })?;
+
+    // Determines the scope of the authority: the (update authority, mint) pair that
+    // each item must match. For update authorities, any item with the same update
+    // authority is allowed.
+    let (update_authority, delegate_scope, scenario) = match authority_type {
+        AuthorityType::Metadata => {
+            msg!("Auth type: Metadata");
+            (
+                *ctx.accounts.authority_info.key,
+                None,
+                UpdateScenario::MetadataAuth,
+            )
+        }
+        AuthorityType::MetadataDelegate => {
+            msg!("Auth type: Delegate");
+            // the delegate record was validated by the authority check
+            let delegate_record_info = ctx
+                .accounts
+                .delegate_record_info
+                .ok_or(MetadataError::MissingDelegateRecord)?;
+            let delegate_record = MetadataDelegateRecord::from_account_info(delegate_record_info)?;
+
+            (
+                delegate_record.update_authority,
+                Some((delegate_record.mint, metadata_delegate_role)),
+                UpdateScenario::Delegate,
+            )
+        }
+        _ => return Err(MetadataError::InvalidAuthorityType.into()),
+    };
+
+    // limited delegates consume a single use for the whole batch
+    if let (AuthorityType::MetadataDelegate, Some(delegate_record_info)) =
+        (&authority_type, ctx.accounts.delegate_record_info)
+    {
+        MetadataDelegateRecord::consume_use(delegate_record_info)?;
+    }
+
+    for (index, partial_data) in items {
+        // the metadata account of the item is followed by its metadata history account
+        let (item_info, item_history_info) = match ctx
+            .remaining_accounts
+            .get(index as usize..index as usize + 2)
+        {
+            Some([item_info, item_history_info]) => (*item_info, *item_history_info),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        assert_owned_by(item_info, program_id)?;
+
+        if !item_info.is_writable {
+            return Err(MetadataError::InvalidBatchUpdateItem.into());
+        }
+
+        let mut item = Metadata::from_account_info(item_info)?;
+
+        if item.update_authority != update_authority {
+            return Err(MetadataError::UpdateAuthorityIncorrect.into());
+        }
+
+        if let Some((delegate_mint, role)) = &delegate_scope {
+            // an unverified collection can be set by anyone, so it does not bring
+            // the item under the scope of the delegate
+            let in_collection = matches!(
+                item.collection,
+                Some(Collection { key, verified: true }) if key == *delegate_mint
+            );
+
+            let in_scope = match role {
+                Some(MetadataDelegateRole::Data) => item.mint == *delegate_mint || in_collection,
+                Some(MetadataDelegateRole::DataItem) => item.mint == *delegate_mint,
+                _ => false,
+            };
+
+            if !in_scope {
+                return Err(MetadataError::InvalidBatchUpdateItem.into());
+            }
+        }
+
+        let item_history_info = if cmp_pubkeys(item_history_info.key, program_id) {
+            None
+        } else {
+            assert_keys_equal(
+                &find_metadata_history_account(&item.mint).0,
+                item_history_info.key,
+            )?;
+            Some(item_history_info)
+        };
+
+        // For programmable assets, we need to validate the authorization rules. The
+        // mint account of the item follows its metadata history account.
+        if let Some(ProgrammableConfig::V1 { rule_set: Some(_) }) = &item.programmable_config {
+            let item_mint_info = ctx
+                .remaining_accounts
+                .get(index as usize + 2)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            if item.mint != *item_mint_info.key {
+                return Err(MetadataError::MintMismatch.into());
+            }
+
+            auth_rules_validate(AuthRulesValidateParams {
+                mint_info: item_mint_info,
+                owner_info: None,
+                authority_info: Some(ctx.accounts.authority_info),
+                source_info: None,
+                destination_info: None,
+                programmable_config: item.programmable_config.clone(),
+                amount: 1,
+                auth_data: authorization_data.clone(),
+                auth_rules_info: ctx.accounts.authorization_rules_info,
+                operation: Operation::Update {
+                    scenario: scenario.clone(),
+                },
+                is_wallet_to_wallet: false,
+                rule_set_revision: None,
+            })?;
+        }
+
+        let data = partial_data.merge(&item.data);
+        let token_standard = item.token_standard;
+
+        item.update_v1(
+            UpdateArgs::AsDataDelegateV2 {
+                data: Some(data),
+                authorization_data: None,
+            },
+            ctx.accounts.authority_info,
+            item_info,
+            None,
+            token_standard,
+        )?;
+
+        record_metadata_history(&item, ctx.accounts.authority_info.key, item_history_info)?;
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::{
+    assertions::{
+        assert_owned_by,
+        metadata::{assert_data_valid, assert_update_authority_is_correct},
+    },
+    error::MetadataError,
+    instruction::{Context, UpdateCreators, UpdateCreatorsArgs},
+    state::{Creator, Data, Metadata, TokenMetadataAccount},
+    utils::{clean_write_metadata, puff_out_data_fields, record_metadata_history},
+};
+
+/// Updates the creators of an asset with the consent of the verified creators.
+pub fn update_creators<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UpdateCreatorsArgs,
+) -> ProgramResult {
+    let context = UpdateCreators::to_context(accounts)?;
+
+    match args {
+        UpdateCreatorsArgs::V1 { creators } => update_creators_v1(program_id, context, creators),
+    }
+}
+
+fn update_creators_v1(
+    program_id: &Pubkey,
+    ctx: Context<UpdateCreators>,
+    creators: Vec<Creator>,
+) -> ProgramResult {
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    // authority must be the update authority (this also checks that it is a signer)
+
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    let data = Data {
+        creators: Some(creators),
+        ..metadata.data.clone()
+    };
+    // validates the share total and that the verified flags of the existing creators
+    // are not changed
+    assert_data_valid(
+        &data,
+        &metadata.update_authority,
+        &metadata,
+        false,
+        ctx.accounts.authority_info.is_signer,
+    )?;
+
+    // verified creators whose share decreases must consent to the change
+
+    if let (Some(existing_creators), Some(new_creators)) = (&metadata.data.creators, &data.creators)
+    {
+        let new_shares: HashMap<&Pubkey, u8> =
+            new_creators.iter().map(|c| (&c.address, c.share)).collect();
+
+        for creator in existing_creators.iter().filter(|c| c.verified) {
+            let new_share = new_shares.get(&creator.address).copied().unwrap_or(0);
+
+            if new_share < creator.share && !is_creator_signer(&ctx, &creator.address) {
+                return Err(MetadataError::MissingCreatorSignature.into());
+            }
+        }
+    }
+
+    metadata.data = data;
+
+    puff_out_data_fields(&mut metadata);
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    record_metadata_history(
+        &metadata,
+        ctx.accounts.authority_info.key,
+        ctx.accounts.metadata_history_info,
+    )
+}
+
+/// Checks whether the creator signed the transaction, either as the authority or as one
+/// of the remaining accounts.
+fn is_creator_signer(ctx: &Context<UpdateCreators>, creator: &Pubkey) -> bool {
+    (ctx.accounts.authority_info.key == creator && ctx.accounts.authority_info.is_signer)
+        || ctx
+            .remaining_accounts
+            .iter()
+            .any(|info| info.key == creator && info.is_signer)
+}
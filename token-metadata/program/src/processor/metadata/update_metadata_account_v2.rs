@@ -13,7 +13,7 @@ use crate::{
     },
     error::MetadataError,
     state::{DataV2, Metadata, TokenMetadataAccount},
-    utils::{clean_write_metadata, puff_out_data_fields},
+    utils::{clean_write_metadata, puff_out_data_fields, record_metadata_history},
 };
 
 // Update existing account instruction
@@ -29,6 +29,8 @@ pub fn process_update_metadata_accounts_v2(
 
     let metadata_account_info = next_account_info(account_info_iter)?;
     let update_authority_info = next_account_info(account_info_iter)?;
+    // the metadata history account is optional
+    let metadata_history_info = next_account_info(account_info_iter).ok();
     let mut metadata = Metadata::from_account_info(metadata_account_info)?;
 
     assert_owned_by(metadata_account_info, program_id)?;
@@ -90,5 +92,7 @@ pub fn process_update_metadata_accounts_v2(
     }
 
     puff_out_data_fields(&mut metadata);
-    clean_write_metadata(&mut metadata, metadata_account_info)
+    clean_write_metadata(&mut metadata, metadata_account_info)?;
+
+    record_metadata_history(&metadata, update_authority_info.key, metadata_history_info)
 }
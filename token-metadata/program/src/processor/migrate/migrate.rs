@@ -0,0 +1,338 @@
+use std::fmt::Display;
+
+use mpl_utils::{assert_signer, cmp_pubkeys};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_option::COption, pubkey::Pubkey,
+    system_program, sysvar,
+};
+use spl_token::state::Account;
+
+use crate::{
+    assertions::{
+        assert_derivation, assert_initialized, assert_keys_equal, assert_owned_by,
+        metadata::assert_update_authority_is_correct, programmable::assert_valid_authorization,
+    },
+    error::MetadataError,
+    instruction::{Context, Migrate, MigrateArgs},
+    pda::{find_migration_allowlist_account, EDITION, PREFIX},
+    state::{
+        AuthorityType, Key, MasterEditionV2, Metadata, MigrationAllowlist, MigrationType,
+        Operation, ProgrammableConfig, Resizable, TokenDelegateRole, TokenMetadataAccount,
+        TokenRecord, TokenStandard, TokenState, TOKEN_RECORD_SEED,
+    },
+    utils::{
+        auth_rules_validate, clean_write_metadata, close_program_account,
+        create_token_record_account, freeze, thaw, AuthRulesValidateParams,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrateScenario {
+    /// Migration from `ProgrammableNonFungible` back to `NonFungible`.
+    NonFungible,
+}
+
+impl Display for MigrateScenario {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NonFungible => write!(f, "NonFungible"),
+        }
+    }
+}
+
+/// Migrates an asset between `NonFungible` and `ProgrammableNonFungible`.
+pub fn migrate<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: MigrateArgs,
+) -> ProgramResult {
+    let context = Migrate::to_context(accounts)?;
+
+    match args {
+        MigrateArgs::V1 {
+            migration_type,
+            rule_set,
+        } => migrate_v1(program_id, context, migration_type, rule_set),
+    }
+}
+
+fn migrate_v1<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, Migrate<'a>>,
+    migration_type: MigrationType,
+    rule_set: Option<Pubkey>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.edition_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+    assert_owned_by(ctx.accounts.token_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+    assert_keys_equal(
+        ctx.accounts.sysvar_instructions_info.key,
+        &sysvar::instructions::ID,
+    )?;
+    assert_keys_equal(ctx.accounts.spl_token_program_info.key, &spl_token::ID)?;
+
+    if let Some(authorization_rules_program) = ctx.accounts.authorization_rules_program_info {
+        assert_keys_equal(authorization_rules_program.key, &mpl_token_auth_rules::ID)?;
+    }
+
+    // account relationships
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // only master editions can be migrated; the edition is also the freeze authority
+    // of the mint
+    assert_derivation(
+        program_id,
+        ctx.accounts.edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+    MasterEditionV2::from_account_info(ctx.accounts.edition_info)
+        .map_err(|_| MetadataError::NotAMasterEdition)?;
+
+    let token: Account = assert_initialized(ctx.accounts.token_info)?;
+
+    if token.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token.owner != *ctx.accounts.token_owner_info.key {
+        return Err(MetadataError::InvalidOwner.into());
+    }
+
+    if token.amount != 1 {
+        return Err(MetadataError::InsufficientTokenBalance.into());
+    }
+
+    match migration_type {
+        MigrationType::ProgrammableV1 => {
+            migrate_to_programmable(program_id, &ctx, metadata, token, rule_set)
+        }
+        MigrationType::NonFungibleV1 => {
+            migrate_to_nonfungible(program_id, &ctx, metadata, token, rule_set)
+        }
+        MigrationType::CollectionV1 => Err(MetadataError::FeatureNotSupported.into()),
+    }
+}
+
+/// Migrates a `NonFungible` asset to `ProgrammableNonFungible`, creating its token record
+/// and freezing the token account.
+fn migrate_to_programmable<'a>(
+    program_id: &Pubkey,
+    ctx: &Context<'a, Migrate<'a>>,
+    mut metadata: Metadata,
+    token: Account,
+    rule_set: Option<Pubkey>,
+) -> ProgramResult {
+    if !matches!(
+        metadata.token_standard,
+        None | Some(TokenStandard::NonFungible)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    // authority – this can be either:
+    //  1. update authority
+    //  2. token owner, when the collection allowlist includes the asset
+
+    if cmp_pubkeys(ctx.accounts.authority_info.key, &metadata.update_authority) {
+        // the update authority can choose any rule set
+    } else if cmp_pubkeys(ctx.accounts.authority_info.key, &token.owner) {
+        let collection = metadata
+            .collection
+            .as_ref()
+            .filter(|c| c.verified)
+            .ok_or(MetadataError::MigrationNotAllowed)?;
+
+        let allowlist_info = ctx
+            .accounts
+            .migration_allowlist_info
+            .ok_or(MetadataError::MigrationNotAllowed)?;
+
+        let (allowlist_key, _) = find_migration_allowlist_account(&collection.key);
+        assert_keys_equal(&allowlist_key, allowlist_info.key)?;
+
+        let allowlist = MigrationAllowlist::from_account_info(allowlist_info)?;
+
+        if !allowlist.is_allowed(ctx.accounts.mint_info.key) {
+            return Err(MetadataError::MigrationNotAllowed.into());
+        }
+
+        // holders cannot choose the rule set of the asset
+        if allowlist.rule_set != rule_set {
+            return Err(MetadataError::InvalidAuthorizationRules.into());
+        }
+    } else {
+        return Err(MetadataError::InvalidAuthorityType.into());
+    }
+
+    let programmable_config = ProgrammableConfig::V1 { rule_set };
+
+    if rule_set.is_some() {
+        assert_valid_authorization(ctx.accounts.authorization_rules_info, &programmable_config)?;
+        // we can safely unwrap since the assertion checked for existence
+        assert_owned_by(
+            ctx.accounts.authorization_rules_info.unwrap(),
+            &mpl_token_auth_rules::ID,
+        )?;
+    }
+
+    // creates the token record of the asset
+
+    create_token_record_account(
+        program_id,
+        ctx.accounts.token_record_info,
+        ctx.accounts.mint_info,
+        ctx.accounts.token_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )?;
+
+    // an existing delegate is kept as a migration delegate; a token frozen by its
+    // delegate is considered locked
+    if let COption::Some(delegate) = token.delegate {
+        let mut token_record = TokenRecord::from_account_info(ctx.accounts.token_record_info)?;
+        token_record.delegate = Some(delegate);
+        token_record.delegate_role = Some(TokenDelegateRole::Migration);
+
+        if token.is_frozen() {
+            token_record.state = TokenState::Locked;
+            token_record.locked_by = Some(AuthorityType::TokenDelegate);
+        }
+
+        token_record.save(
+            ctx.accounts.token_record_info,
+            ctx.accounts.payer_info,
+            ctx.accounts.system_program_info,
+        )?;
+    }
+
+    // programmable assets are always in a frozen state
+    if !token.is_frozen() {
+        freeze(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.token_info.clone(),
+            ctx.accounts.edition_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        )?;
+    }
+
+    metadata.token_standard = Some(TokenStandard::ProgrammableNonFungible);
+    metadata.programmable_config = Some(programmable_config);
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    Ok(())
+}
+
+/// Migrates a `ProgrammableNonFungible` asset back to `NonFungible`, closing its token
+/// record and thawing the token account.
+fn migrate_to_nonfungible<'a>(
+    program_id: &Pubkey,
+    ctx: &Context<'a, Migrate<'a>>,
+    mut metadata: Metadata,
+    token: Account,
+    rule_set: Option<Pubkey>,
+) -> ProgramResult {
+    if !matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ) {
+        return Err(MetadataError::InvalidTokenStandard.into());
+    }
+
+    // the asset will not have a rule set after the migration
+    if rule_set.is_some() {
+        return Err(MetadataError::InvalidAuthorizationRules.into());
+    }
+
+    // authority must be the update authority (this also checks that it is a signer)
+    // and the token owner must consent to the migration
+
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+    assert_signer(ctx.accounts.token_owner_info)?;
+
+    // token record
+
+    assert_owned_by(ctx.accounts.token_record_info, program_id)?;
+    assert_derivation(
+        program_id,
+        ctx.accounts.token_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.mint_info.key.as_ref(),
+            TOKEN_RECORD_SEED.as_bytes(),
+            ctx.accounts.token_info.key.as_ref(),
+        ],
+    )?;
+
+    let token_record = TokenRecord::from_account_info(ctx.accounts.token_record_info)?;
+
+    if token_record.is_locked() {
+        return Err(MetadataError::LockedToken.into());
+    }
+
+    // validates the migration against the rule set of the asset
+
+    auth_rules_validate(AuthRulesValidateParams {
+        mint_info: ctx.accounts.mint_info,
+        owner_info: Some(ctx.accounts.token_owner_info),
+        authority_info: Some(ctx.accounts.authority_info),
+        source_info: None,
+        destination_info: None,
+        programmable_config: metadata.programmable_config.clone(),
+        amount: token.amount,
+        auth_data: None,
+        auth_rules_info: ctx.accounts.authorization_rules_info,
+        operation: Operation::Migrate {
+            scenario: MigrateScenario::NonFungible,
+        },
+        is_wallet_to_wallet: false,
+        rule_set_revision: token_record
+            .rule_set_revision
+            .map(|revision| revision as usize),
+    })?;
+
+    // non-programmable assets are not frozen by default
+    if token.is_frozen() {
+        thaw(
+            ctx.accounts.mint_info.clone(),
+            ctx.accounts.token_info.clone(),
+            ctx.accounts.edition_info.clone(),
+            ctx.accounts.spl_token_program_info.clone(),
+        )?;
+    }
+
+    // closes the token record after the CPI calls and returns the rent to the payer
+    close_program_account(
+        ctx.accounts.token_record_info,
+        ctx.accounts.payer_info,
+        Key::TokenRecord,
+    )?;
+
+    metadata.token_standard = Some(TokenStandard::NonFungible);
+    metadata.programmable_config = None;
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,6 @@
+#![allow(clippy::module_inception)]
+mod migrate;
+mod update_allowlist;
+
+pub use migrate::*;
+pub use update_allowlist::*;
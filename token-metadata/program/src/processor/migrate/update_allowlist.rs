@@ -0,0 +1,122 @@
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+use crate::{
+    assertions::{
+        assert_keys_equal, assert_owned_by, metadata::assert_update_authority_is_correct,
+    },
+    error::MetadataError,
+    instruction::{Context, UpdateMigrationAllowlist, UpdateMigrationAllowlistArgs},
+    pda::{find_migration_allowlist_account, PREFIX},
+    state::{Metadata, MigrationAllowlist, TokenMetadataAccount, MIGRATION_ALLOWLIST_SEED},
+};
+
+/// Creates or updates the migration allowlist of a collection.
+pub fn update_migration_allowlist<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UpdateMigrationAllowlistArgs,
+) -> ProgramResult {
+    let context = UpdateMigrationAllowlist::to_context(accounts)?;
+
+    match args {
+        UpdateMigrationAllowlistArgs::V1 {
+            rule_set,
+            allow_all,
+            add_mints,
+            remove_mints,
+        } => update_migration_allowlist_v1(
+            program_id,
+            context,
+            rule_set,
+            allow_all,
+            add_mints,
+            remove_mints,
+        ),
+    }
+}
+
+fn update_migration_allowlist_v1(
+    program_id: &Pubkey,
+    ctx: Context<UpdateMigrationAllowlist>,
+    rule_set: Option<Pubkey>,
+    allow_all: bool,
+    add_mints: Vec<Pubkey>,
+    remove_mints: Vec<Pubkey>,
+) -> ProgramResult {
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.collection_metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.collection_mint_info, &spl_token::ID)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    let (allowlist_key, bump) =
+        find_migration_allowlist_account(ctx.accounts.collection_mint_info.key);
+    assert_keys_equal(&allowlist_key, ctx.accounts.migration_allowlist_info.key)?;
+
+    // account relationships
+
+    let collection_metadata = Metadata::from_account_info(ctx.accounts.collection_metadata_info)?;
+
+    if collection_metadata.mint != *ctx.accounts.collection_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // authority must be the update authority of the collection (this also checks
+    // that it is a signer)
+    assert_update_authority_is_correct(&collection_metadata, ctx.accounts.authority_info)?;
+
+    let mut allowlist = if ctx.accounts.migration_allowlist_info.data_is_empty() {
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            ctx.accounts.collection_mint_info.key.as_ref(),
+            MIGRATION_ALLOWLIST_SEED.as_bytes(),
+            &[bump],
+        ];
+
+        let allowlist = MigrationAllowlist {
+            bump,
+            collection_mint: *ctx.accounts.collection_mint_info.key,
+            ..Default::default()
+        };
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.migration_allowlist_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            allowlist.data_len(),
+            &signer_seeds,
+        )?;
+
+        allowlist
+    } else {
+        MigrationAllowlist::from_account_info(ctx.accounts.migration_allowlist_info)?
+    };
+
+    allowlist.rule_set = rule_set;
+    allowlist.allow_all = allow_all;
+    allowlist.mints.retain(|mint| !remove_mints.contains(mint));
+
+    for mint in add_mints {
+        if !allowlist.mints.contains(&mint) {
+            allowlist.mints.push(mint);
+        }
+    }
+
+    allowlist.save(
+        ctx.accounts.migration_allowlist_info,
+        ctx.accounts.payer_info,
+        ctx.accounts.system_program_info,
+    )
+}
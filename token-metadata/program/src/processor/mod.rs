@@ -1,3 +1,4 @@
+mod attributes;
 mod bubblegum;
 mod burn;
 mod collection;
@@ -7,10 +8,12 @@ pub(crate) mod escrow;
 mod fee;
 mod freeze;
 mod metadata;
+mod migrate;
 mod state;
 mod uses;
 mod verification;
 
+pub use attributes::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 pub use bubblegum::*;
 pub use burn::*;
@@ -20,6 +23,7 @@ pub use edition::*;
 pub use escrow::*;
 pub use freeze::*;
 pub use metadata::*;
+pub use migrate::*;
 use mpl_token_auth_rules::payload::Payload;
 use mpl_utils::cmp_pubkeys;
 #[cfg(feature = "serde-feature")]
@@ -44,7 +48,7 @@ use crate::{
     processor::{
         edition::{
             process_convert_master_edition_v1_to_v2, process_create_master_edition,
-            process_mint_new_edition_from_master_edition_via_token,
+            process_mint_new_edition_from_master_edition_via_token, process_reprint_edition,
         },
         escrow::process_transfer_out_of_escrow,
     },
@@ -144,7 +148,10 @@ pub fn process_instruction<'a>(
             msg!("IX: Unlock");
             state::unlock(program_id, accounts, args)
         }
-        MetadataInstruction::Migrate => Err(MetadataError::Removed.into()),
+        MetadataInstruction::Migrate(args) => {
+            msg!("IX: Migrate");
+            migrate::migrate(program_id, accounts, args)
+        }
         MetadataInstruction::Transfer(args) => {
             msg!("IX: Transfer");
             metadata::transfer(program_id, accounts, args)
@@ -162,6 +169,78 @@ pub fn process_instruction<'a>(
             verification::unverify(program_id, accounts, args)
         }
         MetadataInstruction::Collect => fee::process_collect_fees(program_id, accounts),
+        MetadataInstruction::CreateAttributes(args) => {
+            msg!("IX: Create Attributes");
+            attributes::create_attributes(program_id, accounts, args)
+        }
+        MetadataInstruction::UpdateAttributes(args) => {
+            msg!("IX: Update Attributes");
+            attributes::update_attributes(program_id, accounts, args)
+        }
+        MetadataInstruction::RemoveAttribute(args) => {
+            msg!("IX: Remove Attribute");
+            attributes::remove_attribute(program_id, accounts, args)
+        }
+        MetadataInstruction::CloseAttributes => {
+            msg!("IX: Close Attributes");
+            attributes::close_attributes(program_id, accounts)
+        }
+        MetadataInstruction::UpdateCreators(args) => {
+            msg!("IX: Update Creators");
+            metadata::update_creators(program_id, accounts, args)
+        }
+        MetadataInstruction::EscrowTransferIn(args) => {
+            msg!("IX: Escrow Transfer In");
+            escrow::escrow_transfer_in(program_id, accounts, args)
+        }
+        MetadataInstruction::EscrowTransferOut(args) => {
+            msg!("IX: Escrow Transfer Out");
+            escrow::escrow_transfer_out(program_id, accounts, args)
+        }
+        MetadataInstruction::ResetPrimarySaleHappened => {
+            msg!("IX: Reset Primary Sale Happened");
+            metadata::reset_primary_sale_happened(program_id, accounts)
+        }
+        MetadataInstruction::UpdateMigrationAllowlist(args) => {
+            msg!("IX: Update Migration Allowlist");
+            migrate::update_migration_allowlist(program_id, accounts, args)
+        }
+        MetadataInstruction::ProposeUpdateAuthority(args) => {
+            msg!("IX: Propose Update Authority");
+            metadata::propose_update_authority(program_id, accounts, args)
+        }
+        MetadataInstruction::AcceptUpdateAuthority => {
+            msg!("IX: Accept Update Authority");
+            metadata::accept_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::EnableMetadataHistory(args) => {
+            msg!("IX: Enable Metadata History");
+            metadata::enable_metadata_history(program_id, accounts, args)
+        }
+        MetadataInstruction::SetRoyaltyConfig(args) => {
+            msg!("IX: Set Royalty Config");
+            metadata::set_royalty_config(program_id, accounts, args)
+        }
+        MetadataInstruction::Use(args) => {
+            msg!("IX: Use");
+            use_asset(program_id, accounts, args)
+        }
+        MetadataInstruction::SetUseConfig(args) => {
+            msg!("IX: Set Use Config");
+            set_use_config(program_id, accounts, args)
+        }
+        MetadataInstruction::SetEditionUriTemplate(args) => {
+            msg!("IX: Set Edition URI Template");
+            edition::set_edition_uri_template(program_id, accounts, args)
+        }
+        MetadataInstruction::SetEditionUri(args) => {
+            msg!("IX: Set Edition URI");
+            edition::set_edition_uri(program_id, accounts, args)
+        }
+        MetadataInstruction::SetEditionNumbering(args) => {
+            msg!("IX: Set Edition Numbering");
+            edition::set_edition_numbering(program_id, accounts, args)
+        }
         _ => {
             // pNFT accounts can only be used by the "new" API; before forwarding
             // the transaction to the "legacy" processor we determine whether we are
@@ -337,6 +416,10 @@ fn process_legacy_instruction<'a>(
             msg!("IX: Transfer Out Of Escrow");
             process_transfer_out_of_escrow(program_id, accounts, args)
         }
+        MetadataInstruction::ReprintEdition(args) => {
+            msg!("IX: Reprint Edition");
+            process_reprint_edition(program_id, accounts, args.edition)
+        }
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
@@ -359,6 +442,19 @@ pub fn next_optional_account_info<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>
     })
 }
 
+/// Convenience function for accessing the next item in an [`AccountInfo`]
+/// iterator when the account is optional and can be omitted from the end of
+/// the accounts list.
+///
+/// As with [`next_optional_account_info`], the `crate::ID` pubkey is used for
+/// accounts that are not set but followed by other accounts.
+pub fn next_trailing_optional_account_info<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+) -> Option<I::Item> {
+    iter.next()
+        .filter(|account_info| !cmp_pubkeys(account_info.key, &crate::ID))
+}
+
 /// Convenience function for accessing an [`AccountInfo`] by index
 /// and validating whether the account is present or not.
 ///
@@ -399,9 +495,9 @@ pub fn try_get_optional_account_info<'a>(
     }
 }
 
-/// Checks if the instruction's accounts contain a pNFT metadata.
+/// Checks if the instruction's accounts contain a programmable metadata.
 ///
-/// We need to determine if we are dealing with a pNFT metadata or not
+/// We need to determine if we are dealing with a programmable metadata or not
 /// so we can restrict the available instructions.
 fn has_programmable_metadata(
     program_id: &Pubkey,
@@ -418,6 +514,8 @@ fn has_programmable_metadata(
                 if matches!(
                     metadata.token_standard,
                     Some(TokenStandard::ProgrammableNonFungible)
+                        | Some(TokenStandard::ProgrammableFungibleAsset)
+                        | Some(TokenStandard::ProgrammableNonTransferable)
                 ) {
                     return Ok(true);
                 }
@@ -14,11 +14,12 @@ pub fn lock<'a>(
     let context = Lock::to_context(accounts)?;
 
     match args {
-        LockArgs::V1 { .. } => toggle_asset_state(
+        LockArgs::V1 { authorization_data } => toggle_asset_state(
             program_id,
             super::ToggleAccounts {
                 payer_info: context.accounts.payer_info,
                 authority_info: context.accounts.authority_info,
+                token_owner_info: context.accounts.token_owner_info,
                 mint_info: context.accounts.mint_info,
                 token_info: context.accounts.token_info,
                 edition_info: context.accounts.edition_info,
@@ -27,9 +28,13 @@ pub fn lock<'a>(
                 system_program_info: context.accounts.system_program_info,
                 sysvar_instructions_info: context.accounts.sysvar_instructions_info,
                 spl_token_program_info: context.accounts.spl_token_program_info,
+                authorization_rules_program_info: context.accounts.authorization_rules_program_info,
+                authorization_rules_info: context.accounts.authorization_rules_info,
+                remaining_accounts: context.remaining_accounts,
             },
             TokenState::Unlocked,
             TokenState::Locked,
+            authorization_data,
         ),
     }
 }
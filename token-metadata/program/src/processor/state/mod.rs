@@ -1,36 +1,75 @@
 mod lock;
 mod unlock;
 
-use borsh::BorshSerialize;
+use std::fmt::Display;
+
 pub use lock::*;
-use mpl_utils::assert_signer;
+use mpl_utils::{assert_signer, cmp_pubkeys, token::unpack};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program::invoke,
-    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, system_program, sysvar,
-};
-use spl_token::{
-    instruction::{freeze_account, thaw_account},
-    state::{Account, Mint},
+    program_error::ProgramError, pubkey::Pubkey, system_program, sysvar,
 };
+use spl_token::state::{Account, Mint};
+use spl_token_2022::instruction::{freeze_account, thaw_account};
 pub use unlock::*;
 
 use crate::{
     assertions::{assert_keys_equal, metadata::assert_state},
     error::MetadataError,
-    pda::find_token_record_account,
+    instruction::MetadataDelegateRole,
+    pda::{find_metadata_delegate_record_account, find_token_record_account},
+    processor::AuthorizationData,
     state::{
-        AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, TokenDelegateRole,
-        TokenMetadataAccount, TokenRecord, TokenStandard, TokenState,
+        AuthorityRequest, AuthorityResponse, AuthorityType, Metadata, MetadataDelegateRecord,
+        Operation, Resizable, TokenDelegateRole, TokenMetadataAccount, TokenRecord, TokenStandard,
+        TokenState,
     },
     utils::{
         assert_delegated_tokens, assert_freeze_authority_matches_mint, assert_initialized,
-        assert_owned_by, freeze, thaw,
+        assert_owned_by, assert_owned_by_token_program, auth_rules_validate, freeze, thaw,
+        AuthRulesValidateParams,
     },
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockScenario {
+    UtilityDelegate,
+    StakingDelegate,
+    LockedTransferDelegate,
+    MigrationDelegate,
+    CollectionLockDelegate,
+}
+
+impl Display for LockScenario {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UtilityDelegate => write!(f, "UtilityDelegate"),
+            Self::StakingDelegate => write!(f, "StakingDelegate"),
+            Self::LockedTransferDelegate => write!(f, "LockedTransferDelegate"),
+            Self::MigrationDelegate => write!(f, "MigrationDelegate"),
+            Self::CollectionLockDelegate => write!(f, "CollectionLockDelegate"),
+        }
+    }
+}
+
+impl TryFrom<TokenDelegateRole> for LockScenario {
+    type Error = MetadataError;
+
+    fn try_from(role: TokenDelegateRole) -> Result<Self, Self::Error> {
+        match role {
+            TokenDelegateRole::Utility => Ok(Self::UtilityDelegate),
+            TokenDelegateRole::Staking => Ok(Self::StakingDelegate),
+            TokenDelegateRole::LockedTransfer => Ok(Self::LockedTransferDelegate),
+            TokenDelegateRole::Migration => Ok(Self::MigrationDelegate),
+            _ => Err(MetadataError::InvalidDelegateRole),
+        }
+    }
+}
+
 pub(crate) struct ToggleAccounts<'a> {
     payer_info: &'a AccountInfo<'a>,
     authority_info: &'a AccountInfo<'a>,
+    token_owner_info: Option<&'a AccountInfo<'a>>,
     mint_info: &'a AccountInfo<'a>,
     token_info: &'a AccountInfo<'a>,
     metadata_info: &'a AccountInfo<'a>,
@@ -39,6 +78,9 @@ pub(crate) struct ToggleAccounts<'a> {
     system_program_info: &'a AccountInfo<'a>,
     sysvar_instructions_info: &'a AccountInfo<'a>,
     spl_token_program_info: Option<&'a AccountInfo<'a>>,
+    authorization_rules_program_info: Option<&'a AccountInfo<'a>>,
+    authorization_rules_info: Option<&'a AccountInfo<'a>>,
+    remaining_accounts: Vec<&'a AccountInfo<'a>>,
 }
 
 pub(crate) fn toggle_asset_state(
@@ -46,6 +88,7 @@ pub(crate) fn toggle_asset_state(
     accounts: ToggleAccounts,
     from: TokenState,
     to: TokenState,
+    auth_data: Option<AuthorizationData>,
 ) -> ProgramResult {
     // signers
 
@@ -55,8 +98,8 @@ pub(crate) fn toggle_asset_state(
     // ownership
 
     assert_owned_by(accounts.metadata_info, program_id)?;
-    assert_owned_by(accounts.mint_info, &spl_token::ID)?;
-    assert_owned_by(accounts.token_info, &spl_token::ID)?;
+    assert_owned_by_token_program(accounts.mint_info)?;
+    assert_owned_by(accounts.token_info, accounts.mint_info.owner)?;
 
     // key match
 
@@ -66,6 +109,10 @@ pub(crate) fn toggle_asset_state(
         &sysvar::instructions::ID,
     )?;
 
+    if let Some(auth_rules_program_info) = accounts.authorization_rules_program_info {
+        assert_keys_equal(auth_rules_program_info.key, &mpl_token_auth_rules::ID)?;
+    }
+
     // account relationships
 
     let metadata = Metadata::from_account_info(accounts.metadata_info)?;
@@ -74,7 +121,7 @@ pub(crate) fn toggle_asset_state(
         return Err(MetadataError::MintMismatch.into());
     }
 
-    let token = Account::unpack(&accounts.token_info.try_borrow_data()?)?;
+    let token: Account = unpack(&accounts.token_info.try_borrow_data()?)?;
     // token mint must match mint account key
     if token.mint != *accounts.mint_info.key {
         return Err(MetadataError::MintMismatch.into());
@@ -85,22 +132,56 @@ pub(crate) fn toggle_asset_state(
     }
 
     // authority – this can be either:
-    //  1. token delegate (programmable non-fungible): valid token_record.delegate
-    //  2. spl-delegate (non-fungibles): authority == token.delegate
-    //  3. freeze authority (fungibles): authority == freeze_authority
+    //  1. token delegate (programmable assets): valid token_record.delegate
+    //  2. collection lock delegate (programmable assets): valid delegate record on
+    //     the verified collection of the asset
+    //  3. spl-delegate (non-fungibles): authority == token.delegate
+    //  4. freeze authority (fungibles): authority == freeze_authority
 
     if matches!(
         metadata.token_standard,
         Some(TokenStandard::ProgrammableNonFungible)
+            | Some(TokenStandard::ProgrammableFungibleAsset)
+            | Some(TokenStandard::ProgrammableNonTransferable)
     ) {
+        // collection lock delegates are set on the collection mint, so they can only
+        // be used on assets of a verified collection; the delegate record is passed as
+        // a remaining account
+        let collection_mint = metadata
+            .collection
+            .as_ref()
+            .filter(|collection| collection.verified)
+            .map(|collection| collection.key);
+
+        let delegate_record_info = collection_mint.and_then(|collection_mint| {
+            let (delegate_record_key, _) = find_metadata_delegate_record_account(
+                &collection_mint,
+                MetadataDelegateRole::CollectionLock,
+                &metadata.update_authority,
+                accounts.authority_info.key,
+            );
+
+            accounts
+                .remaining_accounts
+                .iter()
+                .find(|info| cmp_pubkeys(info.key, &delegate_record_key))
+                .copied()
+        });
+
         let AuthorityResponse { authority_type, .. } =
             AuthorityType::get_authority_type(AuthorityRequest {
-                precedence: &[AuthorityType::TokenDelegate],
+                precedence: &[
+                    AuthorityType::TokenDelegate,
+                    AuthorityType::MetadataDelegate,
+                ],
                 authority: accounts.authority_info.key,
                 update_authority: &metadata.update_authority,
                 mint: accounts.mint_info.key,
+                collection_mint: collection_mint.as_ref(),
                 token: Some(accounts.token_info.key),
                 token_account: Some(&token),
+                metadata_delegate_record_info: delegate_record_info,
+                collection_metadata_delegate_roles: vec![MetadataDelegateRole::CollectionLock],
                 token_record_info: accounts.token_record_info,
                 token_delegate_roles: vec![
                     TokenDelegateRole::Utility,
@@ -111,7 +192,10 @@ pub(crate) fn toggle_asset_state(
                 ..Default::default()
             })?;
         // only a delegate can lock/unlock
-        if !matches!(authority_type, AuthorityType::TokenDelegate) {
+        if !matches!(
+            authority_type,
+            AuthorityType::TokenDelegate | AuthorityType::MetadataDelegate
+        ) {
             return Err(MetadataError::InvalidAuthorityType.into());
         }
 
@@ -136,17 +220,80 @@ pub(crate) fn toggle_asset_state(
 
         // make sure we are on the expected state
         assert_state(&token_record, from)?;
-        // for pNFTs, we only need to flip the programmable state
+
+        // only the same type of authority that locked the token can unlock it; tokens
+        // locked before the authority type was stored can be unlocked by either
+        if matches!(to, TokenState::Unlocked)
+            && matches!(&token_record.locked_by, Some(locked_by) if *locked_by != authority_type)
+        {
+            return Err(MetadataError::InvalidAuthorityType.into());
+        }
+
+        // validates the operation against the rule set (if any); the delegate role
+        // is present when the authority is a token delegate
+        let scenario = match authority_type {
+            AuthorityType::MetadataDelegate => LockScenario::CollectionLockDelegate,
+            _ => LockScenario::try_from(
+                token_record
+                    .delegate_role
+                    .ok_or(MetadataError::MissingDelegateRole)?,
+            )?,
+        };
+
+        // the token owner account is optional, so it is only used if it matches
+        // the owner of the token account
+        let owner_info = accounts
+            .token_owner_info
+            .filter(|info| cmp_pubkeys(info.key, &token.owner));
+
+        auth_rules_validate(AuthRulesValidateParams {
+            mint_info: accounts.mint_info,
+            owner_info,
+            authority_info: Some(accounts.authority_info),
+            source_info: None,
+            destination_info: None,
+            programmable_config: metadata.programmable_config.clone(),
+            amount: token.amount,
+            auth_data,
+            auth_rules_info: accounts.authorization_rules_info,
+            operation: match to {
+                TokenState::Locked => Operation::Lock { scenario },
+                _ => Operation::Unlock { scenario },
+            },
+            is_wallet_to_wallet: false,
+            rule_set_revision: token_record
+                .rule_set_revision
+                .map(|revision| revision as usize),
+        })?;
+        // for programmable assets, we only need to flip the programmable state
+        // and keep track of the authority that locked the token
+        token_record.locked_by = match to {
+            TokenState::Locked => Some(authority_type.clone()),
+            _ => None,
+        };
         token_record.state = to;
 
-        // save the state
-        token_record
-            .serialize(&mut *token_record_info.try_borrow_mut_data()?)
-            .map_err(|_| MetadataError::BorshSerializationError.into())
+        // save the state, resizing token records created before the authority
+        // type was stored
+        token_record.save(
+            token_record_info,
+            accounts.payer_info,
+            accounts.system_program_info,
+        )?;
+
+        // limited delegates consume one use per instruction
+        if let (AuthorityType::MetadataDelegate, Some(delegate_record_info)) =
+            (&authority_type, delegate_record_info)
+        {
+            MetadataDelegateRecord::consume_use(delegate_record_info)?;
+        }
+
+        Ok(())
     } else {
         let spl_token_program_info = match accounts.spl_token_program_info {
             Some(spl_token_program_info) => {
-                assert_keys_equal(spl_token_program_info.key, &spl_token::ID)?;
+                // the token program must be the owner of the mint (SPL Token or Token-2022)
+                assert_keys_equal(spl_token_program_info.key, accounts.mint_info.owner)?;
                 spl_token_program_info
             }
             None => {
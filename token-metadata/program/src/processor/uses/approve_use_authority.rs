@@ -2,9 +2,11 @@ use borsh::BorshSerialize;
 use mpl_utils::{assert_signer, create_or_allocate_account_raw};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     program::invoke,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 use spl_token::instruction::approve;
 
@@ -108,5 +110,7 @@ pub fn process_approve_use_authority(
     record.allowed_uses = number_of_uses;
     record.bump = bump_seed;
     record.serialize(mutable_data)?;
+    // allowed uses of refill assets start refilling from the approval
+    UseAuthorityRecord::set_last_refill_at(mutable_data, Clock::get()?.unix_timestamp);
     Ok(())
 }
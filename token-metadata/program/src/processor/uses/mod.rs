@@ -1,7 +1,11 @@
 mod approve_use_authority;
 mod revoke_use_authority;
+mod set_use_config;
+mod use_asset;
 mod utilize;
 
 pub use approve_use_authority::*;
 pub use revoke_use_authority::*;
+pub use set_use_config::*;
+pub use use_asset::*;
 pub use utilize::*;
@@ -0,0 +1,117 @@
+use borsh::BorshSerialize;
+use mpl_utils::{assert_signer, create_or_allocate_account_raw};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, pubkey::Pubkey,
+    system_program, sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::{
+        assert_keys_equal, assert_owned_by, metadata::assert_update_authority_is_correct,
+    },
+    error::MetadataError,
+    instruction::{Context, SetUseConfig, SetUseConfigArgs},
+    pda::{find_use_config_account, PREFIX},
+    state::{Metadata, TokenMetadataAccount, UseConfig, USE_CONFIG_SEED, USE_CONFIG_SIZE},
+};
+
+/// Creates or updates the use config of an asset, which controls the refills and the
+/// minimum interval between uses.
+pub fn set_use_config<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetUseConfigArgs,
+) -> ProgramResult {
+    let context = SetUseConfig::to_context(accounts)?;
+
+    match args {
+        SetUseConfigArgs::V1 {
+            refill_period,
+            refill_amount,
+            min_use_interval,
+        } => set_use_config_v1(
+            program_id,
+            context,
+            refill_period,
+            refill_amount,
+            min_use_interval,
+        ),
+    }
+}
+
+fn set_use_config_v1(
+    program_id: &Pubkey,
+    ctx: Context<SetUseConfig>,
+    refill_period: i64,
+    refill_amount: u64,
+    min_use_interval: i64,
+) -> ProgramResult {
+    if refill_period < 0 || min_use_interval < 0 {
+        return Err(MetadataError::InvalidUseConfig.into());
+    }
+
+    // signers
+
+    assert_signer(ctx.accounts.payer_info)?;
+
+    // ownership
+
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+
+    // key match
+
+    assert_keys_equal(ctx.accounts.system_program_info.key, &system_program::ID)?;
+
+    let metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    let (use_config_key, bump) = find_use_config_account(&metadata.mint);
+    assert_keys_equal(&use_config_key, ctx.accounts.use_config_info.key)?;
+
+    // authority must be the update authority (this also checks that it is a signer)
+    assert_update_authority_is_correct(&metadata, ctx.accounts.authority_info)?;
+
+    if metadata.uses.is_none() {
+        return Err(MetadataError::Unusable.into());
+    }
+
+    let mut use_config = if ctx.accounts.use_config_info.data_is_empty() {
+        let signer_seeds = [
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            USE_CONFIG_SEED.as_bytes(),
+            &[bump],
+        ];
+
+        create_or_allocate_account_raw(
+            *program_id,
+            ctx.accounts.use_config_info,
+            ctx.accounts.system_program_info,
+            ctx.accounts.payer_info,
+            USE_CONFIG_SIZE,
+            &signer_seeds,
+        )?;
+
+        UseConfig {
+            bump,
+            mint: metadata.mint,
+            last_refill_at: Clock::get()?.unix_timestamp,
+            ..Default::default()
+        }
+    } else {
+        let use_config = UseConfig::from_account_info(ctx.accounts.use_config_info)?;
+        // the config is fixed once the asset was used, as it is with the uses
+        if use_config.last_used_at != 0 {
+            return Err(MetadataError::CannotChangeUsesAfterFirstUse.into());
+        }
+        use_config
+    };
+
+    use_config.refill_period = refill_period;
+    use_config.refill_amount = refill_amount;
+    use_config.min_use_interval = min_use_interval;
+
+    use_config.serialize(&mut *ctx.accounts.use_config_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
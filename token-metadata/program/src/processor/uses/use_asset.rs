@@ -0,0 +1,216 @@
+use borsh::BorshSerialize;
+use mpl_utils::{
+    assert_signer,
+    token::{spl_token_burn, TokenBurnParams},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+use spl_token::state::Account;
+
+use super::{consume_use_authority_uses, consume_uses, load_use_config, UseScenario};
+use crate::{
+    assertions::{assert_initialized, assert_owned_by, uses::assert_burner},
+    error::MetadataError,
+    instruction::{Context, Use, UseArgs},
+    pda::{find_program_as_burner_account, find_use_config_account},
+    state::{
+        Metadata, Operation, TokenMetadataAccount, TokenStandard, UseMethod, Uses, BURN, PREFIX,
+    },
+    utils::{auth_rules_validate, clean_write_metadata, AuthRulesValidateParams},
+};
+
+/// Uses an asset, consuming one of its uses.
+///
+/// The authority can be the token owner or a use authority, in which case the use
+/// authority record must be passed as the delegate record. For the `Refill` use method,
+/// the use config account must be passed as a remaining account; the program as burner
+/// account is expected as a remaining account when a use authority burns the asset on
+/// its last use.
+pub fn use_asset<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UseArgs,
+) -> ProgramResult {
+    let context = Use::to_context(accounts)?;
+
+    match args {
+        UseArgs::V1 { .. } => use_v1(program_id, context, args),
+    }
+}
+
+fn use_v1<'a>(program_id: &Pubkey, ctx: Context<'a, Use<'a>>, args: UseArgs) -> ProgramResult {
+    let UseArgs::V1 { authorization_data } = args;
+    let number_of_uses = 1;
+
+    // ownership
+
+    assert_signer(ctx.accounts.authority_info)?;
+    assert_owned_by(ctx.accounts.metadata_info, program_id)?;
+    assert_owned_by(ctx.accounts.mint_info, &spl_token::ID)?;
+
+    let token_info = ctx
+        .accounts
+        .token_info
+        .ok_or(MetadataError::MissingTokenAccount)?;
+    assert_owned_by(token_info, &spl_token::ID)?;
+
+    let mut metadata = Metadata::from_account_info(ctx.accounts.metadata_info)?;
+
+    if metadata.mint != *ctx.accounts.mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // the token account must hold the asset
+
+    let token: Account = assert_initialized(token_info)?;
+
+    if token.mint != metadata.mint {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token.amount == 0 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    let use_method = metadata
+        .uses
+        .as_ref()
+        .map(|uses| uses.use_method.clone())
+        .ok_or(MetadataError::Unusable)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let (use_config_key, _) = find_use_config_account(&metadata.mint);
+    let use_config_info = ctx
+        .remaining_accounts
+        .iter()
+        .find(|info| *info.key == use_config_key)
+        .copied();
+
+    let mut use_config = match (&use_method, use_config_info) {
+        (UseMethod::Refill, Some(use_config_info)) => {
+            Some(load_use_config(&metadata.mint, use_config_info)?)
+        }
+        (UseMethod::Refill, None) => return Err(MetadataError::MissingUseConfig.into()),
+        _ => None,
+    };
+
+    let remaining_uses = consume_uses(&mut metadata, use_config.as_mut(), number_of_uses, now)?;
+
+    // the authority must be the token owner or a use authority
+
+    let scenario = if let Some(use_authority_record_info) = ctx.accounts.delegate_record_info {
+        consume_use_authority_uses(
+            program_id,
+            use_authority_record_info,
+            ctx.accounts.authority_info,
+            ctx.accounts.mint_info,
+            use_config.as_ref(),
+            metadata
+                .uses
+                .as_ref()
+                .map(|uses| uses.total)
+                .unwrap_or_default(),
+            number_of_uses,
+            now,
+        )?;
+        UseScenario::UseAuthority
+    } else if token.owner == *ctx.accounts.authority_info.key {
+        UseScenario::Holder
+    } else {
+        return Err(MetadataError::InvalidUser.into());
+    };
+
+    auth_rules_validate(AuthRulesValidateParams {
+        mint_info: ctx.accounts.mint_info,
+        owner_info: None,
+        authority_info: Some(ctx.accounts.authority_info),
+        source_info: None,
+        destination_info: None,
+        programmable_config: metadata.programmable_config.clone(),
+        amount: number_of_uses,
+        auth_data: authorization_data,
+        auth_rules_info: ctx.accounts.authorization_rules_info,
+        operation: Operation::Use { scenario },
+        is_wallet_to_wallet: false,
+        rule_set_revision: None,
+    })?;
+
+    let must_burn = remaining_uses == 0
+        && matches!(
+            metadata.uses,
+            Some(Uses {
+                use_method: UseMethod::Burn,
+                ..
+            })
+        );
+
+    // programmable assets are always frozen, so they cannot be burned here
+    if must_burn
+        && matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+                | Some(TokenStandard::ProgrammableFungibleAsset)
+                | Some(TokenStandard::ProgrammableNonTransferable)
+        )
+    {
+        return Err(MetadataError::InvalidUseMethod.into());
+    }
+
+    clean_write_metadata(&mut metadata, ctx.accounts.metadata_info)?;
+
+    if let (Some(use_config), Some(use_config_info)) = (use_config, use_config_info) {
+        use_config.serialize(&mut *use_config_info.try_borrow_mut_data()?)?;
+    }
+
+    if must_burn {
+        let spl_token_program_info = ctx
+            .accounts
+            .spl_token_program_info
+            .ok_or(MetadataError::MissingSplTokenProgram)?;
+
+        if *spl_token_program_info.key != spl_token::ID {
+            return Err(MetadataError::InvalidTokenProgram.into());
+        }
+
+        match scenario {
+            UseScenario::UseAuthority => {
+                let (burner_key, _) = find_program_as_burner_account();
+                let burner_info = ctx
+                    .remaining_accounts
+                    .iter()
+                    .find(|info| *info.key == burner_key)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let seed = assert_burner(burner_info.key)?;
+                let burn_bump_ref = &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    BURN.as_bytes(),
+                    &[seed],
+                ];
+                spl_token_burn(TokenBurnParams {
+                    mint: ctx.accounts.mint_info.clone(),
+                    amount: 1,
+                    authority: (*burner_info).clone(),
+                    token_program: spl_token_program_info.clone(),
+                    source: token_info.clone(),
+                    authority_signer_seeds: Some(burn_bump_ref),
+                })?;
+            }
+            UseScenario::Holder => {
+                spl_token_burn(TokenBurnParams {
+                    mint: ctx.accounts.mint_info.clone(),
+                    amount: 1,
+                    authority: ctx.accounts.authority_info.clone(),
+                    token_program: spl_token_program_info.clone(),
+                    source: token_info.clone(),
+                    authority_signer_seeds: None,
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use borsh::BorshSerialize;
 use mpl_utils::{
     assert_signer,
@@ -5,10 +7,12 @@ use mpl_utils::{
 };
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
+    program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
-    sysvar::SysvarId,
+    sysvar::{Sysvar, SysvarId},
 };
 
 use crate::{
@@ -21,9 +25,34 @@ use crate::{
         },
     },
     error::MetadataError,
-    state::{Metadata, TokenMetadataAccount, UseAuthorityRecord, UseMethod, Uses, BURN, PREFIX},
+    pda::find_use_config_account,
+    state::{
+        Metadata, TokenMetadataAccount, UseAuthorityRecord, UseConfig, UseMethod, Uses, BURN,
+        PREFIX,
+    },
+    utils::clean_write_metadata,
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UseScenario {
+    Holder,
+    UseAuthority,
+}
+
+impl Display for UseScenario {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Holder => write!(f, "Owner"),
+            Self::UseAuthority => write!(f, "UseAuthority"),
+        }
+    }
+}
+
+/// Uses an asset.
+///
+/// The use authority record, burner and use config accounts are optional and follow the
+/// system program (and rent, if passed). Omitted accounts in between are set to the program
+/// id. Programmable assets are used through the `Use` instruction instead.
 pub fn process_utilize(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -41,16 +70,18 @@ pub fn process_utilize(
     let _system_program_account_info = next_account_info(account_info_iter)?;
 
     // consume the next account only if it is Rent
-    let approved_authority_is_using = if account_info_iter
-        .next_if(|info| info.key == &Rent::id())
-        .is_some()
-    {
-        // rent was passed in
-        accounts.len() == 11
-    } else {
-        // necessary accounts is one less if rent isn't passed in.
-        accounts.len() == 10
+    account_info_iter.next_if(|info| info.key == &Rent::id());
+
+    let mut next_optional_account = || {
+        account_info_iter
+            .next()
+            .filter(|info| info.key != program_id)
     };
+    let use_authority_record_info = next_optional_account();
+    let burner_info = next_optional_account();
+    let use_config_info = next_optional_account();
+
+    let approved_authority_is_using = use_authority_record_info.is_some();
 
     let metadata: Metadata = Metadata::from_account_info(metadata_info)?;
 
@@ -70,49 +101,50 @@ pub fn process_utilize(
         token_account_info,
     )?;
     let mut metadata = Metadata::from_account_info(metadata_info)?;
-    let metadata_uses = metadata.uses.unwrap();
-    let must_burn = metadata_uses.use_method == UseMethod::Burn;
-    if number_of_uses > metadata_uses.total || number_of_uses > metadata_uses.remaining {
-        return Err(MetadataError::NotEnoughUses.into());
-    }
-    let remaining_uses = metadata_uses
-        .remaining
-        .checked_sub(number_of_uses)
-        .ok_or(MetadataError::NotEnoughUses)?;
-    metadata.uses = Some(Uses {
-        use_method: metadata_uses.use_method,
-        total: metadata_uses.total,
-        remaining: remaining_uses,
-    });
-    if approved_authority_is_using {
-        let use_authority_record_info = next_account_info(account_info_iter)?;
-        let data = &mut *use_authority_record_info.try_borrow_mut_data()?;
-        process_use_authority_validation(data.len(), false)?;
-        assert_owned_by(use_authority_record_info, program_id)?;
-        let canonical_bump = assert_use_authority_derivation(
+    let must_burn = matches!(
+        metadata.uses,
+        Some(Uses {
+            use_method: UseMethod::Burn,
+            ..
+        })
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut use_config = match use_config_info {
+        Some(use_config_info) => Some(load_use_config(&metadata.mint, use_config_info)?),
+        None => None,
+    };
+
+    let remaining_uses = consume_uses(&mut metadata, use_config.as_mut(), number_of_uses, now)?;
+
+    if let Some(use_authority_record_info) = use_authority_record_info {
+        consume_use_authority_uses(
             program_id,
             use_authority_record_info,
             user_info,
             mint_info,
+            use_config.as_ref(),
+            metadata
+                .uses
+                .as_ref()
+                .map(|uses| uses.total)
+                .unwrap_or_default(),
+            number_of_uses,
+            now,
         )?;
-        let mut record = UseAuthorityRecord::from_bytes(data)?;
-        // Migrates old UARs to having the bump stored
-        if record.bump_empty() {
-            record.bump = canonical_bump;
-        }
-        assert_valid_bump(canonical_bump, &record)?;
-        record.allowed_uses = record
-            .allowed_uses
-            .checked_sub(number_of_uses)
-            .ok_or(MetadataError::NotEnoughUses)?;
-        record.serialize(data)?;
     } else if user_info.key != owner_info.key {
         return Err(MetadataError::InvalidUser.into());
     }
-    metadata.save(&mut metadata_info.try_borrow_mut_data()?)?;
+
+    clean_write_metadata(&mut metadata, metadata_info)?;
+
+    if let (Some(use_config), Some(use_config_info)) = (use_config, use_config_info) {
+        use_config.serialize(&mut *use_config_info.try_borrow_mut_data()?)?;
+    }
+
     if remaining_uses == 0 && must_burn {
         if approved_authority_is_using {
-            let burn_authority_info = next_account_info(account_info_iter)?;
+            let burn_authority_info = burner_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
             let seed = assert_burner(burn_authority_info.key)?;
             let burn_bump_ref = &[
                 PREFIX.as_bytes(),
@@ -141,3 +173,104 @@ pub fn process_utilize(
     }
     Ok(())
 }
+
+/// Loads the use config of an asset, which holds the refill and cooldown settings of the
+/// `Refill` use method.
+pub(crate) fn load_use_config(
+    mint: &Pubkey,
+    use_config_info: &AccountInfo,
+) -> Result<UseConfig, ProgramError> {
+    let (use_config_key, _) = find_use_config_account(mint);
+
+    if use_config_key != *use_config_info.key || use_config_info.data_is_empty() {
+        return Err(MetadataError::MissingUseConfig.into());
+    }
+
+    UseConfig::from_account_info(use_config_info)
+}
+
+/// Consumes uses of an asset, returning the remaining uses. For the `Refill` use method,
+/// the uses are refilled and the minimum interval between uses is enforced.
+pub(crate) fn consume_uses(
+    metadata: &mut Metadata,
+    use_config: Option<&mut UseConfig>,
+    number_of_uses: u64,
+    now: i64,
+) -> Result<u64, ProgramError> {
+    let mut uses = metadata.uses.clone().ok_or(MetadataError::Unusable)?;
+
+    if uses.use_method == UseMethod::Refill {
+        let use_config = use_config.ok_or(MetadataError::MissingUseConfig)?;
+        use_config.assert_cooldown_elapsed(now)?;
+        use_config.refill(&mut uses, now);
+        use_config.last_used_at = now;
+    }
+
+    if number_of_uses > uses.total || number_of_uses > uses.remaining {
+        return Err(MetadataError::NotEnoughUses.into());
+    }
+
+    uses.remaining = uses
+        .remaining
+        .checked_sub(number_of_uses)
+        .ok_or(MetadataError::NotEnoughUses)?;
+
+    let remaining_uses = uses.remaining;
+    metadata.uses = Some(uses);
+
+    Ok(remaining_uses)
+}
+
+/// Consumes the allowed uses of a use authority record. For the `Refill` use method, the
+/// allowed uses are refilled at the same rate as the asset, up to its total uses.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn consume_use_authority_uses(
+    program_id: &Pubkey,
+    use_authority_record_info: &AccountInfo,
+    user_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    use_config: Option<&UseConfig>,
+    total_uses: u64,
+    number_of_uses: u64,
+    now: i64,
+) -> ProgramResult {
+    let data = &mut *use_authority_record_info.try_borrow_mut_data()?;
+    process_use_authority_validation(data.len(), false)?;
+    assert_owned_by(use_authority_record_info, program_id)?;
+    let canonical_bump = assert_use_authority_derivation(
+        program_id,
+        use_authority_record_info,
+        user_info,
+        mint_info,
+    )?;
+    let mut record = UseAuthorityRecord::from_bytes(data)?;
+    // Migrates old UARs to having the bump stored
+    if record.bump_empty() {
+        record.bump = canonical_bump;
+    }
+    assert_valid_bump(canonical_bump, &record)?;
+
+    let last_refill_at = if let Some(use_config) = use_config {
+        match UseAuthorityRecord::last_refill_at(data) {
+            // records approved before the refill timestamp was stored start refilling now
+            0 => now,
+            last_refill_at => {
+                let (allowed_uses, last_refill_at) =
+                    use_config.refilled(record.allowed_uses, total_uses, last_refill_at, now);
+                record.allowed_uses = allowed_uses;
+                last_refill_at
+            }
+        }
+    } else {
+        UseAuthorityRecord::last_refill_at(data)
+    };
+
+    record.allowed_uses = record
+        .allowed_uses
+        .checked_sub(number_of_uses)
+        .ok_or(MetadataError::NotEnoughUses)?;
+    record.serialize(data)?;
+    UseAuthorityRecord::set_last_refill_at(data, last_refill_at);
+
+    Ok(())
+}
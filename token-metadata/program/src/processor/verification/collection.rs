@@ -5,8 +5,13 @@ use crate::{
     },
     error::MetadataError,
     instruction::{Context, MetadataDelegateRole, Unverify, Verify},
-    state::{AuthorityRequest, AuthorityType, Metadata, TokenMetadataAccount},
-    utils::{clean_write_metadata, decrement_collection_size, increment_collection_size},
+    state::{
+        AuthorityRequest, AuthorityType, Metadata, MetadataDelegateRecord, TokenMetadataAccount,
+    },
+    utils::{
+        clean_write_metadata, decrement_collection_size, increment_collection_size,
+        propagate_collection_size_change,
+    },
 };
 use mpl_utils::assert_signer;
 use solana_program::{entrypoint::ProgramResult, pubkey::Pubkey};
@@ -81,15 +86,39 @@ pub(crate) fn verify_collection_v1(program_id: &Pubkey, ctx: Context<Verify>) ->
         _ => return Err(MetadataError::UpdateAuthorityIncorrect.into()),
     }
 
+    // limited delegates consume one use per instruction
+    if let (AuthorityType::MetadataDelegate, Some(delegate_record_info)) = (
+        &authority_response.authority_type,
+        ctx.accounts.delegate_record_info,
+    ) {
+        MetadataDelegateRecord::consume_use(delegate_record_info)?;
+    }
+
+    // Metadata accounts of the parent collections of the collection parent, if any.
+    let ancestors = [
+        ctx.accounts.parent_collection_info,
+        ctx.accounts.grandparent_collection_info,
+        ctx.accounts.great_grandparent_collection_info,
+    ];
+
     // Destructure the collection field from the item metadata.
     match metadata.collection.as_mut() {
         Some(collection) => {
             // Set item metadata collection to verified.
             collection.verified = true;
 
-            // In the case of a sized collection, update the size on the collection parent.
+            // In the case of a sized collection, update the size on the collection parent
+            // and on its parent collections.
             if collection_metadata.collection_details.is_some() {
                 increment_collection_size(&mut collection_metadata, collection_metadata_info)?;
+                propagate_collection_size_change(
+                    program_id,
+                    &collection_metadata,
+                    &ancestors,
+                    true,
+                )?;
+            } else if ancestors.iter().any(Option::is_some) {
+                return Err(MetadataError::UnsizedCollection.into());
             }
         }
         None => return Err(MetadataError::CollectionNotFound.into()),
@@ -178,9 +207,20 @@ pub(crate) fn unverify_collection_v1(program_id: &Pubkey, ctx: Context<Unverify>
         // Now we can deserialize the collection metadata account.
         let mut collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
 
-        // In the case of a sized collection, update the size on the collection parent.
+        // Metadata accounts of the parent collections of the collection parent, if any.
+        let ancestors = [
+            ctx.accounts.parent_collection_info,
+            ctx.accounts.grandparent_collection_info,
+            ctx.accounts.great_grandparent_collection_info,
+        ];
+
+        // In the case of a sized collection, update the size on the collection parent
+        // and on its parent collections.
         if collection_metadata.collection_details.is_some() {
             decrement_collection_size(&mut collection_metadata, collection_metadata_info)?;
+            propagate_collection_size_change(program_id, &collection_metadata, &ancestors, false)?;
+        } else if ancestors.iter().any(Option::is_some) {
+            return Err(MetadataError::UnsizedCollection.into());
         }
 
         // If the collection parent is not burned, the required authority is either the collection
@@ -202,6 +242,14 @@ pub(crate) fn unverify_collection_v1(program_id: &Pubkey, ctx: Context<Unverify>
         _ => return Err(MetadataError::UpdateAuthorityIncorrect.into()),
     }
 
+    // limited delegates consume one use per instruction
+    if let (AuthorityType::MetadataDelegate, Some(delegate_record_info)) = (
+        &authority_response.authority_type,
+        ctx.accounts.delegate_record_info,
+    ) {
+        MetadataDelegateRecord::consume_use(delegate_record_info)?;
+    }
+
     // Set item metadata collection to unverified.
     collection.verified = false;
 
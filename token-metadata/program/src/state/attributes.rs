@@ -0,0 +1,324 @@
+use std::collections::HashSet;
+
+use super::*;
+
+pub const ATTRIBUTES_SEED: &str = "attributes";
+
+/// Maximum number of attributes that can be stored.
+pub const MAX_ATTRIBUTES: usize = 32;
+
+/// Maximum length (in bytes) of an attribute key.
+pub const MAX_ATTRIBUTE_KEY_LENGTH: usize = 32;
+
+/// Maximum length (in bytes) of an attribute value.
+pub const MAX_ATTRIBUTE_VALUE_LENGTH: usize = 64;
+
+/// Size of an empty attributes account.
+pub const ATTRIBUTES_BASE_SIZE: usize = 1 // key
++ 1  // bump
++ 32 // mint
++ 4  // attributes length
++ 4; // frozen keys length
+
+/// Key/value pair of an on-chain attribute.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Attribute {
+    /// Name of the attribute.
+    pub key: String,
+    /// Value of the attribute.
+    pub value: String,
+}
+
+/// The `Attributes` account stores a bounded list of key/value pairs for an asset that
+/// can be read and modified on-chain by the update authority or an `Attributes` delegate.
+///
+/// Keys listed in `frozen_keys` can never be modified or removed. The seeds for the
+/// attributes PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"attributes"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct Attributes {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Mint of the asset.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// List of attributes.
+    pub attributes: Vec<Attribute>,
+    /// Keys that cannot be changed.
+    pub frozen_keys: Vec<String>,
+}
+
+impl Default for Attributes {
+    fn default() -> Self {
+        Self {
+            key: Key::AttributesV1,
+            bump: 255,
+            mint: Pubkey::default(),
+            attributes: Vec::new(),
+            frozen_keys: Vec::new(),
+        }
+    }
+}
+
+impl TokenMetadataAccount for Attributes {
+    fn key() -> Key {
+        Key::AttributesV1
+    }
+
+    /// Returns the size of an empty attributes account; use `Attributes::data_len` to
+    /// determine the size required by a specific instance.
+    fn size() -> usize {
+        ATTRIBUTES_BASE_SIZE
+    }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+
+    fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        let attributes = Self::from_bytes(&account_info.try_borrow_data()?)?;
+
+        // Check that this is a `token-metadata` owned account.
+        assert_owned_by(account_info, &ID)?;
+
+        Ok(attributes)
+    }
+}
+
+impl Attributes {
+    /// Deserializes the attributes account. The account size varies with the number
+    /// of attributes, so only the minimum size is checked.
+    pub fn from_bytes(mut data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < ATTRIBUTES_BASE_SIZE || data[DISCRIMINATOR_INDEX] != Key::AttributesV1 as u8
+        {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let attributes: Attributes = BorshDeserialize::deserialize(&mut data)?;
+        Ok(attributes)
+    }
+
+    /// Returns the serialized length of the account.
+    pub fn data_len(&self) -> usize {
+        ATTRIBUTES_BASE_SIZE
+            + self
+                .attributes
+                .iter()
+                .map(|a| 8 + a.key.len() + a.value.len())
+                .sum::<usize>()
+            + self.frozen_keys.iter().map(|k| 4 + k.len()).sum::<usize>()
+    }
+
+    /// Returns the value of an attribute.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.value.as_str())
+    }
+
+    /// Indicates whether the attribute is frozen.
+    pub fn is_frozen(&self, key: &str) -> bool {
+        self.frozen_keys.iter().any(|k| k == key)
+    }
+
+    /// Sets the value of an attribute, adding it if it does not exist.
+    pub fn set(&mut self, attribute: Attribute) -> ProgramResult {
+        assert_attribute_valid(&attribute)?;
+
+        if self.is_frozen(&attribute.key) {
+            return Err(MetadataError::AttributeFrozen.into());
+        }
+
+        match self.attributes.iter_mut().find(|a| a.key == attribute.key) {
+            Some(existing) => existing.value = attribute.value,
+            None => {
+                if self.attributes.len() >= MAX_ATTRIBUTES {
+                    return Err(MetadataError::TooManyAttributes.into());
+                }
+                self.attributes.push(attribute);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes an attribute.
+    pub fn remove(&mut self, key: &str) -> ProgramResult {
+        if self.is_frozen(key) {
+            return Err(MetadataError::AttributeFrozen.into());
+        }
+
+        let index = self
+            .attributes
+            .iter()
+            .position(|a| a.key == key)
+            .ok_or(MetadataError::AttributeNotFound)?;
+        self.attributes.remove(index);
+
+        Ok(())
+    }
+
+    /// Freezes an attribute, preventing further changes. Once frozen, an attribute
+    /// cannot be unfrozen.
+    pub fn freeze(&mut self, key: &str) -> ProgramResult {
+        if self.get(key).is_none() {
+            return Err(MetadataError::AttributeNotFound.into());
+        }
+
+        if !self.is_frozen(key) {
+            self.frozen_keys.push(key.to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Saves the attributes to the specified account, resizing the account to
+    /// match the length of the data.
+    pub fn save<'a>(
+        &self,
+        account_info: &'a AccountInfo<'a>,
+        payer_info: &'a AccountInfo<'a>,
+        system_program_info: &'a AccountInfo<'a>,
+    ) -> ProgramResult {
+        let required_size = self.data_len();
+
+        if account_info.data_len() != required_size {
+            resize_or_reallocate_account_raw(
+                account_info,
+                payer_info,
+                system_program_info,
+                required_size,
+            )?;
+        }
+
+        let mut account_data = account_info.data.borrow_mut();
+        let mut storage = &mut account_data[..required_size];
+        BorshSerialize::serialize(self, &mut storage)?;
+
+        Ok(())
+    }
+}
+
+/// Checks that the key and value of an attribute are within the limits.
+pub fn assert_attribute_valid(attribute: &Attribute) -> ProgramResult {
+    if attribute.key.is_empty() || attribute.key.len() > MAX_ATTRIBUTE_KEY_LENGTH {
+        return Err(MetadataError::InvalidAttributeKey.into());
+    }
+
+    if attribute.value.len() > MAX_ATTRIBUTE_VALUE_LENGTH {
+        return Err(MetadataError::AttributeValueTooLong.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a list of attributes has no duplicated keys.
+pub fn assert_unique_attribute_keys(attributes: &[Attribute]) -> ProgramResult {
+    let mut keys = HashSet::with_capacity(attributes.len());
+
+    if attributes.iter().all(|a| keys.insert(a.key.as_str())) {
+        Ok(())
+    } else {
+        Err(MetadataError::DuplicateAttributeKey.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::{
+        error::MetadataError,
+        state::{Attribute, Attributes, MAX_ATTRIBUTES},
+    };
+
+    fn attribute(key: &str, value: &str) -> Attribute {
+        Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn serialized_length_matches() {
+        let mut attributes = Attributes {
+            mint: Keypair::new().pubkey(),
+            ..Attributes::default()
+        };
+        attributes.set(attribute("level", "12")).unwrap();
+        attributes.set(attribute("class", "wizard")).unwrap();
+        attributes.freeze("class").unwrap();
+
+        let mut buf = Vec::new();
+        attributes.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), attributes.data_len());
+
+        let deserialized = Attributes::from_bytes(&buf).unwrap();
+        assert_eq!(deserialized, attributes);
+    }
+
+    #[test]
+    fn set_and_remove_attributes() {
+        let mut attributes = Attributes::default();
+
+        attributes.set(attribute("level", "1")).unwrap();
+        attributes.set(attribute("level", "2")).unwrap();
+        assert_eq!(attributes.attributes.len(), 1);
+        assert_eq!(attributes.get("level"), Some("2"));
+
+        attributes.remove("level").unwrap();
+        assert_eq!(attributes.get("level"), None);
+
+        let error = attributes.remove("level").unwrap_err();
+        assert_eq!(error, MetadataError::AttributeNotFound.into());
+    }
+
+    #[test]
+    fn frozen_attributes_cannot_change() {
+        let mut attributes = Attributes::default();
+        attributes.set(attribute("class", "wizard")).unwrap();
+        attributes.freeze("class").unwrap();
+        // freezing twice is a no-op
+        attributes.freeze("class").unwrap();
+        assert_eq!(attributes.frozen_keys.len(), 1);
+
+        let error = attributes.set(attribute("class", "warrior")).unwrap_err();
+        assert_eq!(error, MetadataError::AttributeFrozen.into());
+
+        let error = attributes.remove("class").unwrap_err();
+        assert_eq!(error, MetadataError::AttributeFrozen.into());
+
+        assert_eq!(attributes.get("class"), Some("wizard"));
+    }
+
+    #[test]
+    fn fail_to_exceed_limits() {
+        let mut attributes = Attributes::default();
+
+        for i in 0..MAX_ATTRIBUTES {
+            attributes.set(attribute(&i.to_string(), "")).unwrap();
+        }
+
+        let error = attributes.set(attribute("extra", "")).unwrap_err();
+        assert_eq!(error, MetadataError::TooManyAttributes.into());
+
+        let error = attributes
+            .set(attribute(&"k".repeat(33), "value"))
+            .unwrap_err();
+        assert_eq!(error, MetadataError::InvalidAttributeKey.into());
+
+        let error = attributes.set(attribute("0", &"v".repeat(65))).unwrap_err();
+        assert_eq!(error, MetadataError::AttributeValueTooLong.into());
+    }
+}
@@ -0,0 +1,329 @@
+use super::*;
+
+use solana_program::borsh::try_from_slice_unchecked;
+
+/// Typed representation of a token metadata account.
+///
+/// This is intended to be used off-chain (e.g., by indexers) to decode the raw data of any
+/// account owned by the token metadata program without having to know its type in advance.
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum TokenMetadataAccountKind {
+    Metadata {
+        metadata: Metadata,
+        /// Indicates whether the fee flag is set (the account has fees available for collection).
+        fee_flag: bool,
+    },
+    MasterEditionV1(MasterEditionV1),
+    MasterEditionV2(MasterEditionV2),
+    Edition(Edition),
+    EditionMarker(EditionMarker),
+    ReservationListV1(ReservationListV1),
+    ReservationListV2(ReservationListV2),
+    UseAuthorityRecord(UseAuthorityRecord),
+    CollectionAuthorityRecord(CollectionAuthorityRecord),
+    TokenOwnedEscrow(TokenOwnedEscrow),
+    TokenRecord(TokenRecord),
+    MetadataDelegateRecord(MetadataDelegateRecord),
+    Attributes(Attributes),
+    MigrationAllowlist(MigrationAllowlist),
+    PendingUpdateAuthority(PendingUpdateAuthority),
+    MetadataHistory(MetadataHistory),
+    UseConfig(UseConfig),
+    EditionUriTemplate(EditionUriTemplate),
+    ReprintRecord(ReprintRecord),
+}
+
+impl TokenMetadataAccountKind {
+    /// Returns the account discriminator of the decoded account.
+    pub fn key(&self) -> Key {
+        match self {
+            Self::Metadata { metadata, .. } => metadata.key,
+            Self::MasterEditionV1(account) => account.key,
+            Self::MasterEditionV2(account) => account.key,
+            Self::Edition(account) => account.key,
+            Self::EditionMarker(account) => account.key,
+            Self::ReservationListV1(account) => account.key,
+            Self::ReservationListV2(account) => account.key,
+            Self::UseAuthorityRecord(account) => account.key,
+            Self::CollectionAuthorityRecord(account) => account.key,
+            Self::TokenOwnedEscrow(account) => account.key,
+            Self::TokenRecord(account) => account.key,
+            Self::MetadataDelegateRecord(account) => account.key,
+            Self::Attributes(account) => account.key,
+            Self::MigrationAllowlist(account) => account.key,
+            Self::PendingUpdateAuthority(account) => account.key,
+            Self::MetadataHistory(account) => account.key,
+            Self::UseConfig(account) => account.key,
+            Self::EditionUriTemplate(account) => account.key,
+            Self::ReprintRecord(account) => account.key,
+        }
+    }
+}
+
+/// Decodes the data of a token metadata account based on its `Key` discriminator.
+///
+/// Metadata accounts are deserialized using the same lenient logic as the program
+/// (`meta_deser_unchecked`), so legacy accounts with corrupted optional fields can
+/// still be decoded.
+pub fn decode_account(data: &[u8]) -> Result<TokenMetadataAccountKind, ProgramError> {
+    let key = data
+        .get(DISCRIMINATOR_INDEX)
+        .and_then(|k| Key::from_u8(*k))
+        .ok_or(MetadataError::DataTypeMismatch)?;
+
+    let account = match key {
+        Key::MetadataV1 => {
+            let metadata = meta_deser_unchecked(&mut &data[..])?;
+            // older accounts might not have the fee flag byte
+            let fee_flag = matches!(data.get(METADATA_FEE_FLAG_INDEX), Some(&FEE_FLAG_SET));
+
+            TokenMetadataAccountKind::Metadata { metadata, fee_flag }
+        }
+        Key::MasterEditionV1 => TokenMetadataAccountKind::MasterEditionV1(try_from_slice_checked(
+            data,
+            key,
+            MasterEditionV1::size(),
+        )?),
+        Key::MasterEditionV2 => TokenMetadataAccountKind::MasterEditionV2(try_from_slice_checked(
+            data,
+            key,
+            MasterEditionV2::size(),
+        )?),
+        Key::EditionV1 => {
+            TokenMetadataAccountKind::Edition(try_from_slice_checked(data, key, Edition::size())?)
+        }
+        Key::EditionMarker => TokenMetadataAccountKind::EditionMarker(try_from_slice_checked(
+            data,
+            key,
+            EditionMarker::size(),
+        )?),
+        // reservation lists are variable in size, so we do not check the account length
+        Key::ReservationListV1 => {
+            TokenMetadataAccountKind::ReservationListV1(try_from_slice_unchecked(data)?)
+        }
+        Key::ReservationListV2 => {
+            TokenMetadataAccountKind::ReservationListV2(try_from_slice_unchecked(data)?)
+        }
+        Key::UseAuthorityRecord => {
+            TokenMetadataAccountKind::UseAuthorityRecord(UseAuthorityRecord::from_bytes(data)?)
+        }
+        Key::CollectionAuthorityRecord => TokenMetadataAccountKind::CollectionAuthorityRecord(
+            CollectionAuthorityRecord::from_bytes(data)?,
+        ),
+        Key::TokenOwnedEscrow => {
+            TokenMetadataAccountKind::TokenOwnedEscrow(try_from_slice_unchecked(data)?)
+        }
+        Key::TokenRecord => TokenMetadataAccountKind::TokenRecord(TokenRecord::from_bytes(data)?),
+        Key::MetadataDelegate => TokenMetadataAccountKind::MetadataDelegateRecord(
+            MetadataDelegateRecord::from_bytes(data)?,
+        ),
+        Key::AttributesV1 => TokenMetadataAccountKind::Attributes(Attributes::from_bytes(data)?),
+        Key::MigrationAllowlist => {
+            TokenMetadataAccountKind::MigrationAllowlist(MigrationAllowlist::from_bytes(data)?)
+        }
+        Key::PendingUpdateAuthority => TokenMetadataAccountKind::PendingUpdateAuthority(
+            PendingUpdateAuthority::from_bytes(data)?,
+        ),
+        Key::MetadataHistory => {
+            TokenMetadataAccountKind::MetadataHistory(MetadataHistory::from_bytes(data)?)
+        }
+        Key::UseConfig => TokenMetadataAccountKind::UseConfig(UseConfig::from_bytes(data)?),
+        Key::EditionUriTemplate => {
+            TokenMetadataAccountKind::EditionUriTemplate(EditionUriTemplate::from_bytes(data)?)
+        }
+        Key::ReprintRecord => {
+            TokenMetadataAccountKind::ReprintRecord(ReprintRecord::from_bytes(data)?)
+        }
+        Key::Uninitialized => return Err(MetadataError::DataTypeMismatch.into()),
+    };
+
+    Ok(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::{
+        state::{
+            decode_account, AuthorityType, EscrowAuthority, Key, MasterEditionV2, Metadata,
+            TokenDelegateRole, TokenMetadataAccount, TokenMetadataAccountKind, TokenOwnedEscrow,
+            TokenRecord, TokenState, FEE_FLAG_SET, LOCKED_BY_SIZE, METADATA_FEE_FLAG_INDEX,
+        },
+        utils::metadata::tests::{expected_pesky_metadata, pesky_data},
+    };
+
+    #[test]
+    fn decode_metadata_with_fee_flag() {
+        let expected_metadata = Metadata {
+            update_authority: Keypair::new().pubkey(),
+            mint: Keypair::new().pubkey(),
+            ..Metadata::default()
+        };
+
+        let mut buf = Vec::new();
+        expected_metadata.serialize(&mut buf).unwrap();
+        Metadata::pad_length(&mut buf).unwrap();
+        buf[METADATA_FEE_FLAG_INDEX] = FEE_FLAG_SET;
+
+        let account = decode_account(&buf).unwrap();
+
+        assert_eq!(account.key(), Key::MetadataV1);
+        assert_eq!(
+            account,
+            TokenMetadataAccountKind::Metadata {
+                metadata: expected_metadata,
+                fee_flag: true,
+            }
+        );
+    }
+
+    #[test]
+    fn decode_corrupted_metadata() {
+        let account = decode_account(pesky_data()).unwrap();
+
+        assert_eq!(
+            account,
+            TokenMetadataAccountKind::Metadata {
+                metadata: expected_pesky_metadata(),
+                fee_flag: false,
+            }
+        );
+    }
+
+    #[test]
+    fn decode_master_edition() {
+        let expected_data = MasterEditionV2::default();
+
+        let mut buf = Vec::new();
+        expected_data.serialize(&mut buf).unwrap();
+        MasterEditionV2::pad_length(&mut buf).unwrap();
+
+        let account = decode_account(&buf).unwrap();
+
+        assert_eq!(
+            account,
+            TokenMetadataAccountKind::MasterEditionV2(expected_data)
+        );
+    }
+
+    #[test]
+    fn decode_token_record() {
+        let expected_data = TokenRecord {
+            state: TokenState::Locked,
+            delegate: Some(Keypair::new().pubkey()),
+            ..TokenRecord::default()
+        };
+
+        let mut buf = Vec::new();
+        expected_data.serialize(&mut buf).unwrap();
+        TokenRecord::pad_length(&mut buf).unwrap();
+
+        let account = decode_account(&buf).unwrap();
+
+        assert_eq!(
+            account,
+            TokenMetadataAccountKind::TokenRecord(expected_data)
+        );
+    }
+
+    #[test]
+    fn decode_token_record_locked_by() {
+        let expected_data = TokenRecord {
+            state: TokenState::Locked,
+            delegate: Some(Keypair::new().pubkey()),
+            delegate_role: Some(TokenDelegateRole::Utility),
+            locked_by: Some(AuthorityType::MetadataDelegate),
+            ..TokenRecord::default()
+        };
+
+        let mut buf = Vec::new();
+        expected_data.serialize(&mut buf).unwrap();
+        TokenRecord::pad_length(&mut buf).unwrap();
+
+        let account = decode_account(&buf).unwrap();
+
+        assert_eq!(
+            account,
+            TokenMetadataAccountKind::TokenRecord(expected_data.clone())
+        );
+
+        // token records locked before the authority type was stored
+        buf.truncate(buf.len() - LOCKED_BY_SIZE);
+
+        let account = decode_account(&buf).unwrap();
+
+        assert_eq!(
+            account,
+            TokenMetadataAccountKind::TokenRecord(TokenRecord {
+                locked_by: None,
+                ..expected_data
+            })
+        );
+    }
+
+    #[test]
+    fn decode_token_record_delegate_roles() {
+        for role in [
+            TokenDelegateRole::Sale,
+            TokenDelegateRole::Transfer,
+            TokenDelegateRole::Utility,
+            TokenDelegateRole::Staking,
+            TokenDelegateRole::Standard,
+            TokenDelegateRole::LockedTransfer,
+            TokenDelegateRole::Migration,
+            TokenDelegateRole::Rental,
+        ] {
+            let expected_data = TokenRecord {
+                delegate: Some(Keypair::new().pubkey()),
+                delegate_role: Some(role),
+                rental_expiry: (role == TokenDelegateRole::Rental).then_some(1_000),
+                lender: (role == TokenDelegateRole::Rental).then(|| Keypair::new().pubkey()),
+                ..TokenRecord::default()
+            };
+
+            let mut buf = Vec::new();
+            expected_data.serialize(&mut buf).unwrap();
+            TokenRecord::pad_length(&mut buf).unwrap();
+
+            let account = decode_account(&buf).unwrap();
+
+            assert_eq!(
+                account,
+                TokenMetadataAccountKind::TokenRecord(expected_data)
+            );
+        }
+
+        // roles are serialized by their position
+        assert_eq!(TokenDelegateRole::Migration.try_to_vec().unwrap(), vec![6]);
+        assert_eq!(TokenDelegateRole::Rental.try_to_vec().unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn decode_token_owned_escrow() {
+        let expected_data = TokenOwnedEscrow {
+            key: Key::TokenOwnedEscrow,
+            base_token: Keypair::new().pubkey(),
+            authority: EscrowAuthority::Creator(Keypair::new().pubkey()),
+            bump: 254,
+        };
+
+        let mut buf = Vec::new();
+        expected_data.serialize(&mut buf).unwrap();
+
+        let account = decode_account(&buf).unwrap();
+
+        assert_eq!(
+            account,
+            TokenMetadataAccountKind::TokenOwnedEscrow(expected_data)
+        );
+    }
+
+    #[test]
+    fn fail_to_decode_uninitialized_account() {
+        assert!(decode_account(&[]).is_err());
+        assert!(decode_account(&[0; 32]).is_err());
+    }
+}
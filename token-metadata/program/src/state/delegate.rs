@@ -1,6 +1,13 @@
+use solana_program::{clock::Clock, sysvar::Sysvar};
+
 use super::*;
 
-const SIZE: usize = 98;
+const SIZE: usize = 116;
+
+/// Size of the optional limits (expiry and uses) of a delegate record. Records created
+/// before limits were introduced do not include these bytes.
+pub const DELEGATE_LIMITS_SIZE: usize = 9 // expires at
++ 9; // remaining uses
 
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
@@ -22,6 +29,10 @@ pub struct MetadataDelegateRecord {
     pub delegate: Pubkey, // 32
     #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
     pub update_authority: Pubkey, // 32
+    /// Unix timestamp after which the delegate can no longer be used.
+    pub expires_at: Option<i64>, // 9
+    /// Number of times the delegate can still be used.
+    pub remaining_uses: Option<u64>, // 9
 }
 
 impl Default for MetadataDelegateRecord {
@@ -32,6 +43,8 @@ impl Default for MetadataDelegateRecord {
             mint: Pubkey::default(),
             delegate: Pubkey::default(),
             update_authority: Pubkey::default(),
+            expires_at: None,
+            remaining_uses: None,
         }
     }
 }
@@ -44,12 +57,171 @@ impl TokenMetadataAccount for MetadataDelegateRecord {
     fn size() -> usize {
         SIZE
     }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+
+    fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        let delegate_record = Self::from_bytes(&account_info.try_borrow_data()?)?;
+
+        // Check that this is a `token-metadata` owned account.
+        assert_owned_by(account_info, &ID)?;
+
+        Ok(delegate_record)
+    }
+}
+
+impl Resizable for MetadataDelegateRecord {
+    fn from_bytes(account_data: &[u8]) -> Result<MetadataDelegateRecord, ProgramError> {
+        // we perform a manual deserialization since we are potentially dealing
+        // with accounts of different sizes
+        let length = MetadataDelegateRecord::size() as i64 - account_data.len() as i64;
+
+        if !(length == 0 || length == DELEGATE_LIMITS_SIZE as i64)
+            || !MetadataDelegateRecord::is_correct_account_type(
+                account_data,
+                Key::MetadataDelegate,
+                account_data.len(),
+            )
+        {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+        // mutable "pointer" to the account data
+        let mut data = account_data;
+
+        let key: Key = BorshDeserialize::deserialize(&mut data)?;
+        let bump: u8 = BorshDeserialize::deserialize(&mut data)?;
+        let mint: Pubkey = BorshDeserialize::deserialize(&mut data)?;
+        let delegate: Pubkey = BorshDeserialize::deserialize(&mut data)?;
+        let update_authority: Pubkey = BorshDeserialize::deserialize(&mut data)?;
+
+        let (expires_at, remaining_uses) = if length == 0 {
+            (
+                BorshDeserialize::deserialize(&mut data)?,
+                BorshDeserialize::deserialize(&mut data)?,
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(MetadataDelegateRecord {
+            key,
+            bump,
+            mint,
+            delegate,
+            update_authority,
+            expires_at,
+            remaining_uses,
+        })
+    }
 }
 
 impl MetadataDelegateRecord {
-    pub fn from_bytes(data: &[u8]) -> Result<MetadataDelegateRecord, ProgramError> {
-        let delegate: MetadataDelegateRecord =
-            try_from_slice_checked(data, Key::MetadataDelegate, MetadataDelegateRecord::size())?;
-        Ok(delegate)
+    /// Indicates whether the delegate has expired at the given timestamp.
+    pub fn is_expired(&self, now: i64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if now >= expires_at)
+    }
+
+    /// Indicates whether the delegate has no uses left.
+    pub fn is_exhausted(&self) -> bool {
+        matches!(self.remaining_uses, Some(0))
+    }
+
+    /// Checks that the delegate has not expired nor run out of uses.
+    pub fn assert_active(&self) -> ProgramResult {
+        if self.is_exhausted() {
+            return Err(MetadataError::DelegateUsesExhausted.into());
+        }
+
+        if self.expires_at.is_some() && self.is_expired(Clock::get()?.unix_timestamp) {
+            return Err(MetadataError::DelegateExpired.into());
+        }
+
+        Ok(())
+    }
+
+    /// Consumes one use of the delegate record. This is a no-op for delegates without
+    /// a limited number of uses.
+    pub fn consume_use(delegate_record_info: &AccountInfo) -> ProgramResult {
+        let mut delegate_record = MetadataDelegateRecord::from_account_info(delegate_record_info)?;
+
+        if let Some(remaining_uses) = delegate_record.remaining_uses {
+            delegate_record.remaining_uses = Some(
+                remaining_uses
+                    .checked_sub(1)
+                    .ok_or(MetadataError::DelegateUsesExhausted)?,
+            );
+            // records with limits always have the current size, so there is no need
+            // to resize the account
+            BorshSerialize::serialize(
+                &delegate_record,
+                &mut *delegate_record_info.try_borrow_mut_data()?,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::state::{
+        MetadataDelegateRecord, Resizable, TokenMetadataAccount, DELEGATE_LIMITS_SIZE,
+    };
+
+    #[test]
+    fn deserialize_legacy_delegate_record() {
+        let delegate_record = MetadataDelegateRecord {
+            mint: Keypair::new().pubkey(),
+            delegate: Keypair::new().pubkey(),
+            update_authority: Keypair::new().pubkey(),
+            ..MetadataDelegateRecord::default()
+        };
+
+        let mut buf = Vec::new();
+        delegate_record.serialize(&mut buf).unwrap();
+        // legacy records do not have the limits bytes
+        buf.truncate(MetadataDelegateRecord::size() - DELEGATE_LIMITS_SIZE);
+
+        let deserialized = MetadataDelegateRecord::from_bytes(&buf).unwrap();
+        assert_eq!(deserialized, delegate_record);
+    }
+
+    #[test]
+    fn deserialize_delegate_record_with_limits() {
+        let delegate_record = MetadataDelegateRecord {
+            mint: Keypair::new().pubkey(),
+            delegate: Keypair::new().pubkey(),
+            update_authority: Keypair::new().pubkey(),
+            expires_at: Some(1_700_000_000),
+            remaining_uses: Some(0),
+            ..MetadataDelegateRecord::default()
+        };
+
+        let mut buf = Vec::new();
+        delegate_record.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), MetadataDelegateRecord::size());
+
+        let deserialized = MetadataDelegateRecord::from_bytes(&buf).unwrap();
+        assert_eq!(deserialized, delegate_record);
+
+        assert!(deserialized.is_exhausted());
+        assert!(deserialized.is_expired(1_700_000_000));
+        assert!(!deserialized.is_expired(1_699_999_999));
+    }
+
+    #[test]
+    fn fail_to_deserialize_invalid_size() {
+        let mut buf = Vec::new();
+        MetadataDelegateRecord::default()
+            .serialize(&mut buf)
+            .unwrap();
+        buf.truncate(MetadataDelegateRecord::size() - 1);
+
+        assert!(MetadataDelegateRecord::from_bytes(&buf).is_err());
     }
 }
@@ -2,6 +2,10 @@ use super::*;
 
 pub const MAX_EDITION_LEN: usize = 1 + 32 + 8 + 200;
 
+// The last byte of the account contains the fee flag, indicating
+// if the account has fees available for retrieval.
+pub const EDITION_FEE_FLAG_INDEX: usize = MAX_EDITION_LEN - 1;
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, ShankAccount)]
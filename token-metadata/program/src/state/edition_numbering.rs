@@ -0,0 +1,336 @@
+use super::*;
+
+/// Maximum number of reserved ranges of a numbering policy.
+pub const MAX_RESERVED_RANGES: usize = 8;
+
+// The numbering policy is stored on the master edition account after the data of
+// master edition v1 accounts, since it is left behind when they are converted to v2.
+pub const EDITION_NUMBERING_INDEX: usize = 1 + 8 + 9 + 32 + 32;
+
+pub const EDITION_NUMBERING_SIZE: usize = 1 // option
++ 1  // mode
++ 8  // next edition
++ 4  // reserved ranges length
++ MAX_RESERVED_RANGES * 16;
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum EditionNumberingMode {
+    /// Edition numbers are chosen by the caller.
+    Custom,
+    /// Edition numbers must follow the sequence of the master edition.
+    Sequential,
+    /// The first number available from the requested one is used, skipping numbers
+    /// already minted on the edition marker.
+    NextAvailable,
+}
+
+/// Range of edition numbers (inclusive) that can only be minted when the update
+/// authority of the master edition signs the transaction.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct ReservedRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ReservedRange {
+    pub fn contains(&self, edition: u64) -> bool {
+        self.start <= edition && edition <= self.end
+    }
+}
+
+/// Numbering policy of the print editions of a master edition. Reserved numbers are skipped
+/// by the sequential and next available modes.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct EditionNumbering {
+    /// How edition numbers are assigned.
+    pub mode: EditionNumberingMode,
+    /// Next edition number of the sequence.
+    pub next_edition: u64,
+    /// Reserved edition numbers.
+    pub reserved_ranges: Vec<ReservedRange>,
+}
+
+impl EditionNumbering {
+    /// Returns the numbering policy of the master edition, if any.
+    pub fn from_master_edition(account_info: &AccountInfo) -> Result<Option<Self>, ProgramError> {
+        let data = account_info.try_borrow_data()?;
+
+        if data.len() < EDITION_NUMBERING_INDEX + EDITION_NUMBERING_SIZE {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let mut storage = &data[EDITION_NUMBERING_INDEX..];
+        let numbering: Option<Self> = BorshDeserialize::deserialize(&mut storage)?;
+
+        Ok(numbering)
+    }
+
+    pub fn save(&self, account_info: &AccountInfo) -> ProgramResult {
+        let mut data = account_info.try_borrow_mut_data()?;
+        let storage =
+            &mut data[EDITION_NUMBERING_INDEX..EDITION_NUMBERING_INDEX + EDITION_NUMBERING_SIZE];
+        // clears the previous policy, which might have more ranges
+        storage.fill(0);
+        BorshSerialize::serialize(&Some(self), &mut &mut storage[..])?;
+        Ok(())
+    }
+
+    /// Checks that the reserved ranges are valid, do not overlap and are within the
+    /// max supply of the master edition.
+    pub fn validate(&self, max_supply: Option<u64>) -> ProgramResult {
+        if self.reserved_ranges.len() > MAX_RESERVED_RANGES {
+            return Err(MetadataError::InvalidEditionNumbering.into());
+        }
+
+        for (i, range) in self.reserved_ranges.iter().enumerate() {
+            if range.start == 0 || range.start > range.end {
+                return Err(MetadataError::InvalidEditionNumbering.into());
+            }
+
+            if matches!(max_supply, Some(max_supply) if range.end > max_supply) {
+                return Err(MetadataError::InvalidEditionNumbering.into());
+            }
+
+            if self.reserved_ranges[i + 1..]
+                .iter()
+                .any(|other| other.start <= range.end && range.start <= other.end)
+            {
+                return Err(MetadataError::InvalidEditionNumbering.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_reserved(&self, edition: u64) -> bool {
+        self.reserved_ranges
+            .iter()
+            .any(|range| range.contains(edition))
+    }
+
+    /// Returns the first edition number from `edition` that is not reserved.
+    pub fn next_unreserved(&self, mut edition: u64) -> Result<u64, ProgramError> {
+        while let Some(range) = self
+            .reserved_ranges
+            .iter()
+            .find(|range| range.contains(edition))
+        {
+            edition = range
+                .end
+                .checked_add(1)
+                .ok_or(MetadataError::NumericalOverflowError)?;
+        }
+
+        Ok(edition)
+    }
+
+    /// Returns the edition number to mint for the requested `edition`. The `edition_marker`
+    /// must be the marker of the requested edition, since the next available search does
+    /// not go beyond it.
+    pub fn resolve(
+        &self,
+        edition: u64,
+        edition_marker: &EditionMarker,
+        max_supply: Option<u64>,
+        update_authority_signed: bool,
+    ) -> Result<u64, ProgramError> {
+        if self.is_reserved(edition) {
+            return if update_authority_signed {
+                Ok(edition)
+            } else {
+                Err(MetadataError::EditionNumberReserved.into())
+            };
+        }
+
+        match self.mode {
+            EditionNumberingMode::Custom => Ok(edition),
+            EditionNumberingMode::Sequential => {
+                if edition != self.next_unreserved(self.next_edition)? {
+                    return Err(MetadataError::EditionNumberOutOfSequence.into());
+                }
+
+                Ok(edition)
+            }
+            EditionNumberingMode::NextAvailable => {
+                let marker = edition / EDITION_MARKER_BIT_SIZE;
+                let mut candidate = self.next_unreserved(edition.max(1))?;
+
+                while candidate / EDITION_MARKER_BIT_SIZE == marker
+                    && !matches!(max_supply, Some(max_supply) if candidate > max_supply)
+                {
+                    if !edition_marker.edition_taken(candidate)? {
+                        return Ok(candidate);
+                    }
+
+                    candidate = self.next_unreserved(
+                        candidate
+                            .checked_add(1)
+                            .ok_or(MetadataError::NumericalOverflowError)?,
+                    )?;
+                }
+
+                Err(MetadataError::EditionNumberUnavailable.into())
+            }
+        }
+    }
+
+    /// Moves the sequence past the minted `edition`.
+    pub fn advance(&mut self, edition: u64) -> ProgramResult {
+        if self.mode == EditionNumberingMode::Sequential && !self.is_reserved(edition) {
+            self.next_edition = edition
+                .checked_add(1)
+                .ok_or(MetadataError::NumericalOverflowError)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_program::account_info::AccountInfo;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::{
+        error::MetadataError,
+        state::{
+            EditionMarker, EditionNumbering, EditionNumberingMode, MasterEditionV2, ReservedRange,
+            TokenMetadataAccount, EDITION_NUMBERING_SIZE, MASTER_EDITION_FEE_FLAG_INDEX,
+            MAX_RESERVED_RANGES,
+        },
+        ID,
+    };
+
+    fn numbering(mode: EditionNumberingMode) -> EditionNumbering {
+        EditionNumbering {
+            mode,
+            next_edition: 1,
+            reserved_ranges: vec![
+                ReservedRange { start: 1, end: 50 },
+                ReservedRange { start: 52, end: 52 },
+            ],
+        }
+    }
+
+    #[test]
+    fn max_ranges_fit() {
+        let numbering = EditionNumbering {
+            reserved_ranges: vec![ReservedRange { start: 1, end: 1 }; MAX_RESERVED_RANGES],
+            ..numbering(EditionNumberingMode::Custom)
+        };
+
+        let mut buf = Vec::new();
+        Some(&numbering).serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), EDITION_NUMBERING_SIZE);
+
+        // the policy is saved without touching the flags at the end of the account
+        let mut data = MasterEditionV2::default().try_to_vec().unwrap();
+        MasterEditionV2::pad_length(&mut data).unwrap();
+        data[MASTER_EDITION_FEE_FLAG_INDEX] = 1;
+
+        let pubkey = Keypair::new().pubkey();
+        let mut lamports = 1_000_000_000;
+        let account_info = AccountInfo::new(
+            &pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &ID,
+            false,
+            1_000_000_000,
+        );
+
+        assert_eq!(
+            EditionNumbering::from_master_edition(&account_info).unwrap(),
+            None
+        );
+        numbering.save(&account_info).unwrap();
+        assert_eq!(
+            EditionNumbering::from_master_edition(&account_info).unwrap(),
+            Some(numbering)
+        );
+        assert_eq!(account_info.data.borrow()[MASTER_EDITION_FEE_FLAG_INDEX], 1);
+    }
+
+    #[test]
+    fn validate_ranges() {
+        let mut numbering = numbering(EditionNumberingMode::Custom);
+        numbering.validate(Some(100)).unwrap();
+
+        // exceeds max supply
+        let error = numbering.validate(Some(51)).unwrap_err();
+        assert_eq!(error, MetadataError::InvalidEditionNumbering.into());
+
+        // overlapping ranges
+        numbering
+            .reserved_ranges
+            .push(ReservedRange { start: 40, end: 60 });
+        let error = numbering.validate(None).unwrap_err();
+        assert_eq!(error, MetadataError::InvalidEditionNumbering.into());
+
+        // edition 0 is not valid
+        numbering.reserved_ranges = vec![ReservedRange { start: 0, end: 1 }];
+        let error = numbering.validate(None).unwrap_err();
+        assert_eq!(error, MetadataError::InvalidEditionNumbering.into());
+    }
+
+    #[test]
+    fn reserved_editions_require_update_authority() {
+        let numbering = numbering(EditionNumberingMode::Custom);
+        let marker = EditionMarker::default();
+
+        let error = numbering.resolve(10, &marker, None, false).unwrap_err();
+        assert_eq!(error, MetadataError::EditionNumberReserved.into());
+
+        assert_eq!(numbering.resolve(10, &marker, None, true).unwrap(), 10);
+        assert_eq!(numbering.resolve(60, &marker, None, false).unwrap(), 60);
+    }
+
+    #[test]
+    fn sequential_skips_reserved_ranges() {
+        let mut numbering = numbering(EditionNumberingMode::Sequential);
+        let marker = EditionMarker::default();
+
+        let error = numbering.resolve(60, &marker, None, false).unwrap_err();
+        assert_eq!(error, MetadataError::EditionNumberOutOfSequence.into());
+
+        assert_eq!(numbering.resolve(51, &marker, None, false).unwrap(), 51);
+        numbering.advance(51).unwrap();
+
+        // 52 is reserved
+        assert_eq!(numbering.resolve(53, &marker, None, false).unwrap(), 53);
+
+        // minting reserved editions does not move the sequence
+        numbering.advance(52).unwrap();
+        assert_eq!(numbering.next_edition, 52);
+    }
+
+    #[test]
+    fn next_available_skips_minted_editions() {
+        let numbering = numbering(EditionNumberingMode::NextAvailable);
+        let mut marker = EditionMarker::default();
+        marker.insert_edition(51).unwrap();
+        marker.insert_edition(53).unwrap();
+
+        assert_eq!(numbering.resolve(0, &marker, None, false).unwrap(), 54);
+        assert_eq!(numbering.resolve(100, &marker, None, false).unwrap(), 100);
+
+        // beyond the max supply
+        let error = numbering.resolve(0, &marker, Some(53), false).unwrap_err();
+        assert_eq!(error, MetadataError::EditionNumberUnavailable.into());
+
+        // the search does not go beyond the edition marker
+        let mut marker = EditionMarker::default();
+        marker.insert_edition(247).unwrap();
+        let error = numbering.resolve(247, &marker, None, false).unwrap_err();
+        assert_eq!(error, MetadataError::EditionNumberUnavailable.into());
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+pub const EDITION_URI_TEMPLATE_SEED: &str = "edition_uri_template";
+
+/// Placeholder replaced by the edition number when a print edition is minted.
+pub const EDITION_NUMBER_PLACEHOLDER: &str = "{edition}";
+
+pub const EDITION_URI_TEMPLATE_SIZE: usize = 1 // key
++ 1  // bump
++ 32 // mint
++ 4  // uri template length
++ MAX_URI_LENGTH;
+
+/// The `EditionUriTemplate` account holds the URI of the print editions of a master edition,
+/// e.g., `https://example.com/{edition}.json`. The `{edition}` placeholder is replaced by the
+/// edition number when the print edition is minted. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. master edition mint id
+/// 4. `"edition_uri_template"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct EditionUriTemplate {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Mint of the master edition.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// URI template of the print editions.
+    pub uri_template: String,
+}
+
+impl Default for EditionUriTemplate {
+    fn default() -> Self {
+        Self {
+            key: Key::EditionUriTemplate,
+            bump: 255,
+            mint: Pubkey::default(),
+            uri_template: String::default(),
+        }
+    }
+}
+
+impl TokenMetadataAccount for EditionUriTemplate {
+    fn key() -> Key {
+        Key::EditionUriTemplate
+    }
+
+    fn size() -> usize {
+        EDITION_URI_TEMPLATE_SIZE
+    }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+
+    fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        let template = Self::from_bytes(&account_info.try_borrow_data()?)?;
+
+        // Check that this is a `token-metadata` owned account.
+        assert_owned_by(account_info, &ID)?;
+
+        Ok(template)
+    }
+}
+
+impl EditionUriTemplate {
+    pub fn from_bytes(mut data: &[u8]) -> Result<Self, ProgramError> {
+        if !Self::is_correct_account_type(data, Key::EditionUriTemplate, EDITION_URI_TEMPLATE_SIZE)
+        {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let template: EditionUriTemplate = BorshDeserialize::deserialize(&mut data)?;
+        Ok(template)
+    }
+
+    /// Checks that the URI template fits in the account and has the edition placeholder.
+    pub fn validate(uri_template: &str) -> ProgramResult {
+        if uri_template.len() > MAX_URI_LENGTH {
+            return Err(MetadataError::UriTooLong.into());
+        }
+
+        if !uri_template.contains(EDITION_NUMBER_PLACEHOLDER) {
+            return Err(MetadataError::InvalidEditionUriTemplate.into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the URI of the print edition with the given edition number.
+    pub fn resolve(&self, edition: u64) -> Result<String, ProgramError> {
+        let uri = self
+            .uri_template
+            .replace(EDITION_NUMBER_PLACEHOLDER, &edition.to_string());
+
+        if uri.len() > MAX_URI_LENGTH {
+            return Err(MetadataError::UriTooLong.into());
+        }
+
+        Ok(uri)
+    }
+
+    pub fn save(&self, account_info: &AccountInfo) -> ProgramResult {
+        let mut data = account_info.try_borrow_mut_data()?;
+        // clears the previous template, which might be longer
+        data.fill(0);
+        BorshSerialize::serialize(self, &mut *data)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::{
+        error::MetadataError,
+        state::{EditionUriTemplate, TokenMetadataAccount, MAX_URI_LENGTH},
+    };
+
+    #[test]
+    fn max_uri_template_fits() {
+        let template = EditionUriTemplate {
+            mint: Keypair::new().pubkey(),
+            uri_template: "x".repeat(MAX_URI_LENGTH),
+            ..EditionUriTemplate::default()
+        };
+
+        let mut buf = Vec::new();
+        template.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), EditionUriTemplate::size());
+    }
+
+    #[test]
+    fn resolves_edition_number() {
+        let template = EditionUriTemplate {
+            uri_template: "https://example.com/{edition}.json".to_string(),
+            ..EditionUriTemplate::default()
+        };
+
+        assert_eq!(
+            template.resolve(42).unwrap(),
+            "https://example.com/42.json".to_string()
+        );
+
+        let error = EditionUriTemplate::validate("https://example.com/1.json").unwrap_err();
+        assert_eq!(error, MetadataError::InvalidEditionUriTemplate.into());
+    }
+}
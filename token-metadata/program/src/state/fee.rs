@@ -1,3 +1,5 @@
+use solana_program::rent::Rent;
+
 use super::*;
 
 pub(crate) const FEE_AUTHORITY: Pubkey = pubkey!("Levytx9LLPzAtDJJD7q813Zsm8zg9e1pb53mGxTKpD7");
@@ -7,3 +9,154 @@ pub const CREATE_FEE: u64 = 10_000_000;
 
 pub const FEE_FLAG_SET: u8 = 1;
 pub const FEE_FLAG_CLEARED: u8 = 0;
+
+/// Returns the index of the fee flag on accounts of the given type, or `None` if accounts
+/// of the type do not hold fees.
+pub fn fee_flag_index(key: Key) -> Option<usize> {
+    match key {
+        Key::MetadataV1 => Some(METADATA_FEE_FLAG_INDEX),
+        Key::EditionV1 => Some(EDITION_FEE_FLAG_INDEX),
+        Key::MasterEditionV1 | Key::MasterEditionV2 => Some(MASTER_EDITION_FEE_FLAG_INDEX),
+        _ => None,
+    }
+}
+
+/// Returns the size used to compute the rent of accounts of the given type, or `None` if
+/// accounts of the type do not hold fees.
+pub fn fee_account_size(key: Key) -> Option<usize> {
+    match key {
+        Key::MetadataV1 => Some(MAX_METADATA_LEN),
+        Key::EditionV1 => Some(MAX_EDITION_LEN),
+        Key::MasterEditionV1 | Key::MasterEditionV2 => Some(MAX_MASTER_EDITION_LEN),
+        _ => None,
+    }
+}
+
+/// Splits the balance of a fee account into the collectible fee and the rent that must remain
+/// on the account. Burned accounts (`Uninitialized`) have no rent to keep.
+pub fn split_fee_balance(key: Key, lamports: u64, rent: &Rent) -> Result<(u64, u64), ProgramError> {
+    if key == Key::Uninitialized {
+        return Ok((lamports, 0));
+    }
+
+    let rent_amount =
+        rent.minimum_balance(fee_account_size(key).ok_or(MetadataError::InvalidFeeAccount)?);
+    let fee_amount = lamports
+        .checked_sub(rent_amount)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+
+    Ok((fee_amount, rent_amount))
+}
+
+/// Account with fees available for collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeAccount {
+    pub address: Pubkey,
+    /// Type of the account.
+    pub key: Key,
+    /// Lamports that can be collected from the account.
+    pub collectible: u64,
+}
+
+/// Finds the accounts with the fee flag set among the given `(address, data, lamports)`
+/// entries (e.g., the program accounts returned by an RPC node) and computes the lamports
+/// that can be collected from each of them.
+///
+/// This is intended to be used off-chain to build `Collect` instructions; the result can be
+/// filtered by `key` to collect the fees of specific account types only.
+pub fn find_fee_accounts<'a>(
+    accounts: impl IntoIterator<Item = (Pubkey, &'a [u8], u64)>,
+    rent: &Rent,
+) -> Vec<FeeAccount> {
+    accounts
+        .into_iter()
+        .filter_map(|(address, data, lamports)| {
+            let key = data.first().and_then(|byte| Key::from_u8(*byte))?;
+            let index = fee_flag_index(key)?;
+
+            if data.get(index) != Some(&FEE_FLAG_SET) {
+                return None;
+            }
+
+            let (collectible, _) = split_fee_balance(key, lamports, rent).ok()?;
+
+            Some(FeeAccount {
+                address,
+                key,
+                collectible,
+            })
+        })
+        .collect()
+}
+
+/// Returns the total lamports that can be collected from the given fee accounts.
+pub fn total_collectible_fees(fee_accounts: &[FeeAccount]) -> u64 {
+    fee_accounts.iter().fold(0u64, |total, account| {
+        total.saturating_add(account.collectible)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use super::*;
+
+    #[test]
+    fn finds_flagged_accounts_with_collectible_fees() {
+        let rent = Rent::default();
+
+        let mut metadata = vec![0u8; MAX_METADATA_LEN];
+        metadata[0] = Key::MetadataV1 as u8;
+        metadata[METADATA_FEE_FLAG_INDEX] = FEE_FLAG_SET;
+
+        let mut edition = vec![0u8; MAX_EDITION_LEN];
+        edition[0] = Key::EditionV1 as u8;
+        edition[EDITION_FEE_FLAG_INDEX] = FEE_FLAG_SET;
+
+        // the fee flag is not set
+        let mut master_edition = vec![0u8; MAX_MASTER_EDITION_LEN];
+        master_edition[0] = Key::MasterEditionV2 as u8;
+
+        let metadata_address = Keypair::new().pubkey();
+        let edition_address = Keypair::new().pubkey();
+
+        let fee_accounts = find_fee_accounts(
+            [
+                (
+                    metadata_address,
+                    metadata.as_slice(),
+                    rent.minimum_balance(MAX_METADATA_LEN) + CREATE_FEE,
+                ),
+                (
+                    edition_address,
+                    edition.as_slice(),
+                    rent.minimum_balance(MAX_EDITION_LEN) + CREATE_FEE,
+                ),
+                (
+                    Keypair::new().pubkey(),
+                    master_edition.as_slice(),
+                    rent.minimum_balance(MAX_MASTER_EDITION_LEN),
+                ),
+            ],
+            &rent,
+        );
+
+        assert_eq!(
+            fee_accounts,
+            vec![
+                FeeAccount {
+                    address: metadata_address,
+                    key: Key::MetadataV1,
+                    collectible: CREATE_FEE,
+                },
+                FeeAccount {
+                    address: edition_address,
+                    key: Key::EditionV1,
+                    collectible: CREATE_FEE,
+                },
+            ]
+        );
+        assert_eq!(total_collectible_fees(&fee_accounts), 2 * CREATE_FEE);
+    }
+}
@@ -0,0 +1,253 @@
+use solana_program::hash::{hash, hashv};
+
+use super::*;
+
+pub const METADATA_HISTORY_SEED: &str = "metadata_history";
+
+/// Size of an empty metadata history account.
+pub const METADATA_HISTORY_BASE_SIZE: usize = 1 // key
++ 1  // bump
++ 32 // mint
++ 1  // capacity
++ 32 // base hash
++ 4; // entries length
+
+/// Size of a single entry of the metadata history.
+pub const METADATA_HISTORY_ENTRY_SIZE: usize = 32 // hash
++ 8  // slot
++ 32; // signer
+
+/// The `MetadataHistory` account keeps a record of the last `capacity` changes to the
+/// `Data` of an asset, so the state of a mutable asset at a point in time can be proven.
+///
+/// Each entry stores a hash chained to the previous entry: `hash(previous hash, data hash)`.
+/// Once the history is full, the oldest entry is evicted and its hash is kept as the
+/// `base_hash` of the chain. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"metadata_history"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct MetadataHistory {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Mint of the asset.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Maximum number of entries kept.
+    pub capacity: u8,
+    /// Hash preceding the oldest entry (zeroed until an entry is evicted).
+    pub base_hash: [u8; 32],
+    /// Entries, from the oldest to the most recent.
+    pub entries: Vec<MetadataHistoryEntry>,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct MetadataHistoryEntry {
+    /// Chained hash of the `Data` of the asset.
+    pub hash: [u8; 32],
+    /// Slot of the change.
+    pub slot: u64,
+    /// Signer of the change.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub signer: Pubkey,
+}
+
+impl Default for MetadataHistory {
+    fn default() -> Self {
+        Self {
+            key: Key::MetadataHistory,
+            bump: 255,
+            mint: Pubkey::default(),
+            capacity: 0,
+            base_hash: [0; 32],
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl TokenMetadataAccount for MetadataHistory {
+    fn key() -> Key {
+        Key::MetadataHistory
+    }
+
+    /// Returns the size of an empty history account; use `MetadataHistory::data_len`
+    /// to determine the size required for a specific capacity.
+    fn size() -> usize {
+        METADATA_HISTORY_BASE_SIZE
+    }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+
+    fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        let history = Self::from_bytes(&account_info.try_borrow_data()?)?;
+
+        // Check that this is a `token-metadata` owned account.
+        assert_owned_by(account_info, &ID)?;
+
+        Ok(history)
+    }
+}
+
+impl MetadataHistory {
+    /// Deserializes the history account. The account is allocated for its capacity, so
+    /// any unused space after the entries is ignored.
+    pub fn from_bytes(mut data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < METADATA_HISTORY_BASE_SIZE
+            || data[DISCRIMINATOR_INDEX] != Key::MetadataHistory as u8
+        {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let history: MetadataHistory = BorshDeserialize::deserialize(&mut data)?;
+        Ok(history)
+    }
+
+    /// Returns the account size required for the specified capacity.
+    pub fn data_len(capacity: u8) -> usize {
+        METADATA_HISTORY_BASE_SIZE + capacity as usize * METADATA_HISTORY_ENTRY_SIZE
+    }
+
+    /// Returns the hash of the most recent entry, or the base hash if there are no entries.
+    pub fn last_hash(&self) -> [u8; 32] {
+        self.entries
+            .last()
+            .map(|entry| entry.hash)
+            .unwrap_or(self.base_hash)
+    }
+
+    /// Appends an entry for the specified data, evicting the oldest entries when the
+    /// history is full.
+    pub fn push(&mut self, data: &Data, slot: u64, signer: Pubkey) {
+        let entry = MetadataHistoryEntry {
+            hash: chain_hash(&self.last_hash(), data),
+            slot,
+            signer,
+        };
+
+        self.entries.push(entry);
+        self.truncate();
+    }
+
+    /// Evicts the oldest entries exceeding the capacity.
+    pub fn truncate(&mut self) {
+        let excess = self.entries.len().saturating_sub(self.capacity as usize);
+
+        if excess > 0 {
+            self.base_hash = self.entries[excess - 1].hash;
+            self.entries.drain(..excess);
+        }
+    }
+
+    /// Returns the entry recorded for the specified data snapshot, if the snapshot matches
+    /// any entry of the hash chain.
+    pub fn find_snapshot(&self, data: &Data) -> Option<&MetadataHistoryEntry> {
+        let mut previous = self.base_hash;
+
+        for entry in &self.entries {
+            if chain_hash(&previous, data) == entry.hash {
+                return Some(entry);
+            }
+            previous = entry.hash;
+        }
+
+        None
+    }
+
+    /// Saves the history to the specified account.
+    pub fn save(&self, account_info: &AccountInfo) -> ProgramResult {
+        let mut account_data = account_info.try_borrow_mut_data()?;
+        let mut storage = &mut account_data[..];
+        BorshSerialize::serialize(self, &mut storage)?;
+
+        Ok(())
+    }
+}
+
+/// Returns the hash of the data. Trailing null characters of the name, symbol and uri
+/// (added when the metadata is "puffed") are ignored.
+pub fn hash_data(data: &Data) -> [u8; 32] {
+    let normalized = Data {
+        name: data.name.trim_end_matches(char::from(0)).to_string(),
+        symbol: data.symbol.trim_end_matches(char::from(0)).to_string(),
+        uri: data.uri.trim_end_matches(char::from(0)).to_string(),
+        seller_fee_basis_points: data.seller_fee_basis_points,
+        creators: data.creators.clone(),
+    };
+
+    // serializing a struct to a vector does not fail
+    hash(&normalized.try_to_vec().unwrap()).to_bytes()
+}
+
+fn chain_hash(previous: &[u8; 32], data: &Data) -> [u8; 32] {
+    hashv(&[previous, &hash_data(data)]).to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{hash_data, Data, MetadataHistory};
+
+    fn data(uri: &str) -> Data {
+        Data {
+            name: "Asset".to_string(),
+            symbol: "ASSET".to_string(),
+            uri: uri.to_string(),
+            seller_fee_basis_points: 500,
+            creators: None,
+        }
+    }
+
+    #[test]
+    fn puffed_data_hash_matches() {
+        let mut puffed = data("https://example.com/0.json");
+        puffed.uri.push_str(&"\0".repeat(20));
+
+        assert_eq!(
+            hash_data(&puffed),
+            hash_data(&data("https://example.com/0.json"))
+        );
+    }
+
+    #[test]
+    fn evicted_snapshots_are_not_found() {
+        let mut history = MetadataHistory {
+            capacity: 2,
+            ..Default::default()
+        };
+
+        for slot in 0..3 {
+            history.push(
+                &data(&format!("https://example.com/{slot}.json")),
+                slot,
+                Default::default(),
+            );
+        }
+
+        assert_eq!(history.entries.len(), 2);
+        assert!(history
+            .find_snapshot(&data("https://example.com/0.json"))
+            .is_none());
+
+        let entry = history
+            .find_snapshot(&data("https://example.com/1.json"))
+            .unwrap();
+        assert_eq!(entry.slot, 1);
+
+        let entry = history
+            .find_snapshot(&data("https://example.com/2.json"))
+            .unwrap();
+        assert_eq!(entry.slot, 2);
+
+        // the serialized history fits the account allocated for its capacity
+        let buf = borsh::BorshSerialize::try_to_vec(&history).unwrap();
+        assert_eq!(buf.len(), MetadataHistory::data_len(history.capacity));
+    }
+}
@@ -26,7 +26,8 @@ pub const MAX_METADATA_LEN: usize = 1 // key
 + 18             // uses
 + 10             // collection details
 + 33             // programmable config
-+ 75; // Padding
++ 2              // royalty config
++ 73; // Padding
 
 pub const MAX_DATA_SIZE: usize = 4
     + MAX_NAME_LENGTH
@@ -43,6 +44,13 @@ pub const MAX_DATA_SIZE: usize = 4
 // if the account has fees available for retrieval.
 pub const METADATA_FEE_FLAG_INDEX: usize = MAX_METADATA_LEN - 1;
 
+// The second to last byte of the account indicates if the royalty config was
+// written by the program, since legacy accounts might have non-zero padding
+// where the royalty config is serialized.
+pub const METADATA_ROYALTY_CONFIG_FLAG_INDEX: usize = MAX_METADATA_LEN - 2;
+
+pub const ROYALTY_CONFIG_FLAG_SET: u8 = 1;
+
 #[macro_export]
 macro_rules! metadata_seeds {
     ($mint:expr) => {{
@@ -87,6 +95,8 @@ pub struct Metadata {
     pub collection_details: Option<CollectionDetails>,
     /// Programmable Config
     pub programmable_config: Option<ProgrammableConfig>,
+    /// Royalty Config
+    pub royalty_config: Option<RoyaltyConfig>,
 }
 
 impl Metadata {
@@ -94,6 +104,15 @@ impl Metadata {
         let mut bytes = Vec::with_capacity(MAX_METADATA_LEN);
         BorshSerialize::serialize(&self, &mut bytes)?;
         data[..bytes.len()].copy_from_slice(&bytes);
+
+        if let Some(flag) = data.get_mut(METADATA_ROYALTY_CONFIG_FLAG_INDEX) {
+            *flag = if self.royalty_config.is_some() {
+                ROYALTY_CONFIG_FLAG_SET
+            } else {
+                0
+            };
+        }
+
         Ok(())
     }
 
@@ -103,10 +122,12 @@ impl Metadata {
         update_authority: &AccountInfo<'a>,
         metadata: &AccountInfo<'a>,
         token: Option<TokenAccount>,
-        token_standard: TokenStandard,
+        token_standard: Option<TokenStandard>,
     ) -> ProgramResult {
         // Update the token standard if it is changed.
-        self.token_standard = Some(token_standard);
+        if token_standard.is_some() {
+            self.token_standard = token_standard;
+        }
 
         // Only the Update Authority can update this section.
         match &args {
@@ -246,18 +267,23 @@ impl Metadata {
             | UpdateArgs::AsProgrammableConfigDelegateV2 { rule_set, .. }
             | UpdateArgs::AsProgrammableConfigItemDelegateV2 { rule_set, .. } => {
                 // if the rule_set data is either 'Set' or 'Clear', only allow updating if the
-                // token standard is equal to `ProgrammableNonFungible` and no SPL delegate is set.
+                // token standard is programmable and no SPL delegate is set.
                 if matches!(rule_set, RuleSetToggle::Clear | RuleSetToggle::Set(_)) {
-                    if token_standard != TokenStandard::ProgrammableNonFungible {
-                        return Err(MetadataError::InvalidTokenStandard.into());
-                    }
-
-                    // Require the token so we can check if it has a token delegate.
-                    let token = token.ok_or(MetadataError::MissingTokenAccount)?;
-
-                    // If the token has a delegate, we cannot update the rule set.
-                    if token.delegate.is_some() {
-                        return Err(MetadataError::CannotUpdateAssetWithDelegate.into());
+                    match token_standard {
+                        Some(TokenStandard::ProgrammableNonFungible)
+                        | Some(TokenStandard::ProgrammableNonTransferable) => {
+                            // Require the token so we can check if it has a token delegate.
+                            let token = token.ok_or(MetadataError::MissingTokenAccount)?;
+
+                            // If the token has a delegate, we cannot update the rule set.
+                            if token.delegate.is_some() {
+                                return Err(MetadataError::CannotUpdateAssetWithDelegate.into());
+                            }
+                        }
+                        // programmable fungible assets have multiple holders, so there is
+                        // no single token account to check for delegates
+                        Some(TokenStandard::ProgrammableFungibleAsset) => (),
+                        _ => return Err(MetadataError::InvalidTokenStandard.into()),
                     }
 
                     self.programmable_config =
@@ -317,6 +343,7 @@ impl Default for Metadata {
             uses: None,
             collection_details: None,
             programmable_config: None,
+            royalty_config: None,
         }
     }
 }
@@ -383,6 +410,19 @@ pub enum ProgrammableConfig {
     },
 }
 
+/// Indicates how the royalty settings (`creators` and `seller_fee_basis_points`) of an
+/// asset are shared within a collection.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum RoyaltyConfig {
+    /// The royalty settings of the collection parent apply to the items of the collection
+    /// that inherit them.
+    CollectionAuthoritative,
+    /// The item uses the royalty settings of its verified collection parent.
+    InheritFromCollection,
+}
+
 #[cfg(test)]
 mod tests {
     use borsh::{BorshDeserialize, BorshSerialize};
@@ -6,4 +6,171 @@ use super::*;
 pub enum MigrationType {
     CollectionV1,
     ProgrammableV1,
+    NonFungibleV1,
+}
+
+pub const MIGRATION_ALLOWLIST_SEED: &str = "migration_allowlist";
+
+/// Size of an empty migration allowlist account without a rule set.
+pub const MIGRATION_ALLOWLIST_BASE_SIZE: usize = 1 // key
++ 1  // bump
++ 32 // collection mint
++ 1  // rule set option
++ 1  // allow all
++ 4; // mints length
+
+/// The `MigrationAllowlist` account lets the update authority of a collection opt its items
+/// into a holder-initiated migration to `ProgrammableNonFungible`.
+///
+/// Items of the collection can be migrated by their holders when `allow_all` is set or
+/// their mint is listed in `mints`. The seeds for the allowlist PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. collection mint id
+/// 4. `"migration_allowlist"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct MigrationAllowlist {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Mint of the collection.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub collection_mint: Pubkey,
+    /// Rule set assigned to the migrated items.
+    #[cfg_attr(
+        feature = "serde-feature",
+        serde(
+            deserialize_with = "deser_option_pubkey",
+            serialize_with = "ser_option_pubkey"
+        )
+    )]
+    pub rule_set: Option<Pubkey>,
+    /// Indicates whether every item of the collection can be migrated by its holder.
+    pub allow_all: bool,
+    /// Mints of the items that can be migrated by their holders.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<Vec<DisplayFromStr>>"))]
+    pub mints: Vec<Pubkey>,
+}
+
+impl Default for MigrationAllowlist {
+    fn default() -> Self {
+        Self {
+            key: Key::MigrationAllowlist,
+            bump: 255,
+            collection_mint: Pubkey::default(),
+            rule_set: None,
+            allow_all: false,
+            mints: Vec::new(),
+        }
+    }
+}
+
+impl TokenMetadataAccount for MigrationAllowlist {
+    fn key() -> Key {
+        Key::MigrationAllowlist
+    }
+
+    /// Returns the size of an empty allowlist account; use `MigrationAllowlist::data_len`
+    /// to determine the size required by a specific instance.
+    fn size() -> usize {
+        MIGRATION_ALLOWLIST_BASE_SIZE
+    }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+
+    fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        let allowlist = Self::from_bytes(&account_info.try_borrow_data()?)?;
+
+        // Check that this is a `token-metadata` owned account.
+        assert_owned_by(account_info, &ID)?;
+
+        Ok(allowlist)
+    }
+}
+
+impl MigrationAllowlist {
+    /// Deserializes the allowlist account. The account size varies with the number
+    /// of mints, so only the minimum size is checked.
+    pub fn from_bytes(mut data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < MIGRATION_ALLOWLIST_BASE_SIZE
+            || data[DISCRIMINATOR_INDEX] != Key::MigrationAllowlist as u8
+        {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let allowlist: MigrationAllowlist = BorshDeserialize::deserialize(&mut data)?;
+        Ok(allowlist)
+    }
+
+    /// Returns the serialized length of the account.
+    pub fn data_len(&self) -> usize {
+        MIGRATION_ALLOWLIST_BASE_SIZE
+            + if self.rule_set.is_some() { 32 } else { 0 }
+            + self.mints.len() * 32
+    }
+
+    /// Indicates whether the holder of the mint is allowed to migrate it.
+    pub fn is_allowed(&self, mint: &Pubkey) -> bool {
+        self.allow_all || self.mints.contains(mint)
+    }
+
+    /// Saves the allowlist to the specified account, resizing the account to
+    /// match the length of the data.
+    pub fn save<'a>(
+        &self,
+        account_info: &'a AccountInfo<'a>,
+        payer_info: &'a AccountInfo<'a>,
+        system_program_info: &'a AccountInfo<'a>,
+    ) -> ProgramResult {
+        let required_size = self.data_len();
+
+        if account_info.data_len() != required_size {
+            resize_or_reallocate_account_raw(
+                account_info,
+                payer_info,
+                system_program_info,
+                required_size,
+            )?;
+        }
+
+        let mut account_data = account_info.data.borrow_mut();
+        let mut storage = &mut account_data[..required_size];
+        BorshSerialize::serialize(self, &mut storage)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::state::MigrationAllowlist;
+
+    #[test]
+    fn serialized_length_matches() {
+        let mint = Keypair::new().pubkey();
+        let allowlist = MigrationAllowlist {
+            collection_mint: Keypair::new().pubkey(),
+            rule_set: Some(Keypair::new().pubkey()),
+            mints: vec![mint, Keypair::new().pubkey()],
+            ..MigrationAllowlist::default()
+        };
+
+        let mut buf = Vec::new();
+        allowlist.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), allowlist.data_len());
+
+        let deserialized = MigrationAllowlist::from_bytes(&buf).unwrap();
+        assert_eq!(deserialized, allowlist);
+
+        assert!(allowlist.is_allowed(&mint));
+        assert!(!allowlist.is_allowed(&Keypair::new().pubkey()));
+    }
 }
@@ -1,32 +1,44 @@
 pub(crate) mod asset_data;
+pub(crate) mod attributes;
 pub(crate) mod collection;
 pub(crate) mod creator;
 pub(crate) mod data;
+pub(crate) mod decode;
 pub(crate) mod delegate;
 pub(crate) mod edition;
 pub(crate) mod edition_marker;
+pub(crate) mod edition_numbering;
+pub(crate) mod edition_uri_template;
 pub(crate) mod escrow;
 pub mod fee;
+pub(crate) mod history;
 pub(crate) mod master_edition;
 pub(crate) mod metadata;
 pub(crate) mod migrate;
 pub(crate) mod programmable;
+pub(crate) mod reprint_record;
 pub(crate) mod reservation;
 pub(crate) mod token_auth_payload;
+pub(crate) mod update_authority;
 pub(crate) mod uses;
 
 use std::io::ErrorKind;
 
 pub use asset_data::*;
+pub use attributes::*;
 use borsh::{maybestd::io::Error as BorshError, BorshDeserialize, BorshSerialize};
 pub use collection::*;
 pub use creator::*;
 pub use data::*;
+pub use decode::*;
 pub use delegate::*;
 pub use edition::*;
 pub use edition_marker::*;
+pub use edition_numbering::*;
+pub use edition_uri_template::*;
 pub use escrow::*;
 pub use fee::*;
+pub use history::*;
 pub use master_edition::*;
 pub use metadata::*;
 pub use migrate::*;
@@ -34,6 +46,7 @@ use mpl_utils::resize_or_reallocate_account_raw;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 pub use programmable::*;
+pub use reprint_record::*;
 pub use reservation::*;
 use shank::ShankAccount;
 use solana_program::{
@@ -41,6 +54,7 @@ use solana_program::{
     pubkey::Pubkey,
 };
 use spl_token::state::Account as TokenAccount;
+pub use update_authority::*;
 pub use uses::*;
 #[cfg(feature = "serde-feature")]
 use {
@@ -65,11 +79,13 @@ pub const DISCRIMINATOR_INDEX: usize = 0;
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy, FromPrimitive)]
 pub enum TokenStandard {
-    NonFungible,             // This is a master edition
-    FungibleAsset,           // A token with metadata that can also have attributes
-    Fungible,                // A token with simple metadata
-    NonFungibleEdition,      // This is a limited edition
-    ProgrammableNonFungible, // NonFungible with programmable configuration
+    NonFungible,                 // This is a master edition
+    FungibleAsset,               // A token with metadata that can also have attributes
+    Fungible,                    // A token with simple metadata
+    NonFungibleEdition,          // This is a limited edition
+    ProgrammableNonFungible,     // NonFungible with programmable configuration
+    ProgrammableFungibleAsset,   // FungibleAsset with programmable configuration
+    ProgrammableNonTransferable, // ProgrammableNonFungible that can only be recovered or burned
 }
 
 pub trait TokenMetadataAccount: BorshDeserialize {
@@ -139,6 +155,13 @@ pub enum Key {
     TokenOwnedEscrow,
     TokenRecord,
     MetadataDelegate,
+    AttributesV1,
+    MigrationAllowlist,
+    PendingUpdateAuthority,
+    MetadataHistory,
+    UseConfig,
+    EditionUriTemplate,
+    ReprintRecord,
 }
 
 #[cfg(feature = "serde-feature")]
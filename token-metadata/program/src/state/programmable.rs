@@ -16,7 +16,10 @@ use crate::{
     error::MetadataError,
     instruction::MetadataDelegateRole,
     pda::{find_metadata_delegate_record_account, find_token_record_account},
-    processor::{DelegateScenario, TransferScenario, UpdateScenario},
+    processor::{
+        BurnScenario, DelegateScenario, LockScenario, MigrateScenario, MintScenario,
+        TransferScenario, UpdateScenario, UseScenario,
+    },
     utils::assert_owned_by,
 };
 
@@ -29,13 +32,21 @@ pub const TOKEN_STATE_INDEX: usize = 2;
 
 pub const LOCKED_TRANSFER_SIZE: usize = 33; // Optional Pubkey
 
+pub const RENTAL_SIZE: usize = 9 // Optional i64
++ 33; // Optional Pubkey
+
+pub const LOCKED_BY_SIZE: usize = 2; // Optional AuthorityType
+
 pub const TOKEN_RECORD_SIZE: usize = 1 // Key
 + 1   // bump
 + 1   // state
 + 9   // rule set revision
 + 33  // delegate
 + 2   // delegate role
-+ 33; // locked transfer
++ 33  // locked transfer
++ 9   // rental expiry
++ 33  // lender
++ 2; // locked by
 
 /// The `TokenRecord` struct represents the state of the token account holding a `pNFT`. Given
 /// that the token account is always frozen, it includes a `state` that provides an abstraction
@@ -79,6 +90,22 @@ pub struct TokenRecord {
     /// Stores the destination pubkey when a transfer is lock to an allowed address. This
     /// pubkey gets set when a 'LockTransfer' delegate is approved.
     pub locked_transfer: Option<Pubkey>,
+    /// Stores the unix timestamp when a rental ends. This gets set when a 'Rental'
+    /// delegate is approved.
+    pub rental_expiry: Option<i64>,
+    /// Pubkey of the lender of a rented token. The token can only be transferred back
+    /// to the lender once the rental expires.
+    #[cfg_attr(
+        feature = "serde-feature",
+        serde(
+            deserialize_with = "deser_option_pubkey",
+            serialize_with = "ser_option_pubkey"
+        )
+    )]
+    pub lender: Option<Pubkey>,
+    /// The type of the authority that locked the token. Only an authority of the same
+    /// type can unlock it.
+    pub locked_by: Option<AuthorityType>,
 }
 
 impl Default for TokenRecord {
@@ -91,6 +118,9 @@ impl Default for TokenRecord {
             delegate: None,
             delegate_role: None,
             locked_transfer: None,
+            rental_expiry: None,
+            lender: None,
+            locked_by: None,
         }
     }
 }
@@ -126,6 +156,23 @@ impl TokenRecord {
         self.delegate = None;
         self.delegate_role = None;
         self.locked_transfer = None;
+        self.rental_expiry = None;
+        self.lender = None;
+        self.locked_by = None;
+    }
+
+    /// Checks whether the token is held by the borrower of a rental, in which case
+    /// the holder cannot transfer or burn it.
+    pub fn is_rented(&self, holder: &Pubkey) -> bool {
+        matches!(self.delegate_role, Some(TokenDelegateRole::Rental))
+            && self.lender.as_ref() != Some(holder)
+    }
+
+    /// Checks whether the rental has expired at the specified timestamp.
+    pub fn is_rental_expired(&self, timestamp: i64) -> bool {
+        self.rental_expiry
+            .map(|expiry| timestamp >= expiry)
+            .unwrap_or(false)
     }
 }
 
@@ -137,7 +184,10 @@ impl Resizable for TokenRecord {
 
         // we use the account length in the 'is_correct_account_type' since we are
         // manually checking that the account length is valid
-        if !(length == 0 || length == LOCKED_TRANSFER_SIZE as i64)
+        if !(length == 0
+            || length == LOCKED_BY_SIZE as i64
+            || length == (LOCKED_BY_SIZE + RENTAL_SIZE) as i64
+            || length == (LOCKED_BY_SIZE + RENTAL_SIZE + LOCKED_TRANSFER_SIZE) as i64)
             || !TokenRecord::is_correct_account_type(
                 account_data,
                 Key::TokenRecord,
@@ -156,7 +206,23 @@ impl Resizable for TokenRecord {
         let delegate: Option<Pubkey> = BorshDeserialize::deserialize(&mut data)?;
         let delegate_role: Option<TokenDelegateRole> = BorshDeserialize::deserialize(&mut data)?;
 
-        let locked_transfer: Option<Pubkey> = if length == 0 {
+        let locked_transfer: Option<Pubkey> = if length <= (LOCKED_BY_SIZE + RENTAL_SIZE) as i64 {
+            BorshDeserialize::deserialize(&mut data)?
+        } else {
+            None
+        };
+
+        let (rental_expiry, lender): (Option<i64>, Option<Pubkey>) =
+            if length <= LOCKED_BY_SIZE as i64 {
+                (
+                    BorshDeserialize::deserialize(&mut data)?,
+                    BorshDeserialize::deserialize(&mut data)?,
+                )
+            } else {
+                (None, None)
+            };
+
+        let locked_by: Option<AuthorityType> = if length == 0 {
             BorshDeserialize::deserialize(&mut data)?
         } else {
             None
@@ -170,6 +236,9 @@ impl Resizable for TokenRecord {
             delegate,
             delegate_role,
             locked_transfer,
+            rental_expiry,
+            lender,
+            locked_by,
         })
     }
 }
@@ -197,7 +266,8 @@ pub enum TokenDelegateRole {
     Staking,
     Standard,
     LockedTransfer,
-    Migration = 255,
+    Migration,
+    Rental,
 }
 
 pub struct AuthorityRequest<'a, 'b> {
@@ -343,6 +413,9 @@ impl AuthorityType {
                                 )?;
 
                                 if delegate_record.delegate == *request.authority {
+                                    // expired or exhausted delegates cannot be used
+                                    delegate_record.assert_active()?;
+
                                     return Ok(AuthorityResponse {
                                         authority_type: AuthorityType::MetadataDelegate,
                                         metadata_delegate_role: Some(*role),
@@ -370,6 +443,8 @@ impl AuthorityType {
                                         )?;
 
                                     if delegate_record.delegate == *request.authority {
+                                        delegate_record.assert_active()?;
+
                                         return Ok(AuthorityResponse {
                                             authority_type: AuthorityType::MetadataDelegate,
                                             metadata_delegate_role: Some(*role),
@@ -417,6 +492,12 @@ pub enum Operation {
     Transfer { scenario: TransferScenario },
     Update { scenario: UpdateScenario },
     Delegate { scenario: DelegateScenario },
+    Burn { scenario: BurnScenario },
+    Mint { scenario: MintScenario },
+    Use { scenario: UseScenario },
+    Lock { scenario: LockScenario },
+    Unlock { scenario: LockScenario },
+    Migrate { scenario: MigrateScenario },
 }
 
 impl ToString for Operation {
@@ -425,6 +506,12 @@ impl ToString for Operation {
             Self::Transfer { scenario } => format!("Transfer:{}", scenario),
             Self::Update { scenario } => format!("Update:{}", scenario),
             Self::Delegate { scenario } => format!("Delegate:{}", scenario),
+            Self::Burn { scenario } => format!("Burn:{}", scenario),
+            Self::Mint { scenario } => format!("Mint:{}", scenario),
+            Self::Use { scenario } => format!("Use:{}", scenario),
+            Self::Lock { scenario } => format!("Lock:{}", scenario),
+            Self::Unlock { scenario } => format!("Unlock:{}", scenario),
+            Self::Migrate { scenario } => format!("Migrate:{}", scenario),
         }
     }
 }
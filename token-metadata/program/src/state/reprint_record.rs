@@ -0,0 +1,83 @@
+use super::*;
+
+pub const REPRINT_RECORD_SEED: &str = "reprint";
+
+pub const REPRINT_RECORD_SIZE: usize = 1 // key
++ 1  // bump
++ 8  // edition
++ 32; // burned mint
+
+/// The `ReprintRecord` account records that a print edition was burned while its edition
+/// number stayed taken on the edition marker, which is the case when the print is burned
+/// from a wallet other than the master edition owner. The record allows a single reprint
+/// of the edition number and is closed by it. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. master edition mint id
+/// 4. `"edition"`
+/// 5. edition number
+/// 6. `"reprint"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct ReprintRecord {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Number of the burned print edition.
+    pub edition: u64,
+    /// Mint of the burned print edition.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+}
+
+impl Default for ReprintRecord {
+    fn default() -> Self {
+        Self {
+            key: Key::ReprintRecord,
+            bump: 255,
+            edition: 0,
+            mint: Pubkey::default(),
+        }
+    }
+}
+
+impl TokenMetadataAccount for ReprintRecord {
+    fn key() -> Key {
+        Key::ReprintRecord
+    }
+
+    fn size() -> usize {
+        REPRINT_RECORD_SIZE
+    }
+}
+
+impl ReprintRecord {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let record: ReprintRecord = try_from_slice_checked(data, Key::ReprintRecord, Self::size())?;
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::state::{ReprintRecord, TokenMetadataAccount};
+
+    #[test]
+    fn serialized_record_fits() {
+        let record = ReprintRecord {
+            edition: u64::MAX,
+            mint: Keypair::new().pubkey(),
+            ..ReprintRecord::default()
+        };
+
+        let mut buf = Vec::new();
+        record.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), ReprintRecord::size());
+        assert_eq!(ReprintRecord::from_bytes(&buf).unwrap(), record);
+    }
+}
@@ -0,0 +1,118 @@
+use super::*;
+
+pub const PENDING_UPDATE_AUTHORITY_SEED: &str = "pending_update_authority";
+
+pub const PENDING_UPDATE_AUTHORITY_SIZE: usize = 1 // key
++ 1  // bump
++ 32 // mint
++ 32 // update authority
++ 32 // pending update authority
++ 1; // collection
+
+/// The `PendingUpdateAuthority` account holds an update authority handover proposed by the
+/// current update authority of an asset. The update authority only changes once the
+/// pending update authority accepts the handover.
+///
+/// When `collection` is set, the handover also covers every item of the verified
+/// collection whose update authority is the proposer. The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"pending_update_authority"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct PendingUpdateAuthority {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Mint of the asset (or collection) being handed over.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Update authority that proposed the handover.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub update_authority: Pubkey,
+    /// Update authority that must accept the handover.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub pending_update_authority: Pubkey,
+    /// Indicates whether the handover covers the items of the collection.
+    pub collection: bool,
+}
+
+impl Default for PendingUpdateAuthority {
+    fn default() -> Self {
+        Self {
+            key: Key::PendingUpdateAuthority,
+            bump: 255,
+            mint: Pubkey::default(),
+            update_authority: Pubkey::default(),
+            pending_update_authority: Pubkey::default(),
+            collection: false,
+        }
+    }
+}
+
+impl TokenMetadataAccount for PendingUpdateAuthority {
+    fn key() -> Key {
+        Key::PendingUpdateAuthority
+    }
+
+    fn size() -> usize {
+        PENDING_UPDATE_AUTHORITY_SIZE
+    }
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, BorshError> {
+        Self::from_bytes(data).map_err(|e| BorshError::new(ErrorKind::Other, e.to_string()))
+    }
+
+    fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        let pending = Self::from_bytes(&account_info.try_borrow_data()?)?;
+
+        // Check that this is a `token-metadata` owned account.
+        assert_owned_by(account_info, &ID)?;
+
+        Ok(pending)
+    }
+}
+
+impl PendingUpdateAuthority {
+    pub fn from_bytes(mut data: &[u8]) -> Result<Self, ProgramError> {
+        if !Self::is_correct_account_type(
+            data,
+            Key::PendingUpdateAuthority,
+            PENDING_UPDATE_AUTHORITY_SIZE,
+        ) {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        let pending: PendingUpdateAuthority = BorshDeserialize::deserialize(&mut data)?;
+        Ok(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::state::{PendingUpdateAuthority, TokenMetadataAccount};
+
+    #[test]
+    fn serialized_length_matches() {
+        let pending = PendingUpdateAuthority {
+            mint: Keypair::new().pubkey(),
+            update_authority: Keypair::new().pubkey(),
+            pending_update_authority: Keypair::new().pubkey(),
+            collection: true,
+            ..PendingUpdateAuthority::default()
+        };
+
+        let mut buf = Vec::new();
+        pending.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), PendingUpdateAuthority::size());
+
+        let deserialized = PendingUpdateAuthority::from_bytes(&buf).unwrap();
+        assert_eq!(deserialized, pending);
+    }
+}
@@ -2,6 +2,21 @@ use super::*;
 
 pub const USE_AUTHORITY_RECORD_SIZE: usize = 18; //8 byte padding
 
+/// Index of the last refill timestamp of a use authority record, stored in the padding of
+/// the account since the record was created without space for it.
+pub const USE_AUTHORITY_RECORD_LAST_REFILL_INDEX: usize = 10;
+
+pub const USE_CONFIG_SEED: &str = "use_config";
+
+pub const USE_CONFIG_SIZE: usize = 1 // key
++ 1  // bump
++ 32 // mint
++ 8  // refill period
++ 8  // refill amount
++ 8  // min use interval
++ 8  // last refill at
++ 8; // last used at
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, FromPrimitive)]
@@ -9,6 +24,8 @@ pub enum UseMethod {
     Burn,
     Multiple,
     Single,
+    /// Multiple uses that refill over time according to the `UseConfig` of the asset.
+    Refill,
 }
 
 #[repr(C)]
@@ -60,6 +77,119 @@ impl UseAuthorityRecord {
     pub fn bump_empty(&self) -> bool {
         self.bump == 0 && self.key == Key::UseAuthorityRecord
     }
+
+    /// Returns the timestamp of the last refill of the allowed uses.
+    pub fn last_refill_at(data: &[u8]) -> i64 {
+        let mut timestamp = [0u8; 8];
+        timestamp.copy_from_slice(
+            &data[USE_AUTHORITY_RECORD_LAST_REFILL_INDEX
+                ..USE_AUTHORITY_RECORD_LAST_REFILL_INDEX + 8],
+        );
+        i64::from_le_bytes(timestamp)
+    }
+
+    /// Sets the timestamp of the last refill of the allowed uses.
+    pub fn set_last_refill_at(data: &mut [u8], timestamp: i64) {
+        data[USE_AUTHORITY_RECORD_LAST_REFILL_INDEX..USE_AUTHORITY_RECORD_LAST_REFILL_INDEX + 8]
+            .copy_from_slice(&timestamp.to_le_bytes());
+    }
+}
+
+/// The `UseConfig` account holds the refill and cooldown settings of an asset with the
+/// `Refill` use method, along with the timestamps of its last refill and use.
+///
+/// The seeds for the PDA are:
+/// 1. `"metadata"`
+/// 2. program id
+/// 3. mint id
+/// 4. `"use_config"`
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct UseConfig {
+    /// Account key.
+    pub key: Key,
+    /// Derivation bump.
+    pub bump: u8,
+    /// Mint of the asset.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub mint: Pubkey,
+    /// Number of seconds between refills (`0` disables refills).
+    pub refill_period: i64,
+    /// Number of uses added on each refill, up to the total uses.
+    pub refill_amount: u64,
+    /// Minimum number of seconds between uses.
+    pub min_use_interval: i64,
+    /// Timestamp of the last refill.
+    pub last_refill_at: i64,
+    /// Timestamp of the last use (`0` if the asset was not used).
+    pub last_used_at: i64,
+}
+
+impl Default for UseConfig {
+    fn default() -> Self {
+        Self {
+            key: Key::UseConfig,
+            bump: 255,
+            mint: Pubkey::default(),
+            refill_period: 0,
+            refill_amount: 0,
+            min_use_interval: 0,
+            last_refill_at: 0,
+            last_used_at: 0,
+        }
+    }
+}
+
+impl TokenMetadataAccount for UseConfig {
+    fn key() -> Key {
+        Key::UseConfig
+    }
+
+    fn size() -> usize {
+        USE_CONFIG_SIZE
+    }
+}
+
+impl UseConfig {
+    pub fn from_bytes(data: &[u8]) -> Result<UseConfig, ProgramError> {
+        let config: UseConfig = try_from_slice_checked(data, Key::UseConfig, USE_CONFIG_SIZE)?;
+        Ok(config)
+    }
+
+    /// Returns the refilled number of uses and the timestamp of the refill, given the
+    /// current number of uses and the timestamp of the previous refill.
+    pub fn refilled(&self, current: u64, total: u64, last_refill_at: i64, now: i64) -> (u64, i64) {
+        if self.refill_period <= 0 || self.refill_amount == 0 || now <= last_refill_at {
+            return (current, last_refill_at);
+        }
+
+        let periods = (now - last_refill_at) / self.refill_period;
+        let refill = (periods as u64).saturating_mul(self.refill_amount);
+
+        (
+            current.saturating_add(refill).min(total).max(current),
+            last_refill_at + periods * self.refill_period,
+        )
+    }
+
+    /// Refills the remaining uses of the asset.
+    pub fn refill(&mut self, uses: &mut Uses, now: i64) {
+        let (remaining, last_refill_at) =
+            self.refilled(uses.remaining, uses.total, self.last_refill_at, now);
+
+        uses.remaining = remaining;
+        self.last_refill_at = last_refill_at;
+    }
+
+    /// Checks that the minimum interval since the last use has elapsed.
+    pub fn assert_cooldown_elapsed(&self, now: i64) -> ProgramResult {
+        if self.last_used_at > 0 && now.saturating_sub(self.last_used_at) < self.min_use_interval {
+            return Err(MetadataError::UseCooldownActive.into());
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -70,7 +200,10 @@ mod tests {
 
     use crate::{
         error::MetadataError,
-        state::{CollectionAuthorityRecord, Key, TokenMetadataAccount, UseAuthorityRecord},
+        state::{
+            CollectionAuthorityRecord, Key, TokenMetadataAccount, UseAuthorityRecord, UseConfig,
+            UseMethod, Uses,
+        },
         ID,
     };
 
@@ -129,4 +262,41 @@ mod tests {
         let error = UseAuthorityRecord::from_account_info(&account_info).unwrap_err();
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn refills_uses_up_to_total() {
+        let mut config = UseConfig {
+            refill_period: 60,
+            refill_amount: 2,
+            min_use_interval: 10,
+            last_refill_at: 1_000,
+            ..Default::default()
+        };
+        let mut uses = Uses {
+            use_method: UseMethod::Refill,
+            remaining: 1,
+            total: 5,
+        };
+
+        // less than a period elapsed
+        config.refill(&mut uses, 1_059);
+        assert_eq!(uses.remaining, 1);
+        assert_eq!(config.last_refill_at, 1_000);
+
+        // one period elapsed, the partial period is carried over
+        config.refill(&mut uses, 1_090);
+        assert_eq!(uses.remaining, 3);
+        assert_eq!(config.last_refill_at, 1_060);
+
+        // many periods elapsed
+        config.refill(&mut uses, 10_000);
+        assert_eq!(uses.remaining, 5);
+
+        config.last_used_at = 10_000;
+        assert_eq!(
+            config.assert_cooldown_elapsed(10_005).unwrap_err(),
+            MetadataError::UseCooldownActive.into()
+        );
+        assert!(config.assert_cooldown_elapsed(10_010).is_ok());
+    }
 }
@@ -1,7 +1,10 @@
 use solana_program::msg;
 
 use super::*;
-use crate::state::CollectionDetails;
+use crate::{pda::find_metadata_account, state::CollectionDetails};
+
+/// Maximum number of parent collections that a collection size change is propagated to.
+pub const MAX_COLLECTION_DEPTH: usize = 3;
 
 pub fn increment_collection_size(
     metadata: &mut Metadata,
@@ -47,3 +50,230 @@ pub fn decrement_collection_size(
         Err(MetadataError::UnsizedCollection.into())
     }
 }
+
+/// Returns the mint of the verified parent collection of an asset, if any.
+fn verified_collection_mint(metadata: &Metadata) -> Option<Pubkey> {
+    metadata
+        .collection
+        .as_ref()
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key)
+}
+
+/// Propagates a size change of a collection to its parent collections.
+///
+/// The `ancestors` are the (optional) metadata accounts of the parent collections, starting
+/// with the parent of `collection_metadata`. The propagation only runs when they are passed,
+/// in which case they must include the whole chain of verified parent collections (up to
+/// `MAX_COLLECTION_DEPTH` levels), so the sizes along the chain are always updated together.
+/// The chain ends at the first parent collection that is unsized, since its size is not
+/// tracked.
+pub fn propagate_collection_size_change(
+    program_id: &Pubkey,
+    collection_metadata: &Metadata,
+    ancestors: &[Option<&AccountInfo>],
+    increment: bool,
+) -> ProgramResult {
+    let ancestors = ancestors.iter().flatten().collect::<Vec<_>>();
+
+    if ancestors.is_empty() {
+        return Ok(());
+    }
+
+    if ancestors.len() > MAX_COLLECTION_DEPTH {
+        return Err(MetadataError::CollectionDepthExceeded.into());
+    }
+
+    let mut parent_mint = verified_collection_mint(collection_metadata);
+    let mut ancestors_iter = ancestors.iter();
+
+    for _ in 0..MAX_COLLECTION_DEPTH {
+        let mint = match parent_mint {
+            Some(mint) => mint,
+            None => break,
+        };
+
+        // partial chains would leave the sizes of the upper levels out of sync
+        let ancestor_info = ancestors_iter
+            .next()
+            .ok_or(MetadataError::IncompleteCollectionChain)?;
+
+        if find_metadata_account(&mint).0 != *ancestor_info.key {
+            return Err(MetadataError::InvalidCollectionAncestor.into());
+        }
+        assert_owned_by(ancestor_info, program_id)?;
+
+        let mut ancestor = Metadata::from_account_info(ancestor_info)?;
+
+        if ancestor.collection_details.is_none() {
+            break;
+        }
+
+        if increment {
+            increment_collection_size(&mut ancestor, ancestor_info)?;
+        } else {
+            decrement_collection_size(&mut ancestor, ancestor_info)?;
+        }
+
+        parent_mint = verified_collection_mint(&ancestor);
+    }
+
+    // accounts past the end of the chain are not parent collections
+    if ancestors_iter.next().is_some() {
+        return Err(MetadataError::InvalidCollectionAncestor.into());
+    }
+
+    Ok(())
+}
+
+/// Walks the chain of verified parent collections of an asset, returning the mints of the
+/// parent collections (closest parent first).
+///
+/// The `get_metadata` function is used to retrieve the metadata of a collection mint, which
+/// allows the chain to be walked off-chain (e.g., to build the list of ancestor accounts
+/// required to propagate a collection size change). The walk stops at the first collection
+/// without a verified parent, when a metadata is not found or after `max_depth` levels.
+pub fn get_collection_chain<F>(
+    metadata: &Metadata,
+    max_depth: usize,
+    mut get_metadata: F,
+) -> Result<Vec<Pubkey>, ProgramError>
+where
+    F: FnMut(&Pubkey) -> Result<Option<Metadata>, ProgramError>,
+{
+    let mut chain = Vec::new();
+    let mut parent_mint = verified_collection_mint(metadata);
+
+    while let Some(mint) = parent_mint {
+        if chain.len() == max_depth || chain.contains(&mint) {
+            break;
+        }
+
+        chain.push(mint);
+
+        parent_mint = match get_metadata(&mint)? {
+            Some(parent) => verified_collection_mint(&parent),
+            None => None,
+        };
+    }
+
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use solana_program::pubkey::Pubkey;
+
+    use borsh::BorshSerialize;
+    use solana_program::account_info::AccountInfo;
+
+    use crate::{
+        error::MetadataError,
+        pda::find_metadata_account,
+        state::{Collection, CollectionDetails, Key, Metadata, MAX_METADATA_LEN},
+        utils::{get_collection_chain, propagate_collection_size_change},
+    };
+
+    fn member_of(mint: Pubkey, parent: Option<Pubkey>, verified: bool) -> Metadata {
+        Metadata {
+            mint,
+            collection: parent.map(|key| Collection { verified, key }),
+            ..Metadata::default()
+        }
+    }
+
+    #[test]
+    fn walk_nested_collections() {
+        let series = Pubkey::new_unique();
+        let season = Pubkey::new_unique();
+        let drop = Pubkey::new_unique();
+        let item = Pubkey::new_unique();
+
+        let mut accounts = HashMap::new();
+        accounts.insert(series, member_of(series, None, false));
+        accounts.insert(season, member_of(season, Some(series), true));
+        accounts.insert(drop, member_of(drop, Some(season), true));
+
+        let metadata = member_of(item, Some(drop), true);
+
+        let chain =
+            get_collection_chain(&metadata, 5, |mint| Ok(accounts.get(mint).cloned())).unwrap();
+        assert_eq!(chain, vec![drop, season, series]);
+
+        // the walk is bounded
+        let chain =
+            get_collection_chain(&metadata, 2, |mint| Ok(accounts.get(mint).cloned())).unwrap();
+        assert_eq!(chain, vec![drop, season]);
+    }
+
+    #[test]
+    fn walk_stops_at_unverified_parent() {
+        let season = Pubkey::new_unique();
+        let drop = Pubkey::new_unique();
+        let item = Pubkey::new_unique();
+
+        let mut accounts = HashMap::new();
+        accounts.insert(season, member_of(season, None, false));
+        accounts.insert(drop, member_of(drop, Some(season), false));
+
+        let metadata = member_of(item, Some(drop), true);
+
+        let chain =
+            get_collection_chain(&metadata, 5, |mint| Ok(accounts.get(mint).cloned())).unwrap();
+        assert_eq!(chain, vec![drop]);
+
+        let unverified = member_of(item, Some(drop), false);
+        let chain =
+            get_collection_chain(&unverified, 5, |mint| Ok(accounts.get(mint).cloned())).unwrap();
+        assert!(chain.is_empty());
+    }
+
+    #[test]
+    fn propagation_requires_whole_chain_when_ancestors_are_passed() {
+        let series = Pubkey::new_unique();
+        let season = Pubkey::new_unique();
+        let drop = Pubkey::new_unique();
+
+        // the propagation is skipped when the ancestors are not passed
+        let nested = member_of(drop, Some(season), true);
+        propagate_collection_size_change(&crate::ID, &nested, &[None, None, None], true).unwrap();
+
+        // the season collection is nested in the series collection, which is missing
+        let mut season_metadata = member_of(season, Some(series), true);
+        season_metadata.key = Key::MetadataV1;
+        season_metadata.collection_details = Some(CollectionDetails::V1 { size: 1 });
+
+        let mut data = season_metadata.try_to_vec().unwrap();
+        data.resize(MAX_METADATA_LEN, 0);
+        let mut lamports = 0;
+        let (key, _) = find_metadata_account(&season);
+        let season_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        let error = propagate_collection_size_change(
+            &crate::ID,
+            &nested,
+            &[Some(&season_info), None, None],
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(error, MetadataError::IncompleteCollectionChain.into());
+
+        // collections without a verified parent have no chain to update
+        let unverified = member_of(drop, Some(season), false);
+        let error =
+            propagate_collection_size_change(&crate::ID, &unverified, &[Some(&season_info)], true)
+                .unwrap_err();
+        assert_eq!(error, MetadataError::InvalidCollectionAncestor.into());
+    }
+}
@@ -3,20 +3,28 @@ use solana_program::{
     sysvar::Sysvar,
 };
 
-use crate::state::{fee::CREATE_FEE, Metadata, TokenMetadataAccount, METADATA_FEE_FLAG_INDEX};
+use crate::{
+    error::MetadataError,
+    state::{
+        fee::{fee_flag_index, CREATE_FEE, FEE_FLAG_CLEARED, FEE_FLAG_SET},
+        Key,
+    },
+};
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct LevyArgs<'a> {
     pub payer_account_info: &'a AccountInfo<'a>,
     pub token_metadata_pda_info: &'a AccountInfo<'a>,
+    /// Size of the account, used to fund it with rent.
+    pub account_size: usize,
 }
 
 pub(crate) fn levy(args: LevyArgs) -> ProgramResult {
-    // Fund metadata account with rent + Metaplex fee.
+    // Fund the account with rent + Metaplex fee.
     let rent = Rent::get()?;
 
-    let fee = CREATE_FEE + rent.minimum_balance(Metadata::size());
+    let fee = CREATE_FEE + rent.minimum_balance(args.account_size);
 
     invoke(
         &solana_program::system_instruction::transfer(
@@ -33,19 +41,21 @@ pub(crate) fn levy(args: LevyArgs) -> ProgramResult {
     Ok(())
 }
 
-pub(crate) fn set_fee_flag(pda_account_info: &AccountInfo) -> ProgramResult {
+pub(crate) fn set_fee_flag(pda_account_info: &AccountInfo, key: Key) -> ProgramResult {
+    let index = fee_flag_index(key).ok_or(MetadataError::InvalidFeeAccount)?;
+
     let mut data = pda_account_info.try_borrow_mut_data()?;
-    data[METADATA_FEE_FLAG_INDEX] = 1;
+    data[index] = FEE_FLAG_SET;
 
     Ok(())
 }
 
-pub(crate) fn clear_fee_flag(pda_account_info: &AccountInfo) -> ProgramResult {
+pub(crate) fn clear_fee_flag(pda_account_info: &AccountInfo, key: Key) -> ProgramResult {
     let mut data = pda_account_info.try_borrow_mut_data()?;
 
     // Clear the flag if the index exists.
-    if let Some(flag) = data.get_mut(METADATA_FEE_FLAG_INDEX) {
-        *flag = 0;
+    if let Some(flag) = fee_flag_index(key).and_then(|index| data.get_mut(index)) {
+        *flag = FEE_FLAG_CLEARED;
     }
 
     Ok(())
@@ -13,16 +13,20 @@ use spl_token::state::{Account, Mint};
 use super::*;
 use crate::{
     assertions::{
-        assert_derivation, assert_initialized, assert_mint_authority_matches_mint, assert_owned_by,
+        assert_derivation, assert_initialized, assert_keys_equal,
+        assert_mint_authority_matches_mint, assert_owned_by, assert_owned_by_token_program,
         assert_token_program_matches_package, edition::assert_edition_valid,
         metadata::assert_update_authority_is_correct,
     },
     error::MetadataError,
+    pda::find_edition_uri_template_account,
     state::{
-        get_reservation_list, DataV2, EditionMarker, Key, MasterEdition, Metadata,
-        TokenMetadataAccount, Uses, EDITION, EDITION_MARKER_BIT_SIZE, MAX_EDITION_LEN,
-        MAX_EDITION_MARKER_SIZE, MAX_MASTER_EDITION_LEN, PREFIX,
+        get_reservation_list, DataV2, EditionMarker, EditionNumbering, EditionUriTemplate, Key,
+        MasterEdition, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard, Uses,
+        EDITION, EDITION_MARKER_BIT_SIZE, MAX_EDITION_LEN, MAX_EDITION_MARKER_SIZE,
+        MAX_MASTER_EDITION_LEN, PREFIX, TOKEN_STANDARD_INDEX,
     },
+    utils::fee::{levy, set_fee_flag, LevyArgs},
 };
 
 pub struct MintNewEditionFromMasterEditionViaTokenLogicArgs<'a> {
@@ -39,6 +43,8 @@ pub struct MintNewEditionFromMasterEditionViaTokenLogicArgs<'a> {
     pub master_metadata_account_info: &'a AccountInfo<'a>,
     pub token_program_account_info: &'a AccountInfo<'a>,
     pub system_account_info: &'a AccountInfo<'a>,
+    /// Optional edition URI template account of the master edition.
+    pub edition_uri_template_info: Option<&'a AccountInfo<'a>>,
 }
 
 pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
@@ -61,15 +67,16 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
         master_metadata_account_info,
         token_program_account_info,
         system_account_info,
+        edition_uri_template_info,
     } = accounts;
 
     assert_token_program_matches_package(token_program_account_info)?;
-    assert_owned_by(mint_info, &spl_token::ID)?;
-    assert_owned_by(token_account_info, &spl_token::ID)?;
+    assert_owned_by(mint_info, token_program_account_info.key)?;
+    assert_owned_by_token_program(token_account_info)?;
     assert_owned_by(master_edition_account_info, program_id)?;
     assert_owned_by(master_metadata_account_info, program_id)?;
 
-    let master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
+    let mut master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
     let token_account: Account = assert_initialized(token_account_info)?;
 
     if !ignore_owner_signer {
@@ -133,6 +140,27 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
 
     let mut edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
     edition_marker.key = Key::EditionMarker;
+
+    // the numbering policy of the master edition (if any) determines the edition number
+    let mut numbering = EditionNumbering::from_master_edition(master_edition_account_info)?;
+
+    let edition = if let Some(numbering) = &mut numbering {
+        let update_authority_signed = update_authority_info.is_signer
+            && *update_authority_info.key == master_metadata.update_authority;
+
+        let edition = numbering.resolve(
+            edition,
+            &edition_marker,
+            get_max_supply_off_master_edition(master_edition_account_info)?,
+            update_authority_signed,
+        )?;
+        numbering.advance(edition)?;
+
+        edition
+    } else {
+        edition
+    };
+
     if edition_marker.edition_taken(edition)? {
         return Err(MetadataError::AlreadyInitialized.into());
     } else {
@@ -140,6 +168,8 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
     }
     edition_marker.serialize(&mut *edition_marker_info.data.borrow_mut())?;
 
+    apply_edition_uri_template(&mut master_metadata, edition_uri_template_info, edition)?;
+
     mint_limited_edition(
         program_id,
         master_metadata,
@@ -155,6 +185,35 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
         None,
         Some(edition),
     )?;
+
+    if let Some(numbering) = numbering {
+        numbering.save(master_edition_account_info)?;
+    }
+
+    Ok(())
+}
+
+/// Replaces the URI copied to a print edition by the one resolved from the edition URI
+/// template of the master edition, when the template account is passed and has been
+/// created. Otherwise, the print edition keeps the URI of the master edition.
+pub fn apply_edition_uri_template(
+    master_metadata: &mut Metadata,
+    template_info: Option<&AccountInfo>,
+    edition: u64,
+) -> ProgramResult {
+    let template_info = match template_info {
+        Some(template_info) => template_info,
+        None => return Ok(()),
+    };
+
+    let (template_key, _) = find_edition_uri_template_account(&master_metadata.mint);
+    assert_keys_equal(&template_key, template_info.key)?;
+
+    if !template_info.data_is_empty() {
+        let template = EditionUriTemplate::from_account_info(template_info)?;
+        master_metadata.data.uri = template.resolve(edition)?;
+    }
+
     Ok(())
 }
 
@@ -423,6 +482,13 @@ pub fn mint_limited_edition<'a>(
         &[bump_seed],
     ];
 
+    // Levy fees on the print edition account, the same way they are on the metadata account.
+    levy(LevyArgs {
+        payer_account_info,
+        token_metadata_pda_info: new_edition_account_info,
+        account_size: MAX_EDITION_LEN,
+    })?;
+
     create_or_allocate_account_raw(
         *program_id,
         new_edition_account_info,
@@ -432,22 +498,27 @@ pub fn mint_limited_edition<'a>(
         edition_authority_seeds,
     )?;
 
-    // Doing old school serialization to protect CPU credits.
-    let edition_data = &mut new_edition_account_info.data.borrow_mut();
-    let output = array_mut_ref![edition_data, 0, MAX_EDITION_LEN];
+    // Scope refcell borrow
+    {
+        // Doing old school serialization to protect CPU credits.
+        let edition_data = &mut new_edition_account_info.data.borrow_mut();
+        let output = array_mut_ref![edition_data, 0, MAX_EDITION_LEN];
 
-    let (key, parent, edition, _padding) = mut_array_refs![output, 1, 32, 8, 200];
+        let (key, parent, edition, _padding) = mut_array_refs![output, 1, 32, 8, 200];
 
-    *key = [Key::EditionV1 as u8];
-    parent.copy_from_slice(master_edition_account_info.key.as_ref());
+        *key = [Key::EditionV1 as u8];
+        parent.copy_from_slice(master_edition_account_info.key.as_ref());
 
-    *edition = calculate_edition_number(
-        mint_authority_info,
-        reservation_list_info,
-        edition_override,
-        me_supply,
-    )?
-    .to_le_bytes();
+        *edition = calculate_edition_number(
+            mint_authority_info,
+            reservation_list_info,
+            edition_override,
+            me_supply,
+        )?
+        .to_le_bytes();
+    }
+
+    set_fee_flag(new_edition_account_info, Key::EditionV1)?;
 
     // Now make sure this mint can never be used by anybody else.
     transfer_mint_authority(
@@ -496,7 +567,7 @@ pub fn create_master_edition<'a>(
     assert_token_program_matches_package(token_program_info)?;
     assert_mint_authority_matches_mint(&mint.mint_authority, mint_authority_info)?;
     assert_owned_by(metadata_account_info, program_id)?;
-    assert_owned_by(mint_info, &spl_token::ID)?;
+    assert_owned_by(mint_info, token_program_info.key)?;
 
     if metadata.mint != *mint_info.key {
         return Err(MetadataError::MintMismatch.into());
@@ -552,3 +623,72 @@ pub fn create_master_edition<'a>(
         token_program_info,
     )
 }
+
+/// Creates the master edition account for `ProgrammableFungibleAsset` assets.
+///
+/// The master edition PDA becomes the mint and freeze authority of the mint, so tokens
+/// can only be minted, thawed and frozen through Token Metadata. Unlike non-fungible
+/// master editions, the mint can have decimals and supply, but prints are not allowed.
+pub fn create_programmable_fungible_edition<'a>(
+    program_id: &Pubkey,
+    edition_account_info: &'a AccountInfo<'a>,
+    mint_info: &'a AccountInfo<'a>,
+    mint_authority_info: &'a AccountInfo<'a>,
+    payer_account_info: &'a AccountInfo<'a>,
+    token_program_info: &'a AccountInfo<'a>,
+    system_account_info: &'a AccountInfo<'a>,
+) -> ProgramResult {
+    let mint: Mint = assert_initialized(mint_info)?;
+
+    let bump_seed = assert_derivation(
+        program_id,
+        edition_account_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+
+    assert_token_program_matches_package(token_program_info)?;
+    assert_mint_authority_matches_mint(&mint.mint_authority, mint_authority_info)?;
+    assert_owned_by(mint_info, token_program_info.key)?;
+
+    let edition_authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+        &[bump_seed],
+    ];
+
+    create_or_allocate_account_raw(
+        *program_id,
+        edition_account_info,
+        system_account_info,
+        payer_account_info,
+        MAX_MASTER_EDITION_LEN,
+        edition_authority_seeds,
+    )?;
+
+    let edition = MasterEditionV2 {
+        key: Key::MasterEditionV2,
+        supply: 0,
+        max_supply: Some(0),
+    };
+    edition.save(edition_account_info)?;
+
+    // the token standard value at the end of the account restricts legacy
+    // operations on the master edition
+    edition_account_info.data.borrow_mut()[TOKEN_STANDARD_INDEX] =
+        TokenStandard::ProgrammableFungibleAsset as u8;
+
+    transfer_mint_authority(
+        edition_account_info.key,
+        edition_account_info,
+        mint_info,
+        mint_authority_info,
+        token_program_info,
+    )
+}
@@ -1,19 +1,23 @@
 use borsh::{maybestd::io::Error as BorshError, BorshDeserialize};
 use mpl_utils::{create_or_allocate_account_raw, token::get_mint_authority};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_option::COption, pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, program_option::COption,
+    pubkey::Pubkey, sysvar::Sysvar,
 };
 
 use super::{compression::is_decompression, *};
 use crate::{
     assertions::{
-        assert_mint_authority_matches_mint, assert_owned_by,
+        assert_keys_equal, assert_mint_authority_matches_mint, assert_owned_by_token_program,
         collection::assert_collection_update_is_valid, metadata::assert_data_valid,
         uses::assert_valid_use,
     },
+    pda::find_metadata_history_account,
     state::{
-        Collection, CollectionDetails, Data, DataV2, Key, Metadata, ProgrammableConfig,
-        TokenStandard, Uses, EDITION, MAX_METADATA_LEN, METADATA_FEE_FLAG_INDEX, PREFIX,
+        Collection, CollectionDetails, Data, DataV2, Key, Metadata, MetadataHistory,
+        ProgrammableConfig, RoyaltyConfig, TokenMetadataAccount, TokenStandard, Uses, EDITION,
+        MAX_METADATA_LEN, METADATA_FEE_FLAG_INDEX, METADATA_ROYALTY_CONFIG_FLAG_INDEX, PREFIX,
+        ROYALTY_CONFIG_FLAG_SET,
     },
 };
 
@@ -83,7 +87,7 @@ pub fn process_create_metadata_accounts_logic(
             }
         },
     )?;
-    assert_owned_by(mint_info, &spl_token::ID)?;
+    assert_owned_by_token_program(mint_info)?;
 
     let metadata_seeds = &[
         PREFIX.as_bytes(),
@@ -196,6 +200,12 @@ pub fn process_create_metadata_accounts_logic(
 // It does not check `Key` type or account length and should only be used through the custom functions
 // `from_account_info` and `deserialize` implemented on the Metadata struct.
 pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
+    // The royalty config is only present when its flag is set.
+    let royalty_config_flag = matches!(
+        buf.get(METADATA_ROYALTY_CONFIG_FLAG_INDEX),
+        Some(&ROYALTY_CONFIG_FLAG_SET)
+    );
+
     // Metadata corruption shouldn't appear until after edition_nonce.
     let key: Key = BorshDeserialize::deserialize(buf)?;
     let update_authority: Pubkey = BorshDeserialize::deserialize(buf)?;
@@ -219,6 +229,10 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
     let programmable_config_res: Result<Option<ProgrammableConfig>, BorshError> =
         BorshDeserialize::deserialize(buf);
 
+    // Royalty Config
+    let royalty_config_res: Result<Option<RoyaltyConfig>, BorshError> =
+        BorshDeserialize::deserialize(buf);
+
     // We can have accidentally valid, but corrupted data, particularly on the Collection struct,
     // so to increase probability of catching errors. If any of these deserializations fail, set
     // all values to None.
@@ -235,6 +249,12 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
         Err(_) => None,
     };
 
+    // Royalty Config
+    let royalty_config = match (&programmable_config_res, royalty_config_res) {
+        (Ok(_), Ok(royalty_config)) if royalty_config_flag => royalty_config,
+        _ => None,
+    };
+
     // Programmable Config
     let programmable_config = programmable_config_res.unwrap_or(None);
 
@@ -251,6 +271,7 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
         uses,
         collection_details,
         programmable_config,
+        royalty_config,
     };
 
     Ok(metadata)
@@ -270,8 +291,36 @@ pub fn clean_write_metadata(
     Ok(())
 }
 
+/// Records the data of the metadata in its history account.
+///
+/// The history account is optional, so the record is skipped when the account is not
+/// passed or when the history is not enabled for the asset (i.e., the account has not
+/// been created).
+pub fn record_metadata_history(
+    metadata: &Metadata,
+    signer: &Pubkey,
+    history_info: Option<&AccountInfo>,
+) -> ProgramResult {
+    let history_info = match history_info {
+        Some(history_info) => history_info,
+        None => return Ok(()),
+    };
+
+    let (history_key, _) = find_metadata_history_account(&metadata.mint);
+    assert_keys_equal(&history_key, history_info.key)?;
+
+    if history_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut history = MetadataHistory::from_account_info(history_info)?;
+    history.push(&metadata.data, Clock::get()?.slot, *signer);
+    history.save(history_info)
+}
+
 #[cfg(test)]
 pub mod tests {
+    use borsh::BorshSerialize;
     use solana_program::pubkey;
 
     use super::*;
@@ -354,6 +403,7 @@ pub mod tests {
             uses: None,
             collection_details: None,
             programmable_config: None,
+            royalty_config: None,
         };
 
         puff_out_data_fields(&mut metadata);
@@ -369,4 +419,59 @@ pub mod tests {
 
         assert_eq!(metadata, expected_metadata);
     }
+
+    #[test]
+    fn deserialize_legacy_metadata_without_royalty_config_flag() {
+        let metadata = Metadata {
+            programmable_config: Some(ProgrammableConfig::V1 { rule_set: None }),
+            ..expected_pesky_metadata()
+        };
+
+        let mut data = vec![0u8; MAX_METADATA_LEN];
+        metadata.save(&mut data).unwrap();
+        // fee flag
+        data[METADATA_FEE_FLAG_INDEX] = 1;
+
+        // the royalty config (none) is the last serialized byte
+        let royalty_config_index = metadata.try_to_vec().unwrap().len() - 1;
+
+        // legacy accounts might have non-zero padding after the programmable config,
+        // which looks like a (valid) royalty config
+        data[royalty_config_index..royalty_config_index + 2].copy_from_slice(&[1, 1]);
+        assert_eq!(data[METADATA_ROYALTY_CONFIG_FLAG_INDEX], 0);
+
+        let legacy = meta_deser_unchecked(&mut data.as_slice()).unwrap();
+        assert_eq!(legacy.programmable_config, metadata.programmable_config);
+        assert_eq!(legacy.royalty_config, None);
+
+        // writing a royalty config clears the padding
+        let mut updated = legacy;
+        updated.royalty_config = Some(RoyaltyConfig::InheritFromCollection);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        clean_write_metadata(&mut updated, &account_info).unwrap();
+
+        let data = account_info.data.borrow();
+        let deserialized = meta_deser_unchecked(&mut &data[..]).unwrap();
+        assert_eq!(
+            deserialized.royalty_config,
+            Some(RoyaltyConfig::InheritFromCollection)
+        );
+        assert_eq!(
+            data[METADATA_ROYALTY_CONFIG_FLAG_INDEX],
+            ROYALTY_CONFIG_FLAG_SET
+        );
+        assert_eq!(data[METADATA_FEE_FLAG_INDEX], 1);
+    }
 }
@@ -4,6 +4,7 @@ pub(crate) mod fee;
 pub(crate) mod master_edition;
 pub(crate) mod metadata;
 pub(crate) mod programmable_asset;
+pub(crate) mod royalties;
 
 pub use collection::*;
 pub use compression::*;
@@ -18,17 +19,18 @@ pub use mpl_utils::{
     },
 };
 pub use programmable_asset::*;
+pub use royalties::*;
 use solana_program::{
     account_info::AccountInfo, borsh::try_from_slice_unchecked, entrypoint::ProgramResult,
     program::invoke_signed, program_error::ProgramError, pubkey::Pubkey, rent::Rent,
     sysvar::Sysvar,
 };
-use spl_token::instruction::{set_authority, AuthorityType};
+use spl_token_2022::instruction::{set_authority, AuthorityType};
 
 pub use crate::assertions::{
     assert_delegated_tokens, assert_derivation, assert_freeze_authority_matches_mint,
-    assert_initialized, assert_mint_authority_matches_mint, assert_owned_by, assert_rent_exempt,
-    assert_token_program_matches_package,
+    assert_initialized, assert_mint_authority_matches_mint, assert_owned_by,
+    assert_owned_by_token_program, assert_rent_exempt, assert_token_program_matches_package,
     edition::{assert_edition_is_not_mint_authority, assert_edition_valid},
     metadata::{
         assert_currently_holding, assert_data_valid, assert_update_authority_is_correct,
@@ -39,7 +41,7 @@ use crate::{
     error::MetadataError,
     state::{
         Edition, Key, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard,
-        MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+        MAX_EDITION_LEN, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
     },
 };
 
@@ -197,9 +199,10 @@ pub(crate) fn close_program_account<'a>(
     let rent = Rent::get()?;
 
     let rent_lamports = match key {
-        // Metadata accounts could have fees stored, so we only want to withdraw
-        // the actual rent lamport amount.
+        // Metadata and print edition accounts could have fees stored, so we only
+        // want to withdraw the actual rent lamport amount.
         Key::MetadataV1 => rent.minimum_balance(Metadata::size()),
+        Key::EditionV1 => rent.minimum_balance(MAX_EDITION_LEN),
         // Other accounts the rent is just the current lamport balance.
         _ => account_info.lamports(),
     };
@@ -282,6 +285,7 @@ mod tests {
             token_standard: None,
             collection_details: None,
             programmable_config: None,
+            royalty_config: None,
         };
 
         puff_out_data_fields(&mut metadata);
@@ -1,15 +1,21 @@
 use mpl_token_auth_rules::{
     instruction::{builders::ValidateBuilder, InstructionBuilder, ValidateArgs},
     payload::PayloadType,
+    state::{RuleSetV1, RuleSetV2},
+    types::{LibVersion, RuleSet},
+    utils::get_existing_revision_map,
+};
+use mpl_utils::{
+    create_or_allocate_account_raw,
+    token::{get_mint_decimals, TokenTransferParams},
 };
-use mpl_utils::{create_or_allocate_account_raw, token::TokenTransferParams};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
     program_error::ProgramError, program_option::COption, pubkey::Pubkey,
 };
-use spl_token::{
-    instruction::{freeze_account, thaw_account, AuthorityType as SplAuthorityType},
-    state::Account,
+use spl_token::state::Account;
+use spl_token_2022::instruction::{
+    freeze_account, thaw_account, AuthorityType as SplAuthorityType,
 };
 
 use crate::{
@@ -213,6 +219,22 @@ pub fn auth_rules_validate(params: AuthRulesValidateParams) -> ProgramResult {
             // in the assertion above.
             let auth_pda = auth_rules_info.unwrap();
 
+            // operations not validated by the initial release are only validated when
+            // the rule set defines them, so existing rule sets keep working as before
+            if matches!(
+                operation,
+                Operation::Update { .. }
+                    | Operation::Burn { .. }
+                    | Operation::Mint { .. }
+                    | Operation::Use { .. }
+                    | Operation::Lock { .. }
+                    | Operation::Unlock { .. }
+                    | Operation::Migrate { .. }
+            ) && !rule_set_has_operation(auth_pda, rule_set_revision, &operation)?
+            {
+                return Ok(());
+            }
+
             let mut auth_data = if let Some(auth_data) = auth_data {
                 auth_data
             } else {
@@ -281,8 +303,66 @@ pub fn auth_rules_validate(params: AuthRulesValidateParams) -> ProgramResult {
                         PayloadType::Pubkey(*destination_info.key),
                     );
                 }
-                _ => {
-                    return Err(MetadataError::InvalidOperation.into());
+                Operation::Update { scenario: _ } => {
+                    // get account infos
+                    let authority_info = authority_info.ok_or(MetadataError::InvalidOperation)?;
+
+                    // update authority (or delegate)
+                    auth_data.payload.insert(
+                        PayloadKey::Authority.to_string(),
+                        PayloadType::Pubkey(*authority_info.key),
+                    );
+                }
+                Operation::Burn { scenario: _ }
+                | Operation::Use { scenario: _ }
+                | Operation::Lock { scenario: _ }
+                | Operation::Unlock { scenario: _ }
+                | Operation::Migrate { scenario: _ } => {
+                    // get account infos
+                    let authority_info = authority_info.ok_or(MetadataError::InvalidOperation)?;
+
+                    // amount of tokens (or uses)
+                    auth_data
+                        .payload
+                        .insert(PayloadKey::Amount.to_string(), PayloadType::Number(amount));
+
+                    // authority
+                    auth_data.payload.insert(
+                        PayloadKey::Authority.to_string(),
+                        PayloadType::Pubkey(*authority_info.key),
+                    );
+
+                    // token holder, when the instruction includes the holder account
+                    if let Some(owner_info) = owner_info {
+                        auth_data.payload.insert(
+                            PayloadKey::Holder.to_string(),
+                            PayloadType::Pubkey(*owner_info.key),
+                        );
+                    }
+                }
+                Operation::Mint { scenario: _ } => {
+                    // get account infos
+                    let authority_info = authority_info.ok_or(MetadataError::InvalidOperation)?;
+
+                    // mint amount
+                    auth_data
+                        .payload
+                        .insert(PayloadKey::Amount.to_string(), PayloadType::Number(amount));
+
+                    // mint authority
+                    auth_data.payload.insert(
+                        PayloadKey::Authority.to_string(),
+                        PayloadType::Pubkey(*authority_info.key),
+                    );
+
+                    // owner of the token account receiving the tokens, when the instruction
+                    // includes the owner account
+                    if let Some(destination_info) = destination_info {
+                        auth_data.payload.insert(
+                            PayloadKey::Destination.to_string(),
+                            PayloadType::Pubkey(*destination_info.key),
+                        );
+                    }
                 }
             }
 
@@ -299,6 +379,62 @@ pub fn auth_rules_validate(params: AuthRulesValidateParams) -> ProgramResult {
     Ok(())
 }
 
+/// Checks whether the rule set revision defines rules for the operation, following the
+/// namespace fallback of the rule set (e.g., `Burn` for `Burn:Owner`).
+pub fn rule_set_has_operation(
+    rule_set_info: &AccountInfo,
+    rule_set_revision: Option<usize>,
+    operation: &Operation,
+) -> Result<bool, ProgramError> {
+    let (revision_map, rev_map_location) = get_existing_revision_map(rule_set_info)?;
+
+    // same revision lookup used by the rule set validation
+    let (start, end) = match rule_set_revision {
+        Some(revision) => {
+            let start = revision_map
+                .rule_set_revisions
+                .get(revision)
+                .ok_or(MetadataError::InvalidAuthorizationRules)?;
+            let end = revision_map
+                .rule_set_revisions
+                .get(revision + 1)
+                .unwrap_or(&rev_map_location);
+            (*start, *end)
+        }
+        None => {
+            let start = revision_map
+                .rule_set_revisions
+                .last()
+                .ok_or(MetadataError::InvalidAuthorizationRules)?;
+            (*start, rev_map_location)
+        }
+    };
+
+    let data = rule_set_info.try_borrow_data()?;
+
+    if end >= data.len() {
+        return Err(MetadataError::InvalidAuthorizationRules.into());
+    }
+
+    let lib_version = data
+        .get(start)
+        .and_then(|version| LibVersion::try_from(*version).ok())
+        .ok_or(MetadataError::InvalidAuthorizationRules)?;
+
+    let has_operation = match lib_version {
+        LibVersion::V1 => rmp_serde::from_slice::<RuleSetV1>(&data[start + 1..end])
+            .map_err(|_| MetadataError::InvalidAuthorizationRules)?
+            .get_rule(operation.to_string())
+            .is_ok(),
+        LibVersion::V2 => RuleSetV2::from_bytes(&data[start..end])
+            .map_err(|_| MetadataError::InvalidAuthorizationRules)?
+            .get_rule(operation.to_string())
+            .is_ok(),
+    };
+
+    Ok(has_operation)
+}
+
 pub fn frozen_transfer<'a>(
     params: TokenTransferParams<'a, '_>,
     edition_opt_info: Option<&'a AccountInfo<'a>>,
@@ -319,7 +455,8 @@ pub fn frozen_transfer<'a>(
     let dest_info = params.destination.clone();
     let token_program_info = params.token_program.clone();
 
-    mpl_utils::token::spl_token_transfer(params).unwrap();
+    let decimals = get_mint_decimals(&mint_info)?;
+    mpl_utils::token::spl_token_transfer_checked(params, decimals).unwrap();
 
     freeze(
         mint_info,
@@ -359,7 +496,7 @@ pub(crate) fn clear_close_authority(params: ClearCloseAuthorityParams) -> Progra
         let seeds = edition_seeds!(mint_info.key);
 
         invoke_signed(
-            &spl_token::instruction::set_authority(
+            &spl_token_2022::instruction::set_authority(
                 spl_token_program_info.key,
                 token_info.key,
                 None,
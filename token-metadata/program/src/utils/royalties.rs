@@ -0,0 +1,154 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    assertions::assert_owned_by,
+    error::MetadataError,
+    state::{Creator, Metadata, RoyaltyConfig, TokenMetadataAccount},
+};
+
+/// Effective royalty settings of an asset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Royalties {
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+}
+
+impl From<&Metadata> for Royalties {
+    fn from(metadata: &Metadata) -> Self {
+        Self {
+            seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+            creators: metadata.data.creators.clone(),
+        }
+    }
+}
+
+/// Indicates whether the asset inherits the royalty settings of its verified collection, in
+/// which case the collection metadata is required to resolve its royalties.
+pub fn inherits_royalties(metadata: &Metadata) -> bool {
+    matches!(
+        metadata.royalty_config,
+        Some(RoyaltyConfig::InheritFromCollection)
+    ) && matches!(&metadata.collection, Some(collection) if collection.verified)
+}
+
+/// Resolves the effective royalty settings of an asset.
+///
+/// Items that inherit royalties use the settings of their verified collection parent when
+/// the parent declares them as authoritative; otherwise, the settings of the asset apply.
+pub fn resolve_royalties(
+    metadata: &Metadata,
+    collection_metadata: Option<&Metadata>,
+) -> Result<Royalties, ProgramError> {
+    if !inherits_royalties(metadata) {
+        return Ok(Royalties::from(metadata));
+    }
+
+    let collection_metadata =
+        collection_metadata.ok_or(MetadataError::MissingCollectionMetadata)?;
+
+    // the collection was checked by `inherits_royalties`
+    if let Some(collection) = &metadata.collection {
+        if collection_metadata.mint != collection.key {
+            return Err(MetadataError::NotAMemberOfCollection.into());
+        }
+    }
+
+    let is_authoritative = matches!(
+        collection_metadata.royalty_config,
+        Some(RoyaltyConfig::CollectionAuthoritative)
+    ) && collection_metadata.collection_details.is_some();
+
+    if is_authoritative {
+        Ok(Royalties::from(collection_metadata))
+    } else {
+        Ok(Royalties::from(metadata))
+    }
+}
+
+/// Resolves the effective royalty settings of an asset from its metadata account and,
+/// for items that inherit royalties, the metadata account of its collection parent.
+pub fn resolve_royalties_from_accounts(
+    metadata_info: &AccountInfo,
+    collection_metadata_info: Option<&AccountInfo>,
+) -> Result<Royalties, ProgramError> {
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    let collection_metadata = match collection_metadata_info {
+        Some(collection_metadata_info) if inherits_royalties(&metadata) => {
+            assert_owned_by(collection_metadata_info, &crate::ID)?;
+            Some(Metadata::from_account_info(collection_metadata_info)?)
+        }
+        _ => None,
+    };
+
+    resolve_royalties(&metadata, collection_metadata.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{Collection, CollectionDetails, Data};
+
+    fn metadata(seller_fee_basis_points: u16, royalty_config: Option<RoyaltyConfig>) -> Metadata {
+        Metadata {
+            mint: Pubkey::new_unique(),
+            data: Data {
+                seller_fee_basis_points,
+                creators: Some(vec![Creator {
+                    address: Pubkey::new_unique(),
+                    verified: true,
+                    share: 100,
+                }]),
+                ..Default::default()
+            },
+            royalty_config,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn item_inherits_authoritative_collection_royalties() {
+        let mut parent = metadata(500, Some(RoyaltyConfig::CollectionAuthoritative));
+        parent.collection_details = Some(CollectionDetails::V1 { size: 1 });
+
+        let mut item = metadata(0, Some(RoyaltyConfig::InheritFromCollection));
+        item.collection = Some(Collection {
+            verified: true,
+            key: parent.mint,
+        });
+
+        assert_eq!(
+            resolve_royalties(&item, Some(&parent)).unwrap(),
+            Royalties::from(&parent)
+        );
+
+        // the collection metadata is required
+        assert!(resolve_royalties(&item, None).is_err());
+
+        // without the collection declaring its royalties, the item royalties apply
+        parent.royalty_config = None;
+        assert_eq!(
+            resolve_royalties(&item, Some(&parent)).unwrap(),
+            Royalties::from(&item)
+        );
+    }
+
+    #[test]
+    fn unverified_item_uses_own_royalties() {
+        let mut parent = metadata(500, Some(RoyaltyConfig::CollectionAuthoritative));
+        parent.collection_details = Some(CollectionDetails::V1 { size: 0 });
+
+        let mut item = metadata(250, Some(RoyaltyConfig::InheritFromCollection));
+        item.collection = Some(Collection {
+            verified: false,
+            key: parent.mint,
+        });
+
+        assert_eq!(
+            resolve_royalties(&item, Some(&parent)).unwrap(),
+            Royalties::from(&item)
+        );
+    }
+}
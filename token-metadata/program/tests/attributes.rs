@@ -0,0 +1,395 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{
+            CloseAttributesBuilder, CreateAttributesBuilder, RemoveAttributeBuilder,
+            UpdateAttributesBuilder,
+        },
+        CreateAttributesArgs, DelegateArgs, InstructionBuilder, RemoveAttributeArgs,
+        UpdateAttributesArgs,
+    },
+    pda::find_attributes_account,
+    state::{Attribute, Attributes, Key, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod attributes {
+    use super::*;
+
+    fn attribute(key: &str, value: &str) -> Attribute {
+        Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    async fn create_attributes(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        authority: &Keypair,
+        delegate_record: Option<Pubkey>,
+        args: CreateAttributesArgs,
+    ) -> Result<Pubkey, BanksClientError> {
+        let (attributes, _) = find_attributes_account(&asset.mint.pubkey());
+
+        let mut builder = CreateAttributesBuilder::new();
+        builder
+            .attributes(attributes)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .authority(authority.pubkey())
+            .payer(authority.pubkey());
+
+        if let Some(delegate_record) = delegate_record {
+            builder.delegate_record(delegate_record);
+        }
+
+        let create_ix = builder.build(args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[create_ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map(|_| attributes)
+    }
+
+    async fn update_attributes(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        authority: &Keypair,
+        delegate_record: Option<Pubkey>,
+        args: UpdateAttributesArgs,
+    ) -> Result<(), BanksClientError> {
+        let (attributes, _) = find_attributes_account(&asset.mint.pubkey());
+
+        let mut builder = UpdateAttributesBuilder::new();
+        builder
+            .attributes(attributes)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .authority(authority.pubkey())
+            .payer(authority.pubkey());
+
+        if let Some(delegate_record) = delegate_record {
+            builder.delegate_record(delegate_record);
+        }
+
+        let update_ix = builder.build(args).unwrap().instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn get_attributes(context: &mut ProgramTestContext, attributes: &Pubkey) -> Attributes {
+        let account = get_account(context, attributes).await;
+        Attributes::from_bytes(&account.data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn success_manage_attributes_by_update_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        // creates the attributes account
+
+        let attributes = create_attributes(
+            context,
+            &asset,
+            &update_authority,
+            None,
+            CreateAttributesArgs::V1 {
+                attributes: vec![attribute("class", "wizard"), attribute("level", "1")],
+                frozen_keys: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+        let pda = get_attributes(context, &attributes).await;
+        assert_eq!(pda.key, Key::AttributesV1);
+        assert_eq!(pda.mint, asset.mint.pubkey());
+        assert_eq!(pda.get("class"), Some("wizard"));
+        assert_eq!(pda.get("level"), Some("1"));
+
+        // updates an attribute and adds a new one (the account grows)
+
+        update_attributes(
+            context,
+            &asset,
+            &update_authority,
+            None,
+            UpdateAttributesArgs::V1 {
+                attributes: vec![attribute("level", "2"), attribute("weapon", "staff")],
+                freeze_keys: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+        let account = get_account(context, &attributes).await;
+        let pda = Attributes::from_bytes(&account.data).unwrap();
+        assert_eq!(account.data.len(), pda.data_len());
+        assert_eq!(pda.get("level"), Some("2"));
+        assert_eq!(pda.get("weapon"), Some("staff"));
+
+        // removes an attribute (the account shrinks)
+
+        let mut builder = RemoveAttributeBuilder::new();
+        builder
+            .attributes(attributes)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .authority(update_authority.pubkey())
+            .payer(update_authority.pubkey());
+
+        let remove_ix = builder
+            .build(RemoveAttributeArgs::V1 {
+                key: "weapon".to_string(),
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[remove_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let account = get_account(context, &attributes).await;
+        let pda = Attributes::from_bytes(&account.data).unwrap();
+        assert_eq!(account.data.len(), pda.data_len());
+        assert_eq!(pda.get("weapon"), None);
+
+        // closes the attributes account
+
+        let close_ix = CloseAttributesBuilder::new()
+            .attributes(attributes)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .authority(update_authority.pubkey())
+            .payer(update_authority.pubkey())
+            .build()
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[close_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let account = context.banks_client.get_account(attributes).await.unwrap();
+        assert!(account.is_none());
+    }
+
+    #[tokio::test]
+    async fn success_update_attributes_by_attributes_delegate() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create(context, TokenStandard::ProgrammableNonFungible, None)
+            .await
+            .unwrap();
+
+        let delegate = Keypair::new();
+        delegate.airdrop(context, 1_000_000_000).await.unwrap();
+
+        let delegate_record = asset
+            .delegate(
+                context,
+                update_authority,
+                delegate.pubkey(),
+                DelegateArgs::AttributesV1 {
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        let attributes = create_attributes(
+            context,
+            &asset,
+            &delegate,
+            Some(delegate_record),
+            CreateAttributesArgs::V1 {
+                attributes: vec![attribute("level", "1")],
+                frozen_keys: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+        update_attributes(
+            context,
+            &asset,
+            &delegate,
+            Some(delegate_record),
+            UpdateAttributesArgs::V1 {
+                attributes: vec![attribute("level", "2")],
+                freeze_keys: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+        let pda = get_attributes(context, &attributes).await;
+        assert_eq!(pda.get("level"), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn fail_update_attributes_by_non_delegate() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        create_attributes(
+            context,
+            &asset,
+            &update_authority,
+            None,
+            CreateAttributesArgs::V1 {
+                attributes: vec![attribute("level", "1")],
+                frozen_keys: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+        let fake_authority = Keypair::new();
+        fake_authority
+            .airdrop(context, 1_000_000_000)
+            .await
+            .unwrap();
+
+        let err = update_attributes(
+            context,
+            &asset,
+            &fake_authority,
+            None,
+            UpdateAttributesArgs::V1 {
+                attributes: vec![attribute("level", "99")],
+                freeze_keys: vec![],
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidAuthorityType);
+    }
+
+    #[tokio::test]
+    async fn fail_update_frozen_attribute() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let attributes = create_attributes(
+            context,
+            &asset,
+            &update_authority,
+            None,
+            CreateAttributesArgs::V1 {
+                attributes: vec![attribute("class", "wizard"), attribute("level", "1")],
+                frozen_keys: vec!["class".to_string()],
+            },
+        )
+        .await
+        .unwrap();
+
+        let err = update_attributes(
+            context,
+            &asset,
+            &update_authority,
+            None,
+            UpdateAttributesArgs::V1 {
+                attributes: vec![attribute("class", "warrior")],
+                freeze_keys: vec![],
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::AttributeFrozen);
+
+        // the account cannot be closed while it has frozen attributes
+
+        let close_ix = CloseAttributesBuilder::new()
+            .attributes(attributes)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .authority(update_authority.pubkey())
+            .payer(update_authority.pubkey())
+            .build()
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[close_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::AttributeFrozen);
+
+        let pda = get_attributes(context, &attributes).await;
+        assert_eq!(pda.get("class"), Some("wizard"));
+        assert!(pda.is_frozen("class"));
+    }
+}
@@ -2740,6 +2740,70 @@ mod fungible {
         assert!(token_account.is_none());
     }
 
+    #[tokio::test]
+    async fn owner_burn_programmable_fungible_asset() {
+        let mut context = program_test().start_with_context().await;
+
+        let owner = context.payer.dirty_clone();
+
+        let initial_amount = 10;
+        let burn_amount = 4;
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(
+            &mut context,
+            TokenStandard::ProgrammableFungibleAsset,
+            None,
+            None,
+            initial_amount,
+        )
+        .await
+        .unwrap();
+
+        let args = BurnArgs::V1 {
+            amount: burn_amount,
+        };
+
+        da.burn(&mut context, owner.dirty_clone(), args, None, None)
+            .await
+            .unwrap();
+
+        // We only burned some tokens, so the token account should still exist
+        // and be frozen.
+        let token_account = context
+            .banks_client
+            .get_account(da.token.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let token = TokenAccount::unpack(&token_account.data).unwrap();
+
+        assert_eq!(token.amount, initial_amount - burn_amount);
+        assert!(token.is_frozen());
+
+        let args = BurnArgs::V1 {
+            amount: initial_amount - burn_amount,
+        };
+
+        da.burn(&mut context, owner, args, None, None)
+            .await
+            .unwrap();
+
+        // The token account and token record should be closed now.
+        let token_account = context
+            .banks_client
+            .get_account(da.token.unwrap())
+            .await
+            .unwrap();
+
+        assert!(token_account.is_none());
+
+        da.assert_token_record_closed(&mut context, &da.token.unwrap())
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn only_owner_can_burn() {
         let mut context = program_test().start_with_context().await;
@@ -13,8 +13,8 @@ mod burn_nft {
         error::MetadataError,
         state::{Collection, CollectionDetails},
     };
-    use solana_program::pubkey::Pubkey;
-    use solana_sdk::signature::Keypair;
+    use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+    use solana_sdk::{signature::Keypair, transaction::Transaction};
 
     use super::*;
     #[tokio::test]
@@ -451,6 +451,159 @@ mod burn_nft {
         }
     }
 
+    #[tokio::test]
+    async fn burning_propagates_size_to_parent_collections() {
+        let mut context = program_test().start_with_context().await;
+        let payer = context.payer.dirty_clone();
+
+        // Create the top-level sized collection.
+        let season_nft = Metadata::new();
+        season_nft
+            .create_v3(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        let season_master_edition = MasterEditionV2::new(&season_nft);
+        season_master_edition
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        // Create a sized collection nested in the top-level collection.
+        let drop_nft = Metadata::new();
+        drop_nft
+            .create_v3(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                Some(Collection {
+                    key: season_nft.mint.pubkey(),
+                    verified: false,
+                }),
+                None,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        let drop_master_edition = MasterEditionV2::new(&drop_nft);
+        drop_master_edition
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        drop_nft
+            .verify_sized_collection_item(
+                &mut context,
+                season_nft.pubkey,
+                &payer,
+                season_nft.mint.pubkey(),
+                season_master_edition.pubkey,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_collection_size(&mut context, &season_nft, 1).await;
+
+        // Create an item in the nested collection.
+        let item_nft = Metadata::new();
+        item_nft
+            .create_v3(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                Some(Collection {
+                    key: drop_nft.mint.pubkey(),
+                    verified: false,
+                }),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let item_master_edition = MasterEditionV2::new(&item_nft);
+        item_master_edition
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        // Verify the item, passing the top-level collection as parent collection
+        // (the program id is set for the unused collection authority record).
+        let mut verify_ix = instruction::verify_sized_collection_item(
+            mpl_token_metadata::ID,
+            item_nft.pubkey,
+            payer.pubkey(),
+            payer.pubkey(),
+            drop_nft.mint.pubkey(),
+            drop_nft.pubkey,
+            drop_master_edition.pubkey,
+            None,
+        );
+        verify_ix.accounts.extend([
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new(season_nft.pubkey, false),
+        ]);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        assert_collection_size(&mut context, &drop_nft, 1).await;
+        assert_collection_size(&mut context, &season_nft, 2).await;
+
+        // Burn the item, passing the top-level collection as parent collection.
+        let mut burn_ix = instruction::burn_nft(
+            mpl_token_metadata::ID,
+            item_nft.pubkey,
+            payer.pubkey(),
+            item_nft.mint.pubkey(),
+            item_nft.token.pubkey(),
+            item_master_edition.pubkey,
+            spl_token::ID,
+            Some(drop_nft.pubkey),
+        );
+        burn_ix
+            .accounts
+            .push(AccountMeta::new(season_nft.pubkey, false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // Both collections no longer account for the burned item.
+        assert_collection_size(&mut context, &drop_nft, 0).await;
+        assert_collection_size(&mut context, &season_nft, 1).await;
+    }
+
     #[tokio::test]
     async fn burn_unsized_collection_item() {
         let mut context = program_test().start_with_context().await;
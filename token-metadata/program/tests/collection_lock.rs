@@ -0,0 +1,294 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{LockBuilder, UnlockBuilder},
+        DelegateArgs, InstructionBuilder, LockArgs, MetadataDelegateRole, UnlockArgs,
+        VerificationArgs,
+    },
+    pda::find_metadata_delegate_record_account,
+    state::{AuthorityType, Collection, TokenRecord, TokenStandard, TokenState},
+};
+use num_traits::FromPrimitive;
+use solana_program::{
+    borsh::try_from_slice_unchecked, instruction::AccountMeta, native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod collection_lock {
+    use super::*;
+
+    /// Creates a collection with a `CollectionLock` delegate and an item of the
+    /// collection, verified when `verify` is set.
+    async fn create_item_with_lock_delegate(
+        context: &mut ProgramTestContext,
+        verify: bool,
+    ) -> (DigitalAsset, DigitalAsset, Keypair) {
+        let mut collection_parent_da = DigitalAsset::new();
+        collection_parent_da
+            .create_and_mint_collection_parent(
+                context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint_item_with_collection(
+            context,
+            TokenStandard::ProgrammableNonFungible,
+            None,
+            None,
+            1,
+            Some(Collection {
+                key: collection_parent_da.mint.pubkey(),
+                verified: false,
+            }),
+        )
+        .await
+        .unwrap();
+
+        if verify {
+            let payer = context.payer.dirty_clone();
+            da.verify(
+                context,
+                payer,
+                VerificationArgs::CollectionV1,
+                None,
+                None,
+                Some(collection_parent_da.mint.pubkey()),
+                Some(collection_parent_da.metadata),
+                Some(collection_parent_da.edition.unwrap()),
+            )
+            .await
+            .unwrap();
+        }
+
+        let delegate = Keypair::new();
+        airdrop(context, &delegate.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let payer = context.payer.dirty_clone();
+        collection_parent_da
+            .delegate(
+                context,
+                payer,
+                delegate.pubkey(),
+                DelegateArgs::CollectionLockV1 {
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        (collection_parent_da, da, delegate)
+    }
+
+    /// Locks (or unlocks) the item as a `CollectionLock` delegate, passing the delegate
+    /// record as a remaining account.
+    async fn toggle_as_collection_delegate(
+        context: &mut ProgramTestContext,
+        collection_parent_da: &DigitalAsset,
+        da: &DigitalAsset,
+        delegate: &Keypair,
+        lock: bool,
+    ) -> Result<(), BanksClientError> {
+        let (delegate_record, _) = find_metadata_delegate_record_account(
+            &collection_parent_da.mint.pubkey(),
+            MetadataDelegateRole::CollectionLock,
+            &context.payer.pubkey(),
+            &delegate.pubkey(),
+        );
+
+        let mut toggle_ix = if lock {
+            LockBuilder::new()
+                .authority(delegate.pubkey())
+                .token(da.token.unwrap())
+                .mint(da.mint.pubkey())
+                .metadata(da.metadata)
+                .edition(da.edition.unwrap())
+                .token_record(da.token_record.unwrap())
+                .payer(delegate.pubkey())
+                .spl_token_program(spl_token::ID)
+                .build(LockArgs::V1 {
+                    authorization_data: None,
+                })
+                .unwrap()
+                .instruction()
+        } else {
+            UnlockBuilder::new()
+                .authority(delegate.pubkey())
+                .token(da.token.unwrap())
+                .mint(da.mint.pubkey())
+                .metadata(da.metadata)
+                .edition(da.edition.unwrap())
+                .token_record(da.token_record.unwrap())
+                .payer(delegate.pubkey())
+                .spl_token_program(spl_token::ID)
+                .build(UnlockArgs::V1 {
+                    authorization_data: None,
+                })
+                .unwrap()
+                .instruction()
+        };
+        toggle_ix
+            .accounts
+            .push(AccountMeta::new(delegate_record, false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[toggle_ix],
+            Some(&delegate.pubkey()),
+            &[delegate],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn get_token_state(
+        context: &mut ProgramTestContext,
+        token_record: &Pubkey,
+    ) -> TokenState {
+        let account = get_account(context, token_record).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&account.data).unwrap();
+        token_record.state
+    }
+
+    #[tokio::test]
+    async fn collection_delegate_locks_and_unlocks() {
+        let mut context = program_test().start_with_context().await;
+        let (collection_parent_da, da, delegate) =
+            create_item_with_lock_delegate(&mut context, true).await;
+
+        toggle_as_collection_delegate(&mut context, &collection_parent_da, &da, &delegate, true)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_token_state(&mut context, &da.token_record.unwrap()).await,
+            TokenState::Locked
+        );
+
+        toggle_as_collection_delegate(&mut context, &collection_parent_da, &da, &delegate, false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_token_state(&mut context, &da.token_record.unwrap()).await,
+            TokenState::Unlocked
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_lock_unverified_item() {
+        let mut context = program_test().start_with_context().await;
+        let (collection_parent_da, da, delegate) =
+            create_item_with_lock_delegate(&mut context, false).await;
+
+        let error = toggle_as_collection_delegate(
+            &mut context,
+            &collection_parent_da,
+            &da,
+            &delegate,
+            true,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidAuthorityType);
+    }
+
+    #[tokio::test]
+    async fn fail_lock_with_other_delegate() {
+        let mut context = program_test().start_with_context().await;
+        let (collection_parent_da, da, _) =
+            create_item_with_lock_delegate(&mut context, true).await;
+
+        let other = Keypair::new();
+        airdrop(&mut context, &other.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let error =
+            toggle_as_collection_delegate(&mut context, &collection_parent_da, &da, &other, true)
+                .await
+                .unwrap_err();
+
+        // there is no delegate record for the other authority
+        assert_custom_error!(error, MetadataError::IncorrectOwner);
+    }
+
+    #[tokio::test]
+    async fn fail_unlock_with_token_delegate() {
+        let mut context = program_test().start_with_context().await;
+        let (collection_parent_da, mut da, delegate) =
+            create_item_with_lock_delegate(&mut context, true).await;
+
+        let token_delegate = Keypair::new();
+        airdrop(&mut context, &token_delegate.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let payer = context.payer.dirty_clone();
+        da.delegate(
+            &mut context,
+            payer,
+            token_delegate.pubkey(),
+            DelegateArgs::UtilityV1 {
+                amount: 1,
+                authorization_data: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        toggle_as_collection_delegate(&mut context, &collection_parent_da, &da, &delegate, true)
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &da.token_record.unwrap()).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(
+            token_record.locked_by,
+            Some(AuthorityType::MetadataDelegate)
+        );
+
+        // the token delegate cannot unlock a token locked by the collection delegate
+
+        let payer = context.payer.dirty_clone();
+        let error = da
+            .unlock(
+                &mut context,
+                token_delegate.dirty_clone(),
+                da.token_record,
+                payer,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidAuthorityType);
+
+        toggle_as_collection_delegate(&mut context, &collection_parent_da, &da, &delegate, false)
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &da.token_record.unwrap()).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(token_record.state, TokenState::Unlocked);
+        assert_eq!(token_record.locked_by, None);
+    }
+}
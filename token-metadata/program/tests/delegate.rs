@@ -17,7 +17,8 @@ mod delegate {
         instruction::{DelegateArgs, MetadataDelegateRole},
         pda::{find_metadata_delegate_record_account, find_token_record_account},
         state::{
-            Key, Metadata, MetadataDelegateRecord, TokenDelegateRole, TokenRecord, TokenStandard,
+            Key, Metadata, MetadataDelegateRecord, Resizable, TokenDelegateRole, TokenRecord,
+            TokenStandard,
         },
     };
     use num_traits::FromPrimitive;
@@ -0,0 +1,249 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        self, builders::SetEditionNumberingBuilder, InstructionBuilder, SetEditionNumberingArgs,
+    },
+    state::{EditionNumberingMode, ReservedRange},
+    ID,
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod edition_numbering {
+    use super::*;
+
+    async fn create_master_edition(
+        context: &mut ProgramTestContext,
+    ) -> (Metadata, MasterEditionV2) {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v3(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let test_master_edition = MasterEditionV2::new(&test_metadata);
+        test_master_edition
+            .create_v3(context, Some(10))
+            .await
+            .unwrap();
+
+        (test_metadata, test_master_edition)
+    }
+
+    async fn set_numbering(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_master_edition: &MasterEditionV2,
+        authority: &Keypair,
+        mode: EditionNumberingMode,
+        reserved_ranges: Vec<ReservedRange>,
+    ) -> Result<(), BanksClientError> {
+        let set_ix = SetEditionNumberingBuilder::new()
+            .master_edition(test_master_edition.pubkey)
+            .metadata(test_metadata.pubkey)
+            .authority(authority.pubkey())
+            .build(SetEditionNumberingArgs::V1 {
+                mode,
+                reserved_ranges,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Prints an edition with the update authority of the master edition signing
+    /// the transaction.
+    async fn print_as_update_authority(
+        context: &mut ProgramTestContext,
+        test_edition_marker: &EditionMarker,
+    ) -> Result<(), BanksClientError> {
+        create_mint(
+            context,
+            &test_edition_marker.mint,
+            &context.payer.pubkey(),
+            Some(&context.payer.pubkey()),
+            0,
+        )
+        .await?;
+        create_token_account(
+            context,
+            &test_edition_marker.token,
+            &test_edition_marker.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await?;
+        mint_tokens(
+            context,
+            &test_edition_marker.mint.pubkey(),
+            &test_edition_marker.token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+
+        let mut print_ix = instruction::mint_new_edition_from_master_edition_via_token(
+            ID,
+            test_edition_marker.new_metadata_pubkey,
+            test_edition_marker.new_edition_pubkey,
+            test_edition_marker.master_edition_pubkey,
+            test_edition_marker.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            test_edition_marker.metadata_token_pubkey,
+            context.payer.pubkey(),
+            test_edition_marker.metadata_pubkey,
+            test_edition_marker.metadata_mint_pubkey,
+            test_edition_marker.edition,
+        );
+        // update authority of the new metadata
+        print_ix.accounts[9].is_signer = true;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[print_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn reserved_edition_requires_update_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = create_master_edition(&mut context).await;
+        let payer = context.payer.dirty_clone();
+
+        set_numbering(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &payer,
+            EditionNumberingMode::Custom,
+            vec![ReservedRange { start: 1, end: 2 }],
+        )
+        .await
+        .unwrap();
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        let error = test_edition_marker.create(&mut context).await.unwrap_err();
+
+        assert_custom_error!(error, MetadataError::EditionNumberReserved);
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        print_as_update_authority(&mut context, &test_edition_marker)
+            .await
+            .unwrap();
+
+        let edition_marker = test_edition_marker.get_data(&mut context).await;
+        assert!(edition_marker.edition_taken(1).unwrap());
+
+        // numbers outside of the reserved ranges are still free to choose
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 7);
+        test_edition_marker.create(&mut context).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn sequential_editions_skip_reserved_ranges() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = create_master_edition(&mut context).await;
+        let payer = context.payer.dirty_clone();
+
+        set_numbering(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &payer,
+            EditionNumberingMode::Sequential,
+            vec![ReservedRange { start: 2, end: 3 }],
+        )
+        .await
+        .unwrap();
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 4);
+        let error = test_edition_marker.create(&mut context).await.unwrap_err();
+
+        assert_custom_error!(error, MetadataError::EditionNumberOutOfSequence);
+
+        for edition in [1, 4] {
+            let test_edition_marker =
+                EditionMarker::new(&test_metadata, &test_master_edition, edition);
+            test_edition_marker.create(&mut context).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_reserved_range_beyond_max_supply() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = create_master_edition(&mut context).await;
+        let payer = context.payer.dirty_clone();
+
+        let error = set_numbering(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &payer,
+            EditionNumberingMode::Custom,
+            vec![ReservedRange { start: 5, end: 11 }],
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidEditionNumbering);
+    }
+
+    #[tokio::test]
+    async fn fail_set_numbering_without_update_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = create_master_edition(&mut context).await;
+
+        let authority = Keypair::new();
+        authority
+            .airdrop(&mut context, 1_000_000_000)
+            .await
+            .unwrap();
+
+        let error = set_numbering(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            &authority,
+            EditionNumberingMode::Sequential,
+            vec![],
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
@@ -0,0 +1,279 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        self,
+        builders::{SetEditionUriBuilder, SetEditionUriTemplateBuilder},
+        InstructionBuilder, SetEditionUriArgs, SetEditionUriTemplateArgs,
+    },
+    pda::find_edition_uri_template_account,
+    ID,
+};
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod edition_uri {
+    use super::*;
+
+    async fn create_master_edition(
+        context: &mut ProgramTestContext,
+    ) -> (Metadata, MasterEditionV2) {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v3(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let test_master_edition = MasterEditionV2::new(&test_metadata);
+        test_master_edition
+            .create_v3(context, Some(10))
+            .await
+            .unwrap();
+
+        (test_metadata, test_master_edition)
+    }
+
+    async fn set_template(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_master_edition: &MasterEditionV2,
+        uri_template: &str,
+    ) -> Result<(), BanksClientError> {
+        let (edition_uri_template, _) =
+            find_edition_uri_template_account(&test_metadata.mint.pubkey());
+
+        let set_ix = SetEditionUriTemplateBuilder::new()
+            .edition_uri_template(edition_uri_template)
+            .master_edition(test_master_edition.pubkey)
+            .metadata(test_metadata.pubkey)
+            .authority(context.payer.pubkey())
+            .payer(context.payer.pubkey())
+            .build(SetEditionUriTemplateArgs::V1 {
+                uri_template: uri_template.to_string(),
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Prints an edition passing the edition URI template of the master edition.
+    async fn print_with_template(
+        context: &mut ProgramTestContext,
+        test_edition_marker: &EditionMarker,
+    ) -> Result<(), BanksClientError> {
+        create_mint(
+            context,
+            &test_edition_marker.mint,
+            &context.payer.pubkey(),
+            Some(&context.payer.pubkey()),
+            0,
+        )
+        .await?;
+        create_token_account(
+            context,
+            &test_edition_marker.token,
+            &test_edition_marker.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await?;
+        mint_tokens(
+            context,
+            &test_edition_marker.mint.pubkey(),
+            &test_edition_marker.token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+
+        let print_ix = instruction::mint_new_edition_from_master_edition_via_token(
+            ID,
+            test_edition_marker.new_metadata_pubkey,
+            test_edition_marker.new_edition_pubkey,
+            test_edition_marker.master_edition_pubkey,
+            test_edition_marker.mint.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            test_edition_marker.metadata_token_pubkey,
+            context.payer.pubkey(),
+            test_edition_marker.metadata_pubkey,
+            test_edition_marker.metadata_mint_pubkey,
+            test_edition_marker.edition,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[print_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn set_edition_uri(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        test_edition_marker: &EditionMarker,
+        authority: &Keypair,
+        uri: &str,
+    ) -> Result<(), BanksClientError> {
+        let set_ix = SetEditionUriBuilder::new()
+            .metadata(test_edition_marker.new_metadata_pubkey)
+            .edition(test_edition_marker.new_edition_pubkey)
+            .master_edition(test_edition_marker.master_edition_pubkey)
+            .master_metadata(test_metadata.pubkey)
+            .authority(authority.pubkey())
+            .build(SetEditionUriArgs::V1 {
+                uri: uri.to_string(),
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn get_edition_uri(
+        context: &mut ProgramTestContext,
+        test_edition_marker: &EditionMarker,
+    ) -> String {
+        let account = get_account(context, &test_edition_marker.new_metadata_pubkey).await;
+        let metadata: mpl_token_metadata::state::Metadata =
+            try_from_slice_unchecked(&account.data).unwrap();
+
+        metadata
+            .data
+            .uri
+            .trim_end_matches(char::from(0))
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn print_edition_with_uri_template() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = create_master_edition(&mut context).await;
+
+        set_template(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            "https://example.com/{edition}.json",
+        )
+        .await
+        .unwrap();
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 7);
+        print_with_template(&mut context, &test_edition_marker)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_edition_uri(&mut context, &test_edition_marker).await,
+            "https://example.com/7.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_set_template_without_placeholder() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = create_master_edition(&mut context).await;
+
+        let error = set_template(
+            &mut context,
+            &test_metadata,
+            &test_master_edition,
+            "https://example.com/1.json",
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidEditionUriTemplate);
+    }
+
+    #[tokio::test]
+    async fn master_update_authority_sets_edition_uri() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = create_master_edition(&mut context).await;
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        test_edition_marker.create(&mut context).await.unwrap();
+
+        let authority = context.payer.dirty_clone();
+        set_edition_uri(
+            &mut context,
+            &test_metadata,
+            &test_edition_marker,
+            &authority,
+            "https://example.com/certificate/1.json",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            get_edition_uri(&mut context, &test_edition_marker).await,
+            "https://example.com/certificate/1.json"
+        );
+
+        // the master edition is not changed
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.data.uri.trim_end_matches(char::from(0)), "uri");
+    }
+
+    #[tokio::test]
+    async fn fail_set_edition_uri_with_wrong_authority() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = create_master_edition(&mut context).await;
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        test_edition_marker.create(&mut context).await.unwrap();
+
+        let fake_authority = Keypair::new();
+        let error = set_edition_uri(
+            &mut context,
+            &test_metadata,
+            &test_edition_marker,
+            &fake_authority,
+            "https://example.com/certificate/1.json",
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UpdateAuthorityIncorrect);
+    }
+}
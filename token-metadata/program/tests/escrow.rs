@@ -1,14 +1,31 @@
 #![cfg(feature = "test-bpf")]
 pub mod utils;
 
+use num_traits::FromPrimitive;
 use solana_program::borsh::try_from_slice_unchecked;
 use solana_program_test::*;
-use solana_sdk::{signer::Signer, transaction::Transaction};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
 use utils::*;
 
 mod escrow {
-    use mpl_token_metadata::{escrow::find_escrow_account, state::EscrowAuthority};
+    use mpl_token_metadata::{
+        error::MetadataError,
+        escrow::find_escrow_account,
+        instruction::{
+            builders::{EscrowTransferInBuilder, EscrowTransferOutBuilder},
+            sweep_escrow, EscrowHolding, InstructionBuilder, TransferArgs,
+        },
+        pda::find_token_record_account,
+        state::{EscrowAuthority, TokenStandard},
+    };
     use solana_program::program_pack::Pack;
+    use spl_associated_token_account::get_associated_token_address;
+    use spl_token::state::AccountState;
 
     use super::*;
 
@@ -414,4 +431,172 @@ mod escrow {
         assert!(attribute_dst.owner == context.payer.pubkey());
         println!("Payer Post-Transfer: {:#?}", attribute_dst);
     }
+
+    #[tokio::test]
+    async fn programmable_transfer_in_and_sweep() {
+        let mut context = program_test().start_with_context().await;
+
+        // base asset with a token owned escrow
+
+        let mut base = DigitalAsset::new();
+        base.create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let (escrow, _) = find_escrow_account(&base.mint.pubkey(), &EscrowAuthority::TokenOwner);
+
+        let create_ix = mpl_token_metadata::escrow::create_escrow_account(
+            mpl_token_metadata::ID,
+            escrow,
+            base.metadata,
+            base.mint.pubkey(),
+            base.token.unwrap(),
+            base.edition.unwrap(),
+            context.payer.pubkey(),
+            None,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[create_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // transfers a pNFT into the escrow
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let mint = asset.mint.pubkey();
+        let token = asset.token.unwrap();
+        let escrow_token = get_associated_token_address(&escrow, &mint);
+
+        let transfer_in_ix = EscrowTransferInBuilder::new()
+            .escrow(escrow)
+            .escrow_mint(base.mint.pubkey())
+            .token(token)
+            .token_owner(context.payer.pubkey())
+            .destination(escrow_token)
+            .mint(mint)
+            .metadata(asset.metadata)
+            .edition(asset.edition.unwrap())
+            .owner_token_record(find_token_record_account(&mint, &token).0)
+            .destination_token_record(find_token_record_account(&mint, &escrow_token).0)
+            .authority(context.payer.pubkey())
+            .payer(context.payer.pubkey())
+            .build(TransferArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[transfer_in_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let account = get_account(&mut context, &escrow_token).await;
+        let escrow_holding = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(escrow_holding.amount, 1);
+        assert_eq!(escrow_holding.owner, escrow);
+        assert_eq!(escrow_holding.state, AccountState::Frozen);
+
+        // only the escrow authority can transfer the holding out
+
+        let holdings = vec![EscrowHolding {
+            token: escrow_token,
+            mint,
+            amount: escrow_holding.amount,
+            edition: asset.edition,
+            programmable: true,
+            authorization_rules: None,
+        }];
+
+        let not_authority = Keypair::new();
+        not_authority
+            .airdrop(&mut context, 1_000_000_000)
+            .await
+            .unwrap();
+
+        let transfer_out_ix = EscrowTransferOutBuilder::new()
+            .escrow(escrow)
+            .escrow_mint(base.mint.pubkey())
+            .escrow_token(base.token.unwrap())
+            .token(escrow_token)
+            .destination(get_associated_token_address(&not_authority.pubkey(), &mint))
+            .destination_owner(not_authority.pubkey())
+            .mint(mint)
+            .metadata(asset.metadata)
+            .edition(asset.edition.unwrap())
+            .owner_token_record(find_token_record_account(&mint, &escrow_token).0)
+            .authority(not_authority.pubkey())
+            .payer(not_authority.pubkey())
+            .build(TransferArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[transfer_out_ix],
+            Some(&not_authority.pubkey()),
+            &[&not_authority],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::MustBeEscrowAuthority);
+
+        // sweeps the holdings back to the holder of the base asset
+
+        let sweep_ixs = sweep_escrow(
+            escrow,
+            base.mint.pubkey(),
+            base.token.unwrap(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            &holdings,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &sweep_ixs,
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let account = get_account(&mut context, &token).await;
+        let holder_token = spl_token::state::Account::unpack(&account.data).unwrap();
+        assert_eq!(holder_token.amount, 1);
+        assert_eq!(holder_token.state, AccountState::Frozen);
+
+        // the emptied escrow token account is closed
+        assert!(context
+            .banks_client
+            .get_account(escrow_token)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
@@ -7,7 +7,10 @@ use utils::*;
 mod fees {
     use mpl_token_metadata::{
         instruction::{collect_fees, BurnArgs, UpdateArgs},
-        state::{CREATE_FEE, FEE_FLAG_CLEARED, METADATA_FEE_FLAG_INDEX},
+        state::{
+            find_fee_accounts, total_collectible_fees, Key, CREATE_FEE, EDITION_FEE_FLAG_INDEX,
+            FEE_FLAG_CLEARED, FEE_FLAG_SET, MAX_EDITION_LEN, METADATA_FEE_FLAG_INDEX,
+        },
     };
     use solana_program::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
     use solana_sdk::{
@@ -46,6 +49,28 @@ mod fees {
         nft.assert_create_fees_charged(&mut context).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn charge_print_edition() {
+        let mut context = program_test().start_with_context().await;
+
+        let md = Metadata::new();
+        md.create_v3_default(&mut context).await.unwrap();
+
+        let me = MasterEditionV2::new(&md);
+        me.create_v3(&mut context, Some(10)).await.unwrap();
+
+        let print_edition = EditionMarker::new(&md, &me, 1);
+        print_edition.create(&mut context).await.unwrap();
+
+        let account = get_account(&mut context, &print_edition.new_edition_pubkey).await;
+
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let expected_lamports = rent.minimum_balance(MAX_EDITION_LEN) + CREATE_FEE;
+
+        assert_eq!(account.lamports, expected_lamports);
+        assert_eq!(account.data[EDITION_FEE_FLAG_INDEX], FEE_FLAG_SET);
+    }
+
     #[tokio::test]
     async fn update_does_not_overwrite_flag() {
         let mut context = program_test().start_with_context().await;
@@ -194,4 +219,87 @@ mod fees {
 
         assert_eq!(recipient_balance, expected_balance);
     }
+
+    #[tokio::test]
+    // Used for local QA testing and requires a keypair so excluded from CI.
+    #[ignore]
+    async fn collect_fees_edition_accounts() {
+        // Print editions and then collect the fees from the edition accounts only.
+        let mut context = program_test().start_with_context().await;
+
+        let fee_authority_funding = LAMPORTS_PER_SOL;
+
+        let fee_authority =
+            read_keypair_file("/media/veracrypt1/Levytx9LLPzAtDJJD7q813Zsm8zg9e1pb53mGxTKpD7.json")
+                .unwrap();
+        fee_authority
+            .airdrop(&mut context, fee_authority_funding)
+            .await
+            .unwrap();
+
+        let recipient = Keypair::new();
+
+        let md = Metadata::new();
+        md.create_v3_default(&mut context).await.unwrap();
+
+        let me = MasterEditionV2::new(&md);
+        me.create_v3(&mut context, Some(10)).await.unwrap();
+
+        let mut accounts = vec![md.pubkey];
+        for edition in 1..=3 {
+            let print_edition = EditionMarker::new(&md, &me, edition);
+            print_edition.create(&mut context).await.unwrap();
+
+            accounts.push(print_edition.new_metadata_pubkey);
+            accounts.push(print_edition.new_edition_pubkey);
+        }
+
+        let mut fetched = vec![];
+        for address in accounts {
+            let account = get_account(&mut context, &address).await;
+            fetched.push((address, account.data, account.lamports));
+        }
+
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let fee_accounts: Vec<_> = find_fee_accounts(
+            fetched
+                .iter()
+                .map(|(address, data, lamports)| (*address, data.as_slice(), *lamports)),
+            &rent,
+        )
+        .into_iter()
+        .filter(|account| account.key == Key::EditionV1)
+        .collect();
+
+        assert_eq!(fee_accounts.len(), 3);
+        let expected_balance = total_collectible_fees(&fee_accounts);
+        assert_eq!(expected_balance, 3 * CREATE_FEE);
+
+        let ix = collect_fees(
+            recipient.pubkey(),
+            fee_accounts.iter().map(|account| account.address).collect(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&fee_authority.pubkey()),
+            &[&fee_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let recipient_balance = get_account(&mut context, &recipient.pubkey())
+            .await
+            .lamports;
+
+        assert_eq!(recipient_balance, expected_balance);
+
+        // Fee flag in edition accounts is cleared, metadata accounts are untouched.
+        for fee_account in fee_accounts {
+            let account = get_account(&mut context, &fee_account.address).await;
+
+            assert_eq!(account.data[EDITION_FEE_FLAG_INDEX], FEE_FLAG_CLEARED);
+        }
+
+        md.assert_fee_flag_set(&mut context).await.unwrap();
+    }
 }
@@ -7,11 +7,13 @@ use utils::*;
 
 mod lock {
 
+    use mpl_token_auth_rules::{error::RuleSetError, state::Rule};
     use mpl_token_metadata::{
         error::MetadataError,
         instruction::DelegateArgs,
         pda::find_token_record_account,
-        state::{TokenRecord, TokenStandard, TokenState},
+        processor::{DelegateScenario, LockScenario},
+        state::{Operation, PayloadKey, TokenDelegateRole, TokenRecord, TokenStandard, TokenState},
     };
     use solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack, pubkey::Pubkey};
     use solana_sdk::{
@@ -365,4 +367,153 @@ mod lock {
         assert_eq!(token_record.state, TokenState::Locked);
         assert_eq!(token_record.locked_transfer, Some(Pubkey::default()));
     }
+
+    #[tokio::test]
+    async fn rule_set_validates_lock_programmable_nonfungible() {
+        let mut program_test = ProgramTest::new("mpl_token_metadata", mpl_token_metadata::ID, None);
+        program_test.add_program("mpl_token_auth_rules", mpl_token_auth_rules::ID, None);
+        let mut context = program_test.start_with_context().await;
+
+        // rule set only allowing a specific utility delegate to lock
+
+        let allowed = Keypair::new();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let rule_set = create_rule_set(
+            &mut context,
+            payer,
+            String::from("Lock Allow List"),
+            vec![
+                (
+                    Operation::Delegate {
+                        scenario: DelegateScenario::Token(TokenDelegateRole::Utility),
+                    },
+                    Rule::Pass,
+                ),
+                (
+                    Operation::Lock {
+                        scenario: LockScenario::UtilityDelegate,
+                    },
+                    Rule::PubkeyMatch {
+                        pubkey: allowed.pubkey(),
+                        field: PayloadKey::Authority.to_string(),
+                    },
+                ),
+            ],
+        )
+        .await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                Some(rule_set),
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let (pda_key, _) = find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        // set a utility delegate that is not on the allow list
+
+        let delegate = Keypair::new();
+        let delegate_pubkey = delegate.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate_pubkey,
+                DelegateArgs::UtilityV1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // lock fails
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let error = asset
+            .lock(&mut context, delegate, Some(pda_key), payer)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, RuleSetError::PubkeyMatchCheckFailed);
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+
+        assert_eq!(token_record.state, TokenState::Unlocked);
+    }
+
+    #[tokio::test]
+    async fn lock_programmable_nonfungible_without_lock_rules() {
+        let mut program_test = ProgramTest::new("mpl_token_metadata", mpl_token_metadata::ID, None);
+        program_test.add_program("mpl_token_auth_rules", mpl_token_auth_rules::ID, None);
+        let mut context = program_test.start_with_context().await;
+
+        // the default rule set does not include rules for the lock operation
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let (rule_set, auth_data) =
+            create_default_metaplex_rule_set(&mut context, payer, false).await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                Some(rule_set),
+                Some(auth_data),
+                1,
+            )
+            .await
+            .unwrap();
+
+        let (pda_key, _) = find_token_record_account(&asset.mint.pubkey(), &asset.token.unwrap());
+
+        // set a utility delegate
+
+        let delegate = Keypair::new();
+        let delegate_pubkey = delegate.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                delegate_pubkey,
+                DelegateArgs::UtilityV1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // locks
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        asset
+            .lock(&mut context, delegate, Some(pda_key), payer)
+            .await
+            .unwrap();
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+
+        assert_eq!(token_record.state, TokenState::Locked);
+    }
 }
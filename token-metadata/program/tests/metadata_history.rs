@@ -0,0 +1,223 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{EnableMetadataHistoryBuilder, UpdateBuilder},
+        update_metadata_accounts_v2, EnableMetadataHistoryArgs, InstructionBuilder, UpdateArgs,
+    },
+    pda::find_metadata_history_account,
+    state::{Data, DataV2, MetadataHistory, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod metadata_history {
+    use super::*;
+
+    async fn enable_history(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        capacity: u8,
+    ) -> Result<(), BanksClientError> {
+        let (metadata_history, _) = find_metadata_history_account(&asset.mint.pubkey());
+
+        let enable_ix = EnableMetadataHistoryBuilder::new()
+            .metadata_history(metadata_history)
+            .metadata(asset.metadata)
+            .authority(context.payer.pubkey())
+            .payer(context.payer.pubkey())
+            .build(EnableMetadataHistoryArgs::V1 { capacity })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[enable_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn get_history(context: &mut ProgramTestContext, mint: &Pubkey) -> MetadataHistory {
+        let (metadata_history, _) = find_metadata_history_account(mint);
+        let account = get_account(context, &metadata_history).await;
+        MetadataHistory::from_bytes(&account.data).unwrap()
+    }
+
+    fn with_uri(data: &Data, uri: &str) -> Data {
+        Data {
+            uri: uri.to_string(),
+            ..data.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn records_legacy_and_update_changes() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        enable_history(context, &asset, 4).await.unwrap();
+
+        let original = asset.get_metadata(context).await.data;
+        let (metadata_history, _) = find_metadata_history_account(&asset.mint.pubkey());
+
+        // changes the uri using UpdateMetadataAccountV2
+
+        let mut legacy_ix = update_metadata_accounts_v2(
+            mpl_token_metadata::ID,
+            asset.metadata,
+            context.payer.pubkey(),
+            None,
+            Some(DataV2 {
+                name: original.name.clone(),
+                symbol: original.symbol.clone(),
+                uri: "https://example.com/1.json".to_string(),
+                seller_fee_basis_points: original.seller_fee_basis_points,
+                creators: original.creators.clone(),
+                collection: None,
+                uses: None,
+            }),
+            None,
+            None,
+        );
+        legacy_ix
+            .accounts
+            .push(AccountMeta::new(metadata_history, false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[legacy_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // changes the uri using Update
+
+        let mut args = UpdateArgs::default_as_update_authority();
+        if let UpdateArgs::AsUpdateAuthorityV2 { data, .. } = &mut args {
+            *data = Some(with_uri(&original, "https://example.com/2.json"));
+        }
+
+        let update_ix = UpdateBuilder::new()
+            .authority(context.payer.pubkey())
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .metadata_history(metadata_history)
+            .edition(asset.edition.unwrap())
+            .payer(context.payer.pubkey())
+            .build(args)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // every snapshot can be verified against the history
+
+        let history = get_history(context, &asset.mint.pubkey()).await;
+        assert_eq!(history.entries.len(), 3);
+
+        for (index, snapshot) in [
+            original.clone(),
+            with_uri(&original, "https://example.com/1.json"),
+            with_uri(&original, "https://example.com/2.json"),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let entry = history.find_snapshot(snapshot).unwrap();
+            assert_eq!(entry, &history.entries[index]);
+            assert_eq!(entry.signer, context.payer.pubkey());
+        }
+
+        assert!(history
+            .find_snapshot(&with_uri(&original, "https://example.com/3.json"))
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_enable_history_without_capacity() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let error = enable_history(context, &asset, 0).await.unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidMetadataHistoryCapacity);
+    }
+
+    #[tokio::test]
+    async fn fail_update_with_wrong_history_account() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        enable_history(context, &asset, 4).await.unwrap();
+
+        let original = asset.get_metadata(context).await.data;
+
+        let mut args = UpdateArgs::default_as_update_authority();
+        if let UpdateArgs::AsUpdateAuthorityV2 { data, .. } = &mut args {
+            *data = Some(with_uri(&original, "https://example.com/1.json"));
+        }
+
+        // a history account that is passed must be the history of the asset
+        let update_ix = UpdateBuilder::new()
+            .authority(context.payer.pubkey())
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .metadata_history(Pubkey::new_unique())
+            .edition(asset.edition.unwrap())
+            .payer(context.payer.pubkey())
+            .build(args)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::KeyMismatch);
+
+        let history = get_history(context, &asset.mint.pubkey()).await;
+        assert_eq!(history.entries.len(), 1);
+    }
+}
@@ -0,0 +1,422 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{MigrateBuilder, UpdateMigrationAllowlistBuilder},
+        InstructionBuilder, MigrateArgs, TransferArgs, UpdateMigrationAllowlistArgs,
+        VerificationArgs,
+    },
+    pda::{find_migration_allowlist_account, find_token_record_account},
+    state::{
+        Collection, MigrationType, ProgrammableConfig, TokenRecord, TokenStandard, TokenState,
+    },
+};
+use num_traits::FromPrimitive;
+use solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_token::state::Account;
+use utils::*;
+
+mod migrate {
+    use super::*;
+
+    async fn migrate(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        migration_type: MigrationType,
+        authority: &Keypair,
+        token_owner: &Keypair,
+        migration_allowlist: Option<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let token = asset.token.unwrap();
+        let (token_record, _) = find_token_record_account(&asset.mint.pubkey(), &token);
+
+        let mut builder = MigrateBuilder::new();
+        builder
+            .metadata(asset.metadata)
+            .edition(asset.edition.unwrap())
+            .token(token)
+            .token_owner(token_owner.pubkey())
+            .mint(asset.mint.pubkey())
+            .payer(context.payer.pubkey())
+            .authority(authority.pubkey())
+            .token_record(token_record)
+            .spl_token_program(spl_token::ID);
+
+        if let Some(migration_allowlist) = migration_allowlist {
+            builder.migration_allowlist(migration_allowlist);
+        }
+
+        let migrate_ix = builder
+            .build(MigrateArgs::V1 {
+                migration_type,
+                rule_set: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[migrate_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority, token_owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn update_allowlist(
+        context: &mut ProgramTestContext,
+        collection: &DigitalAsset,
+        allow_all: bool,
+        add_mints: Vec<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let (migration_allowlist, _) = find_migration_allowlist_account(&collection.mint.pubkey());
+
+        let update_ix = UpdateMigrationAllowlistBuilder::new()
+            .migration_allowlist(migration_allowlist)
+            .collection_mint(collection.mint.pubkey())
+            .collection_metadata(collection.metadata)
+            .authority(context.payer.pubkey())
+            .payer(context.payer.pubkey())
+            .build(UpdateMigrationAllowlistArgs::V1 {
+                rule_set: None,
+                allow_all,
+                add_mints,
+                remove_mints: vec![],
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    /// Creates a verified collection item and transfers it to the holder.
+    async fn create_collection_item(
+        context: &mut ProgramTestContext,
+        holder: &Keypair,
+    ) -> (DigitalAsset, DigitalAsset) {
+        let mut collection = DigitalAsset::new();
+        collection
+            .create_and_mint_collection_parent(
+                context,
+                TokenStandard::NonFungible,
+                None,
+                None,
+                1,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint_item_with_collection(
+                context,
+                TokenStandard::NonFungible,
+                None,
+                None,
+                1,
+                Some(Collection {
+                    key: collection.mint.pubkey(),
+                    verified: false,
+                }),
+            )
+            .await
+            .unwrap();
+
+        let payer = context.payer.dirty_clone();
+        asset
+            .verify(
+                context,
+                payer,
+                VerificationArgs::CollectionV1,
+                None,
+                None,
+                Some(collection.mint.pubkey()),
+                Some(collection.metadata),
+                collection.edition,
+            )
+            .await
+            .unwrap();
+
+        let authority = context.payer.dirty_clone();
+        let payer = context.payer.dirty_clone();
+        let source_owner = context.payer.pubkey();
+
+        asset
+            .transfer(TransferParams {
+                context,
+                authority: &authority,
+                source_owner: &source_owner,
+                destination_owner: holder.pubkey(),
+                destination_token: None,
+                payer: &payer,
+                authorization_rules: None,
+                args: TransferArgs::V1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            })
+            .await
+            .unwrap();
+
+        (collection, asset)
+    }
+
+    async fn assert_migrated(context: &mut ProgramTestContext, asset: &DigitalAsset) {
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+        );
+        assert_eq!(
+            metadata.programmable_config,
+            Some(ProgrammableConfig::V1 { rule_set: None })
+        );
+
+        let token = asset.token.unwrap();
+        let token_account = get_account(context, &token).await;
+        assert!(Account::unpack(&token_account.data).unwrap().is_frozen());
+
+        let (token_record, _) = find_token_record_account(&asset.mint.pubkey(), &token);
+        let pda = get_account(context, &token_record).await;
+        let token_record: TokenRecord = try_from_slice_unchecked(&pda.data).unwrap();
+        assert_eq!(token_record.state, TokenState::Unlocked);
+    }
+
+    #[tokio::test]
+    async fn update_authority_migrates_nonfungible() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let authority = context.payer.dirty_clone();
+        let token_owner = context.payer.dirty_clone();
+
+        migrate(
+            context,
+            &asset,
+            MigrationType::ProgrammableV1,
+            &authority,
+            &token_owner,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_migrated(context, &asset).await;
+    }
+
+    #[tokio::test]
+    async fn holder_migrates_allowlisted_item() {
+        let context = &mut program_test().start_with_context().await;
+
+        let holder = Keypair::new();
+        let (collection, asset) = create_collection_item(context, &holder).await;
+
+        update_allowlist(context, &collection, false, vec![asset.mint.pubkey()])
+            .await
+            .unwrap();
+
+        let (migration_allowlist, _) = find_migration_allowlist_account(&collection.mint.pubkey());
+
+        migrate(
+            context,
+            &asset,
+            MigrationType::ProgrammableV1,
+            &holder,
+            &holder,
+            Some(migration_allowlist),
+        )
+        .await
+        .unwrap();
+
+        assert_migrated(context, &asset).await;
+    }
+
+    #[tokio::test]
+    async fn holder_migrates_item_of_allowed_collection() {
+        let context = &mut program_test().start_with_context().await;
+
+        let holder = Keypair::new();
+        let (collection, asset) = create_collection_item(context, &holder).await;
+
+        update_allowlist(context, &collection, true, vec![])
+            .await
+            .unwrap();
+
+        let (migration_allowlist, _) = find_migration_allowlist_account(&collection.mint.pubkey());
+
+        migrate(
+            context,
+            &asset,
+            MigrationType::ProgrammableV1,
+            &holder,
+            &holder,
+            Some(migration_allowlist),
+        )
+        .await
+        .unwrap();
+
+        assert_migrated(context, &asset).await;
+    }
+
+    #[tokio::test]
+    async fn fail_holder_migrates_item_not_allowlisted() {
+        let context = &mut program_test().start_with_context().await;
+
+        let holder = Keypair::new();
+        let (collection, asset) = create_collection_item(context, &holder).await;
+
+        // the allowlist includes a different mint
+        update_allowlist(context, &collection, false, vec![Keypair::new().pubkey()])
+            .await
+            .unwrap();
+
+        let (migration_allowlist, _) = find_migration_allowlist_account(&collection.mint.pubkey());
+
+        let error = migrate(
+            context,
+            &asset,
+            MigrationType::ProgrammableV1,
+            &holder,
+            &holder,
+            Some(migration_allowlist),
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::MigrationNotAllowed);
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.token_standard, Some(TokenStandard::NonFungible));
+    }
+
+    #[tokio::test]
+    async fn migrate_programmable_back_to_nonfungible() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // transfers the asset to a holder other than the update authority
+
+        let holder = Keypair::new();
+        let authority = context.payer.dirty_clone();
+        let payer = context.payer.dirty_clone();
+        let source_owner = context.payer.pubkey();
+
+        asset
+            .transfer(TransferParams {
+                context,
+                authority: &authority,
+                source_owner: &source_owner,
+                destination_owner: holder.pubkey(),
+                destination_token: None,
+                payer: &payer,
+                authorization_rules: None,
+                args: TransferArgs::V1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            })
+            .await
+            .unwrap();
+
+        migrate(
+            context,
+            &asset,
+            MigrationType::NonFungibleV1,
+            &authority,
+            &holder,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.token_standard, Some(TokenStandard::NonFungible));
+        assert_eq!(metadata.programmable_config, None);
+
+        let token = asset.token.unwrap();
+        let token_account = get_account(context, &token).await;
+        assert!(!Account::unpack(&token_account.data).unwrap().is_frozen());
+
+        let (token_record, _) = find_token_record_account(&asset.mint.pubkey(), &token);
+        let account = context
+            .banks_client
+            .get_account(token_record)
+            .await
+            .unwrap();
+        assert!(account.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_migrate_to_nonfungible_without_update_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        // the holder alone cannot migrate the asset
+
+        let holder = context.payer.dirty_clone();
+        let fake_authority = Keypair::new();
+
+        let error = migrate(
+            context,
+            &asset,
+            MigrationType::NonFungibleV1,
+            &fake_authority,
+            &holder,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UpdateAuthorityIncorrect);
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+        );
+    }
+}
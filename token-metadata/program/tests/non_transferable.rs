@@ -0,0 +1,272 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::TransferBuilder, BurnArgs, DelegateArgs, InstructionBuilder, TransferArgs,
+    },
+    pda::find_token_record_account,
+    state::{PrintSupply, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{native_token::LAMPORTS_PER_SOL, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Account;
+use utils::*;
+
+mod non_transferable {
+    use super::*;
+
+    /// Transfers the asset to the `destination_owner` as a recovery initiated by the
+    /// update authority; the holder only signs when `holder_signs` is set.
+    async fn recover(
+        context: &mut ProgramTestContext,
+        asset: &mut DigitalAsset,
+        update_authority: &Keypair,
+        holder: &Keypair,
+        destination_owner: &Pubkey,
+        holder_signs: bool,
+    ) -> Result<(), BanksClientError> {
+        let destination = get_associated_token_address(destination_owner, &asset.mint.pubkey());
+        let (destination_token_record, _) =
+            find_token_record_account(&asset.mint.pubkey(), &destination);
+
+        let mut recover_ix = TransferBuilder::new()
+            .authority(update_authority.pubkey())
+            .token_owner(holder.pubkey())
+            .token(asset.token.unwrap())
+            .owner_token_record(asset.token_record.unwrap())
+            .destination_owner(*destination_owner)
+            .destination(destination)
+            .destination_token_record(destination_token_record)
+            .metadata(asset.metadata)
+            .edition(asset.edition.unwrap())
+            .payer(update_authority.pubkey())
+            .mint(asset.mint.pubkey())
+            .build(TransferArgs::V1 {
+                authorization_data: None,
+                amount: 1,
+            })
+            .unwrap()
+            .instruction();
+
+        let mut signers = vec![update_authority];
+
+        if holder_signs && holder.pubkey() != update_authority.pubkey() {
+            // the token owner is not a signer of regular transfers
+            recover_ix.accounts[1].is_signer = true;
+            signers.push(holder);
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[recover_ix],
+            Some(&update_authority.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.map(|_| {
+            asset.token = Some(destination);
+            asset.token_record = Some(destination_token_record);
+        })
+    }
+
+    /// Creates a non-transferable asset and recovers it to a holder that is not the
+    /// update authority.
+    async fn create_with_holder(context: &mut ProgramTestContext) -> (DigitalAsset, Keypair) {
+        let update_authority = context.payer.dirty_clone();
+        let holder = Keypair::new();
+        holder.airdrop(context, LAMPORTS_PER_SOL).await.unwrap();
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                context,
+                TokenStandard::ProgrammableNonTransferable,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        recover(
+            context,
+            &mut asset,
+            &update_authority,
+            &update_authority,
+            &holder.pubkey(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        (asset, holder)
+    }
+
+    #[tokio::test]
+    async fn holder_cannot_transfer() {
+        let mut context = program_test().start_with_context().await;
+        let (mut asset, holder) = create_with_holder(&mut context).await;
+
+        let error = asset
+            .transfer(TransferParams {
+                context: &mut context,
+                authority: &holder,
+                source_owner: &holder.pubkey(),
+                destination_owner: Keypair::new().pubkey(),
+                destination_token: None,
+                payer: &holder,
+                authorization_rules: None,
+                args: TransferArgs::V1 {
+                    authorization_data: None,
+                    amount: 1,
+                },
+            })
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(2, error, MetadataError::NonTransferableAsset);
+    }
+
+    #[tokio::test]
+    async fn recover_requires_holder_signature() {
+        let mut context = program_test().start_with_context().await;
+        let (mut asset, holder) = create_with_holder(&mut context).await;
+
+        let update_authority = context.payer.dirty_clone();
+        let new_wallet = Keypair::new();
+
+        let error = recover(
+            &mut context,
+            &mut asset,
+            &update_authority,
+            &holder,
+            &new_wallet.pubkey(),
+            false,
+        )
+        .await
+        .unwrap_err();
+
+        assert_transport_error!(
+            error,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn recover_to_new_wallet() {
+        let mut context = program_test().start_with_context().await;
+        let (mut asset, holder) = create_with_holder(&mut context).await;
+
+        let update_authority = context.payer.dirty_clone();
+        let new_wallet = Keypair::new();
+        let source_token_record = asset.token_record.unwrap();
+
+        recover(
+            &mut context,
+            &mut asset,
+            &update_authority,
+            &holder,
+            &new_wallet.pubkey(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        let token_account = get_account(&mut context, &asset.token.unwrap()).await;
+        let token = Account::unpack(&token_account.data).unwrap();
+        assert_eq!(token.owner, new_wallet.pubkey());
+        assert_eq!(token.amount, 1);
+        assert!(token.is_frozen());
+
+        // the token record of the previous wallet is closed
+        let source_token_record = context
+            .banks_client
+            .get_account(source_token_record)
+            .await
+            .unwrap();
+        assert!(source_token_record.is_none());
+    }
+
+    #[tokio::test]
+    async fn holder_can_burn() {
+        let mut context = program_test().start_with_context().await;
+        let (mut asset, holder) = create_with_holder(&mut context).await;
+
+        asset
+            .burn(&mut context, holder, BurnArgs::V1 { amount: 1 }, None, None)
+            .await
+            .unwrap();
+
+        asset.assert_burned(&mut context).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_transfer_delegate() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonTransferable,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let payer = context.payer.dirty_clone();
+        let error = asset
+            .delegate(
+                &mut context,
+                payer,
+                Keypair::new().pubkey(),
+                DelegateArgs::TransferV1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::InvalidDelegateRole);
+    }
+
+    #[tokio::test]
+    async fn fail_create_with_prints() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        let error = asset
+            .create_advanced(
+                &mut context,
+                TokenStandard::ProgrammableNonTransferable,
+                String::from("Credential"),
+                String::from("CRD"),
+                String::from("uri"),
+                0,
+                None,
+                None,
+                None,
+                None,
+                PrintSupply::Limited(10),
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(1, error, MetadataError::NonTransferablePrintsNotSupported);
+    }
+}
@@ -0,0 +1,354 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::TransferBuilder, BurnArgs, DelegateArgs, InstructionBuilder, RevokeArgs,
+        TransferArgs,
+    },
+    pda::find_token_record_account,
+    state::{Resizable, TokenDelegateRole, TokenRecord, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Account;
+use utils::*;
+
+mod rental {
+    use super::*;
+
+    const RENTAL_DURATION: i64 = 3600;
+
+    /// Lends a pNFT to the borrower, which transfers it to its own wallet.
+    async fn lend(
+        context: &mut ProgramTestContext,
+        asset: &mut DigitalAsset,
+        lender: &Keypair,
+        borrower: &Keypair,
+    ) {
+        let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+
+        asset
+            .delegate(
+                context,
+                lender.dirty_clone(),
+                borrower.pubkey(),
+                DelegateArgs::RentalV1 {
+                    amount: 1,
+                    expiry: clock.unix_timestamp + RENTAL_DURATION,
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let lender_pubkey = lender.pubkey();
+
+        asset
+            .transfer(TransferParams {
+                context,
+                authority: borrower,
+                source_owner: &lender_pubkey,
+                destination_owner: borrower.pubkey(),
+                destination_token: None,
+                payer: borrower,
+                authorization_rules: None,
+                args: TransferArgs::V1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            })
+            .await
+            .unwrap();
+    }
+
+    /// Transfers the rented token back to the lender, marking the lender as writable when
+    /// `writable_lender` is set.
+    async fn reclaim(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        crank: &Keypair,
+        borrower: &Pubkey,
+        lender: &Pubkey,
+        lender_token: &Pubkey,
+        writable_lender: bool,
+    ) -> Result<(), BanksClientError> {
+        let borrower_token = get_associated_token_address(borrower, &asset.mint.pubkey());
+        let (owner_token_record, _) =
+            find_token_record_account(&asset.mint.pubkey(), &borrower_token);
+        let (destination_token_record, _) =
+            find_token_record_account(&asset.mint.pubkey(), lender_token);
+
+        let mut transfer_ix = TransferBuilder::new()
+            .authority(crank.pubkey())
+            .token_owner(*borrower)
+            .token(borrower_token)
+            .destination_owner(*lender)
+            .destination(*lender_token)
+            .metadata(asset.metadata)
+            .edition(asset.edition.unwrap())
+            .owner_token_record(owner_token_record)
+            .destination_token_record(destination_token_record)
+            .payer(crank.pubkey())
+            .mint(asset.mint.pubkey())
+            .build(TransferArgs::V1 {
+                amount: 1,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        // destination owner
+        transfer_ix.accounts[3].is_writable = writable_lender;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(800_000),
+                transfer_ix,
+            ],
+            Some(&crank.pubkey()),
+            &[crank],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn lender_reclaims_after_expiry() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let lender = context.payer.dirty_clone();
+        let lender_token = asset.token.unwrap();
+
+        let borrower = Keypair::new();
+        borrower.airdrop(&mut context, 1_000_000_000).await.unwrap();
+
+        lend(&mut context, &mut asset, &lender, &borrower).await;
+
+        // the borrower holds the token with the edition as delegate
+
+        let borrower_token = get_associated_token_address(&borrower.pubkey(), &asset.mint.pubkey());
+        assert_eq!(asset.token, Some(borrower_token));
+
+        let (token_record, _) = find_token_record_account(&asset.mint.pubkey(), &borrower_token);
+        let account = get_account(&mut context, &token_record).await;
+        let record = TokenRecord::from_bytes(&account.data).unwrap();
+
+        assert_eq!(record.delegate_role, Some(TokenDelegateRole::Rental));
+        assert_eq!(record.delegate, asset.edition);
+        assert_eq!(record.lender, Some(lender.pubkey()));
+
+        let account = get_account(&mut context, &borrower_token).await;
+        let token = Account::unpack(&account.data).unwrap();
+        assert_eq!(token.amount, 1);
+        assert_eq!(token.delegate, COption::from(asset.edition));
+
+        // a crank cannot reclaim the token before the rental expires
+
+        let crank = Keypair::new();
+        crank.airdrop(&mut context, 1_000_000_000).await.unwrap();
+
+        let borrower_pubkey = borrower.pubkey();
+
+        let err = asset
+            .transfer(TransferParams {
+                context: &mut context,
+                authority: &crank,
+                source_owner: &borrower_pubkey,
+                destination_owner: lender.pubkey(),
+                destination_token: Some(lender_token),
+                payer: &crank,
+                authorization_rules: None,
+                args: TransferArgs::V1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            })
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::RentalNotExpired);
+
+        // moves the clock past the rental expiry
+
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += RENTAL_DURATION + 1;
+        context.set_sysvar(&clock);
+
+        // the token can only go back to the lender
+
+        let err = asset
+            .transfer(TransferParams {
+                context: &mut context,
+                authority: &crank,
+                source_owner: &borrower_pubkey,
+                destination_owner: crank.pubkey(),
+                destination_token: None,
+                payer: &crank,
+                authorization_rules: None,
+                args: TransferArgs::V1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            })
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidRentalDestination);
+
+        // the lender must be writable to receive the rent of the borrower token record
+
+        let err = reclaim(
+            &mut context,
+            &asset,
+            &crank,
+            &borrower_pubkey,
+            &lender.pubkey(),
+            &lender_token,
+            false,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::LenderNotWritable);
+
+        let (borrower_token_record, _) =
+            find_token_record_account(&asset.mint.pubkey(), &borrower_token);
+        let record_rent = get_account(&mut context, &borrower_token_record)
+            .await
+            .lamports;
+        let lender_lamports = get_account(&mut context, &lender.pubkey()).await.lamports;
+
+        reclaim(
+            &mut context,
+            &asset,
+            &crank,
+            &borrower_pubkey,
+            &lender.pubkey(),
+            &lender_token,
+            true,
+        )
+        .await
+        .unwrap();
+
+        let account = get_account(&mut context, &lender_token).await;
+        let token = Account::unpack(&account.data).unwrap();
+        assert_eq!(token.amount, 1);
+        assert_eq!(token.delegate, COption::None);
+
+        let (token_record, _) = find_token_record_account(&asset.mint.pubkey(), &lender_token);
+        let account = get_account(&mut context, &token_record).await;
+        let record = TokenRecord::from_bytes(&account.data).unwrap();
+        assert_eq!(record.delegate_role, None);
+        assert_eq!(record.lender, None);
+
+        asset
+            .assert_token_record_closed(&mut context, &borrower_token)
+            .await
+            .unwrap();
+
+        // the rent of the borrower token record goes back to the lender
+        let account = get_account(&mut context, &lender.pubkey()).await;
+        assert_eq!(account.lamports, lender_lamports + record_rent);
+    }
+
+    #[tokio::test]
+    async fn borrower_cannot_transfer_burn_or_revoke() {
+        let mut context = program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(
+                &mut context,
+                TokenStandard::ProgrammableNonFungible,
+                None,
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let lender = context.payer.dirty_clone();
+
+        let borrower = Keypair::new();
+        borrower.airdrop(&mut context, 1_000_000_000).await.unwrap();
+
+        lend(&mut context, &mut asset, &lender, &borrower).await;
+
+        // transfer
+
+        let borrower_pubkey = borrower.pubkey();
+        let destination = Keypair::new();
+
+        let err = asset
+            .transfer(TransferParams {
+                context: &mut context,
+                authority: &borrower,
+                source_owner: &borrower_pubkey,
+                destination_owner: destination.pubkey(),
+                destination_token: None,
+                payer: &borrower,
+                authorization_rules: None,
+                args: TransferArgs::V1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            })
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::TokenIsRented);
+
+        // burn
+
+        let err = asset
+            .burn(
+                &mut context,
+                borrower.dirty_clone(),
+                BurnArgs::V1 { amount: 1 },
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::TokenIsRented);
+
+        // revoke
+
+        let err = asset
+            .revoke(
+                &mut context,
+                borrower.dirty_clone(),
+                borrower.dirty_clone(),
+                asset.edition.unwrap(),
+                RevokeArgs::RentalV1,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::TokenIsRented);
+    }
+}
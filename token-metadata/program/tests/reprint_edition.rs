@@ -0,0 +1,192 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{instruction::InstructionError, signer::Signer, transaction::TransactionError};
+use utils::*;
+
+mod reprint_edition {
+    use mpl_token_metadata::{
+        error::MetadataError,
+        instruction,
+        pda::find_reprint_record_account,
+        state::{
+            Edition, MasterEditionV2 as ProgramMasterEdition, ReprintRecord, TokenMetadataAccount,
+        },
+    };
+    use solana_program::{instruction::AccountMeta, system_program};
+    use solana_sdk::{signature::Keypair, transaction::Transaction};
+    use spl_associated_token_account::get_associated_token_address;
+
+    use super::*;
+
+    /// Prints edition 1, transfers it to a user and burns it from the user wallet, which
+    /// keeps the edition number taken on the edition marker. The burn is recorded on the
+    /// reprint record of the edition number when `record` is set.
+    async fn print_and_burn_from_user_wallet(
+        context: &mut ProgramTestContext,
+        original_nft: &Metadata,
+        master_edition: &MasterEditionV2,
+        record: bool,
+    ) -> EditionMarker {
+        let mut print_edition = EditionMarker::new(original_nft, master_edition, 1);
+        print_edition.create(context).await.unwrap();
+
+        let user = Keypair::new();
+        airdrop(context, &user.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        context.warp_to_slot(10).unwrap();
+
+        print_edition
+            .transfer(context, &user.pubkey())
+            .await
+            .unwrap();
+        let user_token_account =
+            get_associated_token_address(&user.pubkey(), &print_edition.mint.pubkey());
+
+        let mut burn_ix = instruction::burn_edition_nft(
+            mpl_token_metadata::ID,
+            print_edition.new_metadata_pubkey,
+            user.pubkey(),
+            print_edition.mint.pubkey(),
+            original_nft.mint.pubkey(),
+            user_token_account,
+            original_nft.token.pubkey(),
+            master_edition.pubkey,
+            print_edition.new_edition_pubkey,
+            print_edition.pubkey,
+            spl_token::ID,
+        );
+
+        if record {
+            let (reprint_record, _) = find_reprint_record_account(&original_nft.mint.pubkey(), 1);
+            burn_ix
+                .accounts
+                .push(AccountMeta::new(reprint_record, false));
+            burn_ix
+                .accounts
+                .push(AccountMeta::new_readonly(system_program::ID, false));
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_ix],
+            Some(&user.pubkey()),
+            &[&user],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        assert!(!print_edition.exists_on_chain(context).await);
+
+        print_edition
+    }
+
+    async fn create_master_edition(
+        context: &mut ProgramTestContext,
+    ) -> (Metadata, MasterEditionV2) {
+        let original_nft = Metadata::new();
+        original_nft.create_v3_default(context).await.unwrap();
+
+        let master_edition = MasterEditionV2::new(&original_nft);
+        master_edition.create_v3(context, Some(10)).await.unwrap();
+
+        (original_nft, master_edition)
+    }
+
+    #[tokio::test]
+    async fn successfully_reprint_burned_edition() {
+        let mut context = program_test().start_with_context().await;
+        let (original_nft, master_edition) = create_master_edition(&mut context).await;
+
+        let burned_edition =
+            print_and_burn_from_user_wallet(&mut context, &original_nft, &master_edition, true)
+                .await;
+
+        // The burn is recorded for the edition number.
+        let (reprint_record, _) = find_reprint_record_account(&original_nft.mint.pubkey(), 1);
+        let record_account = get_account(&mut context, &reprint_record).await;
+        let record = ReprintRecord::safe_deserialize(&record_account.data).unwrap();
+        assert_eq!(record.edition, 1);
+        assert_eq!(record.mint, burned_edition.mint.pubkey());
+
+        // The edition number is still taken, so it cannot be printed again.
+        let print_edition = EditionMarker::new(&original_nft, &master_edition, 1);
+        let err = print_edition.create(&mut context).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::AlreadyInitialized);
+
+        // But it can be reprinted.
+        let reprint_edition = EditionMarker::new(&original_nft, &master_edition, 1);
+        reprint_edition.reprint(&mut context).await.unwrap();
+
+        assert!(reprint_edition.exists_on_chain(&mut context).await);
+
+        let edition_account = get_account(&mut context, &reprint_edition.new_edition_pubkey).await;
+        let edition = Edition::safe_deserialize(&edition_account.data).unwrap();
+        assert_eq!(edition.edition, 1);
+        assert_eq!(edition.parent, master_edition.pubkey);
+
+        // The supply is restored.
+        let master_edition_account = get_account(&mut context, &master_edition.pubkey).await;
+        let master_edition_struct =
+            ProgramMasterEdition::safe_deserialize(&master_edition_account.data).unwrap();
+        assert_eq!(master_edition_struct.supply, 1);
+
+        // The reprint record is closed.
+        assert!(context
+            .banks_client
+            .get_account(reprint_record)
+            .await
+            .unwrap()
+            .is_none());
+
+        // And the burn cannot be used for another reprint.
+        let second_reprint = EditionMarker::new(&original_nft, &master_edition, 1);
+        let err = second_reprint.reprint(&mut context).await.unwrap_err();
+        assert_custom_error!(err, MetadataError::PrintEditionNotBurned);
+    }
+
+    #[tokio::test]
+    async fn fail_to_reprint_with_live_edition() {
+        let mut context = program_test().start_with_context().await;
+        let (original_nft, master_edition) = create_master_edition(&mut context).await;
+
+        let print_edition = EditionMarker::new(&original_nft, &master_edition, 1);
+        print_edition.create(&mut context).await.unwrap();
+
+        let reprint_edition = EditionMarker::new(&original_nft, &master_edition, 1);
+        let err = reprint_edition.reprint(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::PrintEditionNotBurned);
+    }
+
+    #[tokio::test]
+    async fn fail_to_reprint_unrecorded_burn() {
+        let mut context = program_test().start_with_context().await;
+        let (original_nft, master_edition) = create_master_edition(&mut context).await;
+
+        // The burn is not recorded, so the edition number stays taken.
+        print_and_burn_from_user_wallet(&mut context, &original_nft, &master_edition, false).await;
+
+        let reprint_edition = EditionMarker::new(&original_nft, &master_edition, 1);
+        let err = reprint_edition.reprint(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::PrintEditionNotBurned);
+    }
+
+    #[tokio::test]
+    async fn fail_to_reprint_edition_number_not_printed() {
+        let mut context = program_test().start_with_context().await;
+        let (original_nft, master_edition) = create_master_edition(&mut context).await;
+
+        print_and_burn_from_user_wallet(&mut context, &original_nft, &master_edition, true).await;
+
+        // Edition 2 was never printed.
+        let reprint_edition = EditionMarker::new(&original_nft, &master_edition, 2);
+        let err = reprint_edition.reprint(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::EditionNumberNotPrinted);
+    }
+}
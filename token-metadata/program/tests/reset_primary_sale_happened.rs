@@ -0,0 +1,122 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::ResetPrimarySaleHappenedBuilder, update_primary_sale_happened_via_token,
+        InstructionBuilder, TransferArgs,
+    },
+    state::TokenStandard,
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod reset_primary_sale_happened {
+    use super::*;
+
+    async fn reset(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+    ) -> Result<(), BanksClientError> {
+        let reset_ix = ResetPrimarySaleHappenedBuilder::new()
+            .metadata(asset.metadata)
+            .authority(context.payer.pubkey())
+            .token(asset.token.unwrap())
+            .build()
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[reset_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn success_reset_while_held_by_verified_creator() {
+        let context = &mut program_test().start_with_context().await;
+
+        // the payer is the only (verified) creator and the holder of the asset
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // flags the primary sale
+
+        let update_ix = update_primary_sale_happened_via_token(
+            mpl_token_metadata::ID,
+            asset.metadata,
+            context.payer.pubkey(),
+            asset.token.unwrap(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = asset.get_metadata(context).await;
+        assert!(metadata.primary_sale_happened);
+
+        // resets the flag
+
+        reset(context, &asset).await.unwrap();
+
+        let metadata = asset.get_metadata(context).await;
+        assert!(!metadata.primary_sale_happened);
+    }
+
+    #[tokio::test]
+    async fn fail_reset_when_holder_is_not_verified_creator() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // transfers the asset to a wallet that is not a creator
+
+        let destination_owner = Keypair::new().pubkey();
+        let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let source_owner = context.payer.pubkey();
+
+        asset
+            .transfer(TransferParams {
+                context,
+                authority: &authority,
+                source_owner: &source_owner,
+                destination_owner,
+                destination_token: None,
+                payer: &payer,
+                authorization_rules: None,
+                args: TransferArgs::V1 {
+                    amount: 1,
+                    authorization_data: None,
+                },
+            })
+            .await
+            .unwrap();
+
+        let error = reset(context, &asset).await.unwrap_err();
+
+        assert_custom_error!(error, MetadataError::HolderNotVerifiedCreator);
+    }
+}
@@ -14,18 +14,25 @@ mod revoke {
     use borsh::BorshSerialize;
     use mpl_token_metadata::{
         error::MetadataError,
-        instruction::{DelegateArgs, MetadataDelegateRole, RevokeArgs},
+        instruction::{
+            builders::RevokeBuilder, DelegateArgs, InstructionBuilder, MetadataDelegateRole,
+            RevokeArgs,
+        },
         pda::{find_metadata_delegate_record_account, find_token_record_account},
         state::{
-            Key, Metadata, MetadataDelegateRecord, TokenDelegateRole, TokenRecord, TokenStandard,
-            TOKEN_RECORD_SIZE,
+            Key, Metadata, MetadataDelegateRecord, Resizable, TokenDelegateRole, TokenRecord,
+            TokenStandard, TOKEN_RECORD_SIZE,
         },
     };
     use num_traits::FromPrimitive;
     use solana_program::{
-        borsh::try_from_slice_unchecked, program_option::COption, program_pack::Pack,
+        borsh::try_from_slice_unchecked, clock::Clock, instruction::AccountMeta,
+        program_option::COption, program_pack::Pack,
+    };
+    use solana_sdk::{
+        account::{Account as SdkAccount, AccountSharedData},
+        transaction::Transaction,
     };
-    use solana_sdk::account::{Account as SdkAccount, AccountSharedData};
     use spl_token::state::Account;
 
     use super::*;
@@ -662,4 +669,145 @@ mod revoke {
 
         assert_custom_error!(err, MetadataError::InvalidCloseAuthority);
     }
+
+    #[tokio::test]
+    async fn anyone_can_revoke_expired_data_delegate() {
+        let mut context = program_test().start_with_context().await;
+
+        // asset
+
+        let mut asset = DigitalAsset::default();
+        asset
+            .create_and_mint(&mut context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        // creates a data delegate that expires in one hour
+
+        let user = Keypair::new();
+        let user_pubkey = user.pubkey();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let payer_pubkey = payer.pubkey();
+
+        let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+
+        asset
+            .delegate(
+                &mut context,
+                payer,
+                user_pubkey,
+                DelegateArgs::LimitedMetadataV1 {
+                    role: MetadataDelegateRole::Data,
+                    expires_at: Some(clock.unix_timestamp + 3600),
+                    max_uses: None,
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let (pda_key, _) = find_metadata_delegate_record_account(
+            &asset.mint.pubkey(),
+            MetadataDelegateRole::Data,
+            &payer_pubkey,
+            &user_pubkey,
+        );
+
+        let pda = get_account(&mut context, &pda_key).await;
+        let delegate_record = MetadataDelegateRecord::from_bytes(&pda.data).unwrap();
+        assert_eq!(
+            delegate_record.expires_at,
+            Some(clock.unix_timestamp + 3600)
+        );
+
+        // a random account cannot revoke an active delegate
+
+        let crank = Keypair::new();
+        crank.airdrop(&mut context, 1_000_000_000).await.unwrap();
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let approver = Keypair::from_bytes(&crank.to_bytes()).unwrap();
+
+        let error = asset
+            .revoke(
+                &mut context,
+                payer,
+                approver,
+                user_pubkey,
+                RevokeArgs::DataV1,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UpdateAuthorityIncorrect);
+
+        // moves the clock past the expiry time
+
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += 3601;
+        context.set_sysvar(&clock);
+
+        let mut revoke_ix = RevokeBuilder::new()
+            .delegate_record(pda_key)
+            .delegate(user_pubkey)
+            .metadata(asset.metadata)
+            .mint(asset.mint.pubkey())
+            .authority(crank.pubkey())
+            .payer(crank.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(RevokeArgs::DataV1)
+            .unwrap()
+            .instruction();
+
+        // the rent goes back to the approver, so its account is required
+
+        let tx = Transaction::new_signed_with_payer(
+            &[revoke_ix.clone()],
+            Some(&crank.pubkey()),
+            &[&crank],
+            context.last_blockhash,
+        );
+        let error = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_transport_error!(
+            error,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::NotEnoughAccountKeys
+            ))
+        );
+
+        let approver_lamports = get_account(&mut context, &payer_pubkey).await.lamports;
+        let rent = pda.lamports;
+
+        revoke_ix
+            .accounts
+            .push(AccountMeta::new(payer_pubkey, false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[revoke_ix],
+            Some(&crank.pubkey()),
+            &[&crank],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        assert_eq!(
+            get_account(&mut context, &payer_pubkey).await.lamports,
+            approver_lamports + rent
+        );
+
+        // checks that the delegate does not exist
+
+        assert!(context
+            .banks_client
+            .get_account(pda_key)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
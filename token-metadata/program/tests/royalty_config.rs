@@ -0,0 +1,148 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::SetRoyaltyConfigBuilder, InstructionBuilder, SetRoyaltyConfigArgs,
+        VerificationArgs,
+    },
+    state::{Collection, RoyaltyConfig, TokenStandard},
+    utils::{resolve_royalties, Royalties},
+};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod royalty_config {
+    use super::*;
+
+    async fn set_royalty_config(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        royalty_config: Option<RoyaltyConfig>,
+    ) -> Result<(), BanksClientError> {
+        let set_ix = SetRoyaltyConfigBuilder::new()
+            .metadata(asset.metadata)
+            .authority(context.payer.pubkey())
+            .build(SetRoyaltyConfigArgs::V1 { royalty_config })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn item_inherits_collection_royalties() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::new();
+        collection
+            .create_and_mint_collection_parent(
+                context,
+                TokenStandard::NonFungible,
+                None,
+                None,
+                1,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        let mut item = DigitalAsset::new();
+        item.create_and_mint_item_with_collection(
+            context,
+            TokenStandard::NonFungible,
+            None,
+            None,
+            1,
+            Some(Collection {
+                key: collection.mint.pubkey(),
+                verified: false,
+            }),
+        )
+        .await
+        .unwrap();
+
+        let payer = context.payer.dirty_clone();
+        item.verify(
+            context,
+            payer,
+            VerificationArgs::CollectionV1,
+            None,
+            None,
+            Some(collection.mint.pubkey()),
+            Some(collection.metadata),
+            collection.edition,
+        )
+        .await
+        .unwrap();
+
+        set_royalty_config(
+            context,
+            &collection,
+            Some(RoyaltyConfig::CollectionAuthoritative),
+        )
+        .await
+        .unwrap();
+
+        set_royalty_config(context, &item, Some(RoyaltyConfig::InheritFromCollection))
+            .await
+            .unwrap();
+
+        let collection_metadata = collection.get_metadata(context).await;
+        let item_metadata = item.get_metadata(context).await;
+
+        assert_eq!(
+            item_metadata.royalty_config,
+            Some(RoyaltyConfig::InheritFromCollection)
+        );
+        assert_eq!(
+            resolve_royalties(&item_metadata, Some(&collection_metadata)).unwrap(),
+            Royalties::from(&collection_metadata)
+        );
+
+        // reverting the item config restores its own royalties
+
+        set_royalty_config(context, &item, None).await.unwrap();
+
+        let item_metadata = item.get_metadata(context).await;
+        assert_eq!(
+            resolve_royalties(&item_metadata, None).unwrap(),
+            Royalties::from(&item_metadata)
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_authoritative_royalties_without_collection_details() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let error = set_royalty_config(
+            context,
+            &asset,
+            Some(RoyaltyConfig::CollectionAuthoritative),
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::NotACollectionParent);
+    }
+}
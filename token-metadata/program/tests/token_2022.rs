@@ -0,0 +1,255 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    instruction::{
+        builders::{CreateBuilder, MintBuilder, TransferBuilder},
+        CreateArgs, InstructionBuilder, MintArgs, TransferArgs,
+    },
+    pda::find_metadata_account,
+    state::{AssetData, PrintSupply, TokenStandard},
+};
+use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::{
+    extension::{transfer_fee, ExtensionType, StateWithExtensions},
+    state::{Account, Mint},
+};
+use utils::*;
+
+mod token_2022 {
+    use super::*;
+
+    /// Creates a fungible asset; when `transfer_fee_basis_points` is set, the Token-2022 mint
+    /// is created with the transfer fee extension before the metadata.
+    async fn create_fungible(
+        context: &mut ProgramTestContext,
+        mint: &Keypair,
+        transfer_fee_basis_points: Option<u16>,
+    ) -> Result<(), BanksClientError> {
+        let payer_pubkey = context.payer.pubkey();
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(800_000)];
+
+        let mut builder = CreateBuilder::new();
+        builder
+            .metadata(find_metadata_account(&mint.pubkey()).0)
+            .mint(mint.pubkey())
+            .authority(payer_pubkey)
+            .payer(payer_pubkey)
+            .update_authority(payer_pubkey)
+            .spl_token_program(spl_token_2022::ID)
+            .update_authority_as_signer(true);
+
+        if let Some(basis_points) = transfer_fee_basis_points {
+            // extensions must be initialized before the mint
+            let space = ExtensionType::get_account_len::<Mint>(&[ExtensionType::TransferFeeConfig]);
+            let rent = context.banks_client.get_rent().await.unwrap();
+
+            instructions.push(system_instruction::create_account(
+                &payer_pubkey,
+                &mint.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &spl_token_2022::ID,
+            ));
+            instructions.push(
+                transfer_fee::instruction::initialize_transfer_fee_config(
+                    &spl_token_2022::ID,
+                    &mint.pubkey(),
+                    None,
+                    None,
+                    basis_points,
+                    u64::MAX,
+                )
+                .unwrap(),
+            );
+            instructions.push(
+                spl_token_2022::instruction::initialize_mint2(
+                    &spl_token_2022::ID,
+                    &mint.pubkey(),
+                    &payer_pubkey,
+                    Some(&payer_pubkey),
+                    0,
+                )
+                .unwrap(),
+            );
+        } else {
+            builder.initialize_mint(true);
+        }
+
+        instructions.push(
+            builder
+                .build(CreateArgs::V1 {
+                    asset_data: AssetData::new(
+                        TokenStandard::Fungible,
+                        "Token".to_string(),
+                        "TKN".to_string(),
+                        "uri".to_string(),
+                    ),
+                    decimals: Some(0),
+                    print_supply: Some(PrintSupply::Zero),
+                })
+                .unwrap()
+                .instruction(),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer_pubkey),
+            &[&context.payer, mint],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn mint_to_payer(
+        context: &mut ProgramTestContext,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let payer_pubkey = context.payer.pubkey();
+        let token =
+            get_associated_token_address_with_program_id(&payer_pubkey, mint, &spl_token_2022::ID);
+
+        let mint_ix = MintBuilder::new()
+            .token(token)
+            .token_owner(payer_pubkey)
+            .metadata(find_metadata_account(mint).0)
+            .mint(*mint)
+            .payer(payer_pubkey)
+            .authority(payer_pubkey)
+            .spl_token_program(spl_token_2022::ID)
+            .build(MintArgs::V1 {
+                amount,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mint_ix],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await?;
+
+        Ok(token)
+    }
+
+    async fn transfer(
+        context: &mut ProgramTestContext,
+        mint: &Pubkey,
+        token: &Pubkey,
+        destination_owner: &Pubkey,
+        amount: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let payer_pubkey = context.payer.pubkey();
+        let destination = get_associated_token_address_with_program_id(
+            destination_owner,
+            mint,
+            &spl_token_2022::ID,
+        );
+
+        let transfer_ix = TransferBuilder::new()
+            .authority(payer_pubkey)
+            .token_owner(payer_pubkey)
+            .token(*token)
+            .destination_owner(*destination_owner)
+            .destination(destination)
+            .metadata(find_metadata_account(mint).0)
+            .payer(payer_pubkey)
+            .mint(*mint)
+            .spl_token_program(spl_token_2022::ID)
+            .build(TransferArgs::V1 {
+                amount,
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[transfer_ix],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await?;
+
+        Ok(destination)
+    }
+
+    async fn token_amount(context: &mut ProgramTestContext, token: &Pubkey) -> u64 {
+        let account = get_account(context, token).await;
+        assert_eq!(account.owner, spl_token_2022::ID);
+
+        StateWithExtensions::<Account>::unpack(&account.data)
+            .unwrap()
+            .base
+            .amount
+    }
+
+    #[tokio::test]
+    async fn create_mint_and_transfer() {
+        let mut context = program_test().start_with_context().await;
+        let mint = Keypair::new();
+
+        create_fungible(&mut context, &mint, None).await.unwrap();
+
+        let account = get_account(&mut context, &mint.pubkey()).await;
+        assert_eq!(account.owner, spl_token_2022::ID);
+        assert_eq!(account.data.len(), spl_token_2022::state::Mint::LEN);
+
+        let token = mint_to_payer(&mut context, &mint.pubkey(), 10)
+            .await
+            .unwrap();
+        assert_eq!(token_amount(&mut context, &token).await, 10);
+
+        let destination_owner = Keypair::new().pubkey();
+        let destination = transfer(&mut context, &mint.pubkey(), &token, &destination_owner, 4)
+            .await
+            .unwrap();
+
+        assert_eq!(token_amount(&mut context, &token).await, 6);
+        assert_eq!(token_amount(&mut context, &destination).await, 4);
+    }
+
+    #[tokio::test]
+    async fn transfer_with_transfer_fee() {
+        let mut context = program_test().start_with_context().await;
+        let mint = Keypair::new();
+
+        // 1% transfer fee
+        create_fungible(&mut context, &mint, Some(100))
+            .await
+            .unwrap();
+
+        let token = mint_to_payer(&mut context, &mint.pubkey(), 1_000)
+            .await
+            .unwrap();
+
+        let destination_owner = Keypair::new().pubkey();
+        let destination = transfer(
+            &mut context,
+            &mint.pubkey(),
+            &token,
+            &destination_owner,
+            1_000,
+        )
+        .await
+        .unwrap();
+
+        // the fee is withheld on the destination account
+        assert_eq!(token_amount(&mut context, &token).await, 0);
+        assert_eq!(token_amount(&mut context, &destination).await, 990);
+    }
+}
@@ -198,6 +198,94 @@ mod standard_transfer {
         assert_eq!(token_account.amount, transfer_amount);
     }
 
+    #[tokio::test]
+    async fn transfer_programmable_fungible_asset() {
+        let mut context = program_test().start_with_context().await;
+
+        let mint_amount = 100;
+        let transfer_amount = 40;
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(
+            &mut context,
+            TokenStandard::ProgrammableFungibleAsset,
+            None,
+            None,
+            mint_amount,
+        )
+        .await
+        .unwrap();
+
+        let source_token = da.token.unwrap();
+        let source_token_record = da.token_record.unwrap();
+
+        let destination_owner = Pubkey::new_unique();
+        let destination_token = get_associated_token_address(&destination_owner, &da.mint.pubkey());
+        airdrop(&mut context, &destination_owner, LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+
+        let authority = &Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let args = TransferArgs::V1 {
+            authorization_data: None,
+            amount: transfer_amount,
+        };
+
+        let params = TransferParams {
+            context: &mut context,
+            authority,
+            source_owner: &authority.pubkey(),
+            destination_owner,
+            destination_token: None,
+            authorization_rules: None,
+            payer: authority,
+            args,
+        };
+
+        da.transfer(params).await.unwrap();
+
+        // both token accounts hold tokens and remain frozen
+
+        let destination_account = spl_token::state::Account::unpack(
+            &context
+                .banks_client
+                .get_account(destination_token)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+
+        assert_eq!(destination_account.amount, transfer_amount);
+        assert!(destination_account.is_frozen());
+
+        let source_account = spl_token::state::Account::unpack(
+            &context
+                .banks_client
+                .get_account(source_token)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+
+        assert_eq!(source_account.amount, mint_amount - transfer_amount);
+        assert!(source_account.is_frozen());
+
+        // the source token record is kept since the account still holds tokens
+
+        let source_token_record = context
+            .banks_client
+            .get_account(source_token_record)
+            .await
+            .unwrap();
+
+        assert!(source_token_record.is_some());
+    }
+
     #[tokio::test]
     async fn transfer_with_delegate() {
         let mut context = program_test().start_with_context().await;
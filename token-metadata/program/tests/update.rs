@@ -4,15 +4,16 @@ pub mod utils;
 use mpl_token_metadata::{
     error::MetadataError,
     instruction::{
-        builders::UpdateBuilder, CollectionToggle, DelegateArgs, InstructionBuilder, RuleSetToggle,
-        TransferArgs, UpdateArgs,
+        builders::UpdateBuilder, CollectionToggle, DelegateArgs, InstructionBuilder,
+        MetadataDelegateRole, PartialData, RuleSetToggle, TransferArgs, UpdateArgs,
     },
+    pda::find_metadata_history_account,
     state::{Collection, Creator, Data, ProgrammableConfig, TokenStandard},
     state::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH},
     utils::puffed_out_string,
 };
 use num_traits::FromPrimitive;
-use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program::{instruction::AccountMeta, program_pack::Pack, pubkey::Pubkey};
 use solana_program_test::*;
 use solana_sdk::{
     instruction::InstructionError,
@@ -3608,4 +3609,504 @@ mod update {
             panic!("Missing rule set programmable config");
         }
     }
+
+    #[tokio::test]
+    async fn success_batch_update_by_update_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut assets = Vec::new();
+
+        for _ in 0..3 {
+            let mut da = DigitalAsset::new();
+            da.create(context, TokenStandard::NonFungible, None)
+                .await
+                .unwrap();
+            assets.push(da);
+        }
+
+        let items = (0..assets.len())
+            .map(|index| {
+                (
+                    (index * 2) as u8,
+                    PartialData {
+                        uri: Some(format!("https://new.digital.asset.org/{index}")),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+
+        let args = UpdateArgs::BatchV1 {
+            items,
+            authorization_data: None,
+        };
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(update_authority.pubkey())
+            .metadata(assets[0].metadata)
+            .mint(assets[0].mint.pubkey())
+            .payer(update_authority.pubkey());
+
+        let mut update_ix = builder.build(args).unwrap().instruction();
+        // each item is followed by its metadata history account
+        for da in &assets {
+            update_ix
+                .accounts
+                .push(AccountMeta::new(da.metadata, false));
+            update_ix.accounts.push(AccountMeta::new(
+                find_metadata_history_account(&da.mint.pubkey()).0,
+                false,
+            ));
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        // checks that only the uri was updated
+        for (index, da) in assets.iter().enumerate() {
+            let metadata = da.get_metadata(context).await;
+
+            assert_eq!(
+                metadata.data.uri,
+                puffed_out_string(
+                    &format!("https://new.digital.asset.org/{index}"),
+                    MAX_URI_LENGTH
+                )
+            );
+            assert_eq!(
+                metadata.data.name,
+                puffed_out_string(DEFAULT_NAME, MAX_NAME_LENGTH)
+            );
+            assert_eq!(metadata.data.creators.unwrap().len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_batch_update_out_of_data_item_delegate_scope() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let mut other_da = DigitalAsset::new();
+        other_da
+            .create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        // delegate is only valid for the first asset
+        let delegate = Keypair::new();
+        delegate.airdrop(context, 1_000_000_000).await.unwrap();
+
+        let delegate_record = da
+            .delegate(
+                context,
+                update_authority,
+                delegate.pubkey(),
+                DelegateArgs::DataItemV1 {
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        let args = UpdateArgs::BatchV1 {
+            items: vec![
+                (
+                    0,
+                    PartialData {
+                        uri: Some("https://new.digital.asset.org".to_string()),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    2,
+                    PartialData {
+                        uri: Some("https://new.digital.asset.org".to_string()),
+                        ..Default::default()
+                    },
+                ),
+            ],
+            authorization_data: None,
+        };
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(delegate.pubkey())
+            .delegate_record(delegate_record)
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .payer(delegate.pubkey());
+
+        let mut update_ix = builder.build(args).unwrap().instruction();
+        for item in [&da, &other_da] {
+            update_ix
+                .accounts
+                .push(AccountMeta::new(item.metadata, false));
+            update_ix.accounts.push(AccountMeta::new(
+                find_metadata_history_account(&item.mint.pubkey()).0,
+                false,
+            ));
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&delegate.pubkey()),
+            &[&delegate],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidBatchUpdateItem);
+
+        // the whole batch is rejected
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(
+            metadata.data.uri,
+            puffed_out_string(DEFAULT_URI, MAX_URI_LENGTH)
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_batch_update_unverified_collection_item() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut collection_parent_da = DigitalAsset::new();
+        collection_parent_da
+            .create_and_mint_collection_parent(
+                context,
+                TokenStandard::NonFungible,
+                None,
+                None,
+                1,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        // the item points to the collection, but it is not verified
+        let mut da = DigitalAsset::new();
+        da.create_and_mint_item_with_collection(
+            context,
+            TokenStandard::NonFungible,
+            None,
+            None,
+            1,
+            Some(Collection {
+                key: collection_parent_da.mint.pubkey(),
+                verified: false,
+            }),
+        )
+        .await
+        .unwrap();
+
+        let delegate = Keypair::new();
+        delegate.airdrop(context, 1_000_000_000).await.unwrap();
+
+        let delegate_record = collection_parent_da
+            .delegate(
+                context,
+                update_authority,
+                delegate.pubkey(),
+                DelegateArgs::DataV1 {
+                    authorization_data: None,
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        let args = UpdateArgs::BatchV1 {
+            items: vec![(
+                0,
+                PartialData {
+                    uri: Some("https://new.digital.asset.org".to_string()),
+                    ..Default::default()
+                },
+            )],
+            authorization_data: None,
+        };
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(delegate.pubkey())
+            .delegate_record(delegate_record)
+            .metadata(collection_parent_da.metadata)
+            .mint(collection_parent_da.mint.pubkey())
+            .payer(delegate.pubkey());
+
+        let mut update_ix = builder.build(args).unwrap().instruction();
+        update_ix
+            .accounts
+            .push(AccountMeta::new(da.metadata, false));
+        update_ix.accounts.push(AccountMeta::new(
+            find_metadata_history_account(&da.mint.pubkey()).0,
+            false,
+        ));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&delegate.pubkey()),
+            &[&delegate],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidBatchUpdateItem);
+    }
+
+    #[tokio::test]
+    async fn fail_batch_update_with_wrong_history_account() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        let args = UpdateArgs::BatchV1 {
+            items: vec![(
+                0,
+                PartialData {
+                    uri: Some("https://new.digital.asset.org".to_string()),
+                    ..Default::default()
+                },
+            )],
+            authorization_data: None,
+        };
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(update_authority.pubkey())
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .payer(update_authority.pubkey());
+
+        // the history account must be derived from the mint of the item
+        let mut update_ix = builder.build(args).unwrap().instruction();
+        update_ix
+            .accounts
+            .push(AccountMeta::new(da.metadata, false));
+        update_ix.accounts.push(AccountMeta::new(
+            find_metadata_history_account(&Pubkey::new_unique()).0,
+            false,
+        ));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::KeyMismatch);
+    }
+
+    #[tokio::test]
+    async fn success_batch_update_programmable_item() {
+        let mut program_test = ProgramTest::new("mpl_token_metadata", mpl_token_metadata::ID, None);
+        program_test.add_program("mpl_token_auth_rules", mpl_token_auth_rules::ID, None);
+        let context = &mut program_test.start_with_context().await;
+
+        let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let (authorization_rules, auth_data) =
+            create_default_metaplex_rule_set(context, authority, false).await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create_and_mint(
+            context,
+            TokenStandard::ProgrammableNonFungible,
+            Some(authorization_rules),
+            Some(auth_data),
+            1,
+        )
+        .await
+        .unwrap();
+
+        let args = UpdateArgs::BatchV1 {
+            items: vec![(
+                0,
+                PartialData {
+                    uri: Some("https://new.digital.asset.org".to_string()),
+                    ..Default::default()
+                },
+            )],
+            authorization_data: None,
+        };
+
+        let mut builder = UpdateBuilder::new();
+        builder
+            .authority(update_authority.pubkey())
+            .metadata(da.metadata)
+            .mint(da.mint.pubkey())
+            .authorization_rules(authorization_rules)
+            .payer(update_authority.pubkey());
+
+        // the mint account of a programmable item is required to validate the rule set
+        let mut update_ix = builder.build(args).unwrap().instruction();
+        update_ix
+            .accounts
+            .push(AccountMeta::new(da.metadata, false));
+        update_ix
+            .accounts
+            .push(AccountMeta::new_readonly(mpl_token_metadata::ID, false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix.clone()],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err();
+
+        assert_transport_error!(
+            err,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::NotEnoughAccountKeys
+            ))
+        );
+
+        update_ix
+            .accounts
+            .push(AccountMeta::new_readonly(da.mint.pubkey(), false));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&update_authority.pubkey()),
+            &[&update_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(
+            metadata.data.uri,
+            puffed_out_string("https://new.digital.asset.org", MAX_URI_LENGTH)
+        );
+    }
+
+    #[tokio::test]
+    async fn fail_update_by_exhausted_data_delegate() {
+        let context = &mut program_test().start_with_context().await;
+
+        let update_authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let mut da = DigitalAsset::new();
+        da.create(context, TokenStandard::NonFungible, None)
+            .await
+            .unwrap();
+
+        // Create a metadata delegate that can only be used once.
+        let delegate = Keypair::new();
+        delegate.airdrop(context, 1_000_000_000).await.unwrap();
+        let delegate_args = DelegateArgs::LimitedMetadataV1 {
+            role: MetadataDelegateRole::Data,
+            expires_at: None,
+            max_uses: Some(1),
+            authorization_data: None,
+        };
+        let delegate_record = da
+            .delegate(context, update_authority, delegate.pubkey(), delegate_args)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let metadata = da.get_metadata(context).await;
+
+        let mut results = Vec::new();
+
+        for uri in [
+            "https://first.digital.asset.org",
+            "https://second.digital.asset.org",
+        ] {
+            let mut data = metadata.data.clone();
+            data.uri = uri.to_string();
+
+            let args = UpdateArgs::AsDataDelegateV2 {
+                data: Some(data),
+                authorization_data: None,
+            };
+
+            let mut builder = UpdateBuilder::new();
+            builder
+                .authority(delegate.pubkey())
+                .delegate_record(delegate_record)
+                .metadata(da.metadata)
+                .mint(da.mint.pubkey())
+                .payer(delegate.pubkey());
+
+            if let Some(edition) = da.edition {
+                builder.edition(edition);
+            }
+
+            let update_ix = builder.build(args).unwrap().instruction();
+
+            let tx = Transaction::new_signed_with_payer(
+                &[update_ix],
+                Some(&delegate.pubkey()),
+                &[&delegate],
+                context.last_blockhash,
+            );
+
+            results.push(context.banks_client.process_transaction(tx).await);
+        }
+
+        // The first update uses the delegate, the second one fails.
+        let mut results = results.into_iter();
+        results.next().unwrap().unwrap();
+
+        let err = results.next().unwrap().unwrap_err();
+        assert_custom_error!(err, MetadataError::DelegateUsesExhausted);
+
+        // Only the first update was applied.
+        let metadata = da.get_metadata(context).await;
+        assert_eq!(
+            metadata.data.uri,
+            puffed_out_string("https://first.digital.asset.org", MAX_URI_LENGTH)
+        );
+    }
 }
@@ -0,0 +1,284 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{AcceptUpdateAuthorityBuilder, ProposeUpdateAuthorityBuilder},
+        InstructionBuilder, ProposeUpdateAuthorityArgs, VerificationArgs,
+    },
+    pda::find_pending_update_authority_account,
+    state::{Collection, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod update_authority_handover {
+    use super::*;
+
+    async fn propose(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        new_update_authority: Pubkey,
+        collection: bool,
+    ) -> Result<(), BanksClientError> {
+        let (pending_update_authority, _) =
+            find_pending_update_authority_account(&asset.mint.pubkey());
+
+        let propose_ix = ProposeUpdateAuthorityBuilder::new()
+            .pending_update_authority(pending_update_authority)
+            .metadata(asset.metadata)
+            .authority(context.payer.pubkey())
+            .payer(context.payer.pubkey())
+            .build(ProposeUpdateAuthorityArgs::V1 {
+                new_update_authority,
+                collection,
+            })
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[propose_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn accept(
+        context: &mut ProgramTestContext,
+        mint: &Pubkey,
+        metadata: &[Pubkey],
+        new_update_authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let (pending_update_authority, _) = find_pending_update_authority_account(mint);
+
+        let mut accept_ix = AcceptUpdateAuthorityBuilder::new()
+            .pending_update_authority(pending_update_authority)
+            .metadata(metadata[0])
+            .new_update_authority(new_update_authority.pubkey())
+            .build()
+            .unwrap()
+            .instruction();
+
+        // additional collection items
+        accept_ix.accounts.extend(
+            metadata[1..]
+                .iter()
+                .map(|metadata| AccountMeta::new(*metadata, false)),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[accept_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, new_update_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn accept_proposed_update_authority() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let new_update_authority = Keypair::new();
+        airdrop(context, &new_update_authority.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        propose(context, &asset, new_update_authority.pubkey(), false)
+            .await
+            .unwrap();
+
+        // the update authority does not change until the handover is accepted
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.update_authority, context.payer.pubkey());
+
+        accept(
+            context,
+            &asset.mint.pubkey(),
+            &[asset.metadata],
+            &new_update_authority,
+        )
+        .await
+        .unwrap();
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+
+        // the pending account is closed
+        let (pending_update_authority, _) =
+            find_pending_update_authority_account(&asset.mint.pubkey());
+        let account = context
+            .banks_client
+            .get_account(pending_update_authority)
+            .await
+            .unwrap();
+        assert!(account.is_none());
+    }
+
+    #[tokio::test]
+    async fn fail_accept_with_wrong_signer() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        propose(context, &asset, Keypair::new().pubkey(), false)
+            .await
+            .unwrap();
+
+        let fake_authority = Keypair::new();
+
+        let error = accept(
+            context,
+            &asset.mint.pubkey(),
+            &[asset.metadata],
+            &fake_authority,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::InvalidPendingUpdateAuthority);
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.update_authority, context.payer.pubkey());
+    }
+
+    #[tokio::test]
+    async fn accept_collection_handover() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::new();
+        collection
+            .create_and_mint_collection_parent(
+                context,
+                TokenStandard::NonFungible,
+                None,
+                None,
+                1,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        let mut items = Vec::new();
+
+        for _ in 0..2 {
+            let mut item = DigitalAsset::new();
+            item.create_and_mint_item_with_collection(
+                context,
+                TokenStandard::NonFungible,
+                None,
+                None,
+                1,
+                Some(Collection {
+                    key: collection.mint.pubkey(),
+                    verified: false,
+                }),
+            )
+            .await
+            .unwrap();
+
+            let payer = context.payer.dirty_clone();
+            item.verify(
+                context,
+                payer,
+                VerificationArgs::CollectionV1,
+                None,
+                None,
+                Some(collection.mint.pubkey()),
+                Some(collection.metadata),
+                collection.edition,
+            )
+            .await
+            .unwrap();
+
+            items.push(item);
+        }
+
+        let new_update_authority = Keypair::new();
+        airdrop(context, &new_update_authority.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        propose(context, &collection, new_update_authority.pubkey(), true)
+            .await
+            .unwrap();
+
+        // the collection parent is handed over last, which closes the pending account
+        accept(
+            context,
+            &collection.mint.pubkey(),
+            &[items[0].metadata, items[1].metadata, collection.metadata],
+            &new_update_authority,
+        )
+        .await
+        .unwrap();
+
+        for asset in items.iter().chain(std::iter::once(&collection)) {
+            let metadata = asset.get_metadata(context).await;
+            assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+        }
+    }
+
+    #[tokio::test]
+    async fn fail_accept_item_outside_collection() {
+        let context = &mut program_test().start_with_context().await;
+
+        let mut collection = DigitalAsset::new();
+        collection
+            .create_and_mint_collection_parent(
+                context,
+                TokenStandard::NonFungible,
+                None,
+                None,
+                1,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_and_mint(context, TokenStandard::NonFungible, None, None, 1)
+            .await
+            .unwrap();
+
+        let new_update_authority = Keypair::new();
+
+        propose(context, &collection, new_update_authority.pubkey(), true)
+            .await
+            .unwrap();
+
+        let error = accept(
+            context,
+            &collection.mint.pubkey(),
+            &[asset.metadata],
+            &new_update_authority,
+        )
+        .await
+        .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::NotAMemberOfCollection);
+    }
+}
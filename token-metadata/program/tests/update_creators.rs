@@ -0,0 +1,284 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{EnableMetadataHistoryBuilder, UpdateCreatorsBuilder},
+        sign_metadata, EnableMetadataHistoryArgs, InstructionBuilder, UpdateCreatorsArgs,
+    },
+    pda::find_metadata_history_account,
+    state::{Creator, MetadataHistory, PrintSupply, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_program::instruction::AccountMeta;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod update_creators {
+    use super::*;
+
+    /// Creates an asset with the update authority and a collaborator as verified creators.
+    async fn create_asset_with_verified_creators(
+        context: &mut ProgramTestContext,
+        collaborator: &Keypair,
+    ) -> DigitalAsset {
+        let creators = vec![
+            Creator {
+                address: context.payer.pubkey(),
+                share: 50,
+                verified: true,
+            },
+            Creator {
+                address: collaborator.pubkey(),
+                share: 50,
+                verified: false,
+            },
+        ];
+
+        let mut asset = DigitalAsset::new();
+        asset
+            .create_advanced(
+                context,
+                TokenStandard::NonFungible,
+                String::from(DEFAULT_NAME),
+                String::from(DEFAULT_SYMBOL),
+                String::from(DEFAULT_URI),
+                500,
+                Some(creators),
+                None,
+                None,
+                None,
+                PrintSupply::Zero,
+            )
+            .await
+            .unwrap();
+
+        let sign_ix = sign_metadata(
+            mpl_token_metadata::ID,
+            asset.metadata,
+            collaborator.pubkey(),
+        );
+        let sign_tx = Transaction::new_signed_with_payer(
+            &[sign_ix],
+            Some(&context.payer.pubkey()),
+            &[collaborator, &context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(sign_tx)
+            .await
+            .unwrap();
+
+        asset
+    }
+
+    async fn update_creators(
+        context: &mut ProgramTestContext,
+        asset: &DigitalAsset,
+        creators: Vec<Creator>,
+        creator_signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let (metadata_history, _) = find_metadata_history_account(&asset.mint.pubkey());
+
+        let mut update_ix = UpdateCreatorsBuilder::new()
+            .metadata(asset.metadata)
+            .authority(context.payer.pubkey())
+            .metadata_history(metadata_history)
+            .build(UpdateCreatorsArgs::V1 { creators })
+            .unwrap()
+            .instruction();
+
+        for signer in creator_signers {
+            update_ix
+                .accounts
+                .push(AccountMeta::new_readonly(signer.pubkey(), true));
+        }
+
+        let mut signers = vec![&context.payer];
+        signers.extend_from_slice(creator_signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&context.payer.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn success_decrease_share_with_creator_signature() {
+        let context = &mut program_test().start_with_context().await;
+
+        let collaborator = Keypair::new();
+        let asset = create_asset_with_verified_creators(context, &collaborator).await;
+
+        let new_creators = vec![
+            Creator {
+                address: context.payer.pubkey(),
+                share: 70,
+                verified: true,
+            },
+            Creator {
+                address: collaborator.pubkey(),
+                share: 30,
+                verified: true,
+            },
+        ];
+
+        update_creators(context, &asset, new_creators.clone(), &[&collaborator])
+            .await
+            .unwrap();
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.data.creators, Some(new_creators));
+    }
+
+    #[tokio::test]
+    async fn success_increase_share_without_creator_signature() {
+        let context = &mut program_test().start_with_context().await;
+
+        let collaborator = Keypair::new();
+        let asset = create_asset_with_verified_creators(context, &collaborator).await;
+
+        // only the update authority share decreases, which signs as the authority
+        let new_creators = vec![
+            Creator {
+                address: context.payer.pubkey(),
+                share: 40,
+                verified: true,
+            },
+            Creator {
+                address: collaborator.pubkey(),
+                share: 60,
+                verified: true,
+            },
+        ];
+
+        update_creators(context, &asset, new_creators.clone(), &[])
+            .await
+            .unwrap();
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.data.creators, Some(new_creators));
+    }
+
+    #[tokio::test]
+    async fn success_remove_creator_with_history() {
+        let context = &mut program_test().start_with_context().await;
+
+        let collaborator = Keypair::new();
+        let asset = create_asset_with_verified_creators(context, &collaborator).await;
+
+        let (metadata_history, _) = find_metadata_history_account(&asset.mint.pubkey());
+        let enable_ix = EnableMetadataHistoryBuilder::new()
+            .metadata_history(metadata_history)
+            .metadata(asset.metadata)
+            .authority(context.payer.pubkey())
+            .payer(context.payer.pubkey())
+            .build(EnableMetadataHistoryArgs::V1 { capacity: 2 })
+            .unwrap()
+            .instruction();
+        let enable_tx = Transaction::new_signed_with_payer(
+            &[enable_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(enable_tx)
+            .await
+            .unwrap();
+
+        let previous = asset.get_metadata(context).await;
+
+        // the shorter creators array must not leave stale bytes behind
+        let new_creators = vec![Creator {
+            address: context.payer.pubkey(),
+            share: 100,
+            verified: true,
+        }];
+
+        update_creators(context, &asset, new_creators.clone(), &[&collaborator])
+            .await
+            .unwrap();
+
+        let metadata = asset.get_metadata(context).await;
+        assert_eq!(metadata.data.creators, Some(new_creators));
+        assert_eq!(
+            metadata.primary_sale_happened,
+            previous.primary_sale_happened
+        );
+        assert_eq!(metadata.is_mutable, previous.is_mutable);
+        assert_eq!(metadata.token_standard, previous.token_standard);
+        assert_eq!(metadata.programmable_config, previous.programmable_config);
+
+        let history_account = get_account(context, &metadata_history).await;
+        let history = MetadataHistory::from_bytes(&history_account.data).unwrap();
+        assert_eq!(history.entries.len(), 2);
+        assert!(history.find_snapshot(&metadata.data).is_some());
+    }
+
+    #[tokio::test]
+    async fn fail_decrease_share_without_creator_signature() {
+        let context = &mut program_test().start_with_context().await;
+
+        let collaborator = Keypair::new();
+        let asset = create_asset_with_verified_creators(context, &collaborator).await;
+
+        let new_creators = vec![
+            Creator {
+                address: context.payer.pubkey(),
+                share: 70,
+                verified: true,
+            },
+            Creator {
+                address: collaborator.pubkey(),
+                share: 30,
+                verified: true,
+            },
+        ];
+
+        let err = update_creators(context, &asset, new_creators, &[])
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::MissingCreatorSignature);
+    }
+
+    #[tokio::test]
+    async fn fail_update_creators_with_invalid_total() {
+        let context = &mut program_test().start_with_context().await;
+
+        let collaborator = Keypair::new();
+        let asset = create_asset_with_verified_creators(context, &collaborator).await;
+
+        let new_creators = vec![
+            Creator {
+                address: context.payer.pubkey(),
+                share: 50,
+                verified: true,
+            },
+            Creator {
+                address: collaborator.pubkey(),
+                share: 60,
+                verified: true,
+            },
+        ];
+
+        let err = update_creators(context, &asset, new_creators, &[])
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::ShareTotalMustBe100);
+    }
+}
@@ -0,0 +1,266 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction::{
+        builders::{SetUseConfigBuilder, UseBuilder},
+        utilize, InstructionBuilder, SetUseConfigArgs, UseArgs,
+    },
+    pda::find_use_config_account,
+    state::{UseConfig, UseMethod, Uses},
+    ID,
+};
+use num_traits::FromPrimitive;
+use solana_program::{borsh::try_from_slice_unchecked, instruction::AccountMeta};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod use_config {
+    use super::*;
+
+    async fn create_refill_asset(context: &mut ProgramTestContext, total: u64) -> Metadata {
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v3(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                true,
+                None,
+                Some(Uses {
+                    use_method: UseMethod::Refill,
+                    total,
+                    remaining: total,
+                }),
+                None,
+            )
+            .await
+            .unwrap();
+
+        test_metadata
+    }
+
+    async fn set_use_config(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        args: SetUseConfigArgs,
+    ) -> Result<(), BanksClientError> {
+        let (use_config, _) = find_use_config_account(&test_metadata.mint.pubkey());
+
+        let set_ix = SetUseConfigBuilder::new()
+            .use_config(use_config)
+            .metadata(test_metadata.pubkey)
+            .authority(context.payer.pubkey())
+            .payer(context.payer.pubkey())
+            .build(args)
+            .unwrap()
+            .instruction();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn use_asset(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        with_use_config: bool,
+    ) -> Result<(), BanksClientError> {
+        let mut use_ix = UseBuilder::new()
+            .authority(context.payer.pubkey())
+            .token(test_metadata.token.pubkey())
+            .mint(test_metadata.mint.pubkey())
+            .metadata(test_metadata.pubkey)
+            .payer(context.payer.pubkey())
+            .spl_token_program(spl_token::ID)
+            .build(UseArgs::V1 {
+                authorization_data: None,
+            })
+            .unwrap()
+            .instruction();
+
+        if with_use_config {
+            let (use_config, _) = find_use_config_account(&test_metadata.mint.pubkey());
+            use_ix.accounts.push(AccountMeta::new(use_config, false));
+        }
+
+        // each use needs a fresh blockhash, since the transactions are identical
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[use_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[tokio::test]
+    async fn fail_use_refill_asset_without_use_config() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_refill_asset(&mut context, 2).await;
+
+        let error = use_asset(&mut context, &test_metadata, false)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::MissingUseConfig);
+    }
+
+    #[tokio::test]
+    async fn fail_use_during_cooldown() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_refill_asset(&mut context, 2).await;
+
+        set_use_config(
+            &mut context,
+            &test_metadata,
+            SetUseConfigArgs::V1 {
+                refill_period: 0,
+                refill_amount: 0,
+                min_use_interval: 1_000_000,
+            },
+        )
+        .await
+        .unwrap();
+
+        use_asset(&mut context, &test_metadata, true).await.unwrap();
+
+        let error = use_asset(&mut context, &test_metadata, true)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::UseCooldownActive);
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 1);
+    }
+
+    #[tokio::test]
+    async fn use_refills_after_period() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_refill_asset(&mut context, 1).await;
+
+        set_use_config(
+            &mut context,
+            &test_metadata,
+            SetUseConfigArgs::V1 {
+                refill_period: 60,
+                refill_amount: 1,
+                min_use_interval: 0,
+            },
+        )
+        .await
+        .unwrap();
+
+        use_asset(&mut context, &test_metadata, true).await.unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 0);
+
+        // the use is only available again after the refill period
+        context.warp_to_slot(1_000).unwrap();
+
+        use_asset(&mut context, &test_metadata, true).await.unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 0);
+
+        let (use_config, _) = find_use_config_account(&test_metadata.mint.pubkey());
+        let account = get_account(&mut context, &use_config).await;
+        let use_config: UseConfig = try_from_slice_unchecked(&account.data).unwrap();
+        assert!(use_config.last_used_at > 0);
+    }
+
+    #[tokio::test]
+    async fn utilize_refill_asset_with_use_config() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_refill_asset(&mut context, 2).await;
+
+        set_use_config(
+            &mut context,
+            &test_metadata,
+            SetUseConfigArgs::V1 {
+                refill_period: 60,
+                refill_amount: 1,
+                min_use_interval: 0,
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut utilize_ix = utilize(
+            ID,
+            test_metadata.pubkey,
+            test_metadata.token.pubkey(),
+            test_metadata.mint.pubkey(),
+            None,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            None,
+            1,
+        );
+        // the use authority record and burner are omitted
+        let (use_config, _) = find_use_config_account(&test_metadata.mint.pubkey());
+        utilize_ix.accounts.extend([
+            AccountMeta::new_readonly(ID, false),
+            AccountMeta::new_readonly(ID, false),
+            AccountMeta::new(use_config, false),
+        ]);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[utilize_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.uses.unwrap().remaining, 1);
+
+        let account = get_account(&mut context, &use_config).await;
+        let use_config: UseConfig = try_from_slice_unchecked(&account.data).unwrap();
+        assert!(use_config.last_used_at > 0);
+    }
+
+    #[tokio::test]
+    async fn fail_set_use_config_after_first_use() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = create_refill_asset(&mut context, 2).await;
+
+        let args = SetUseConfigArgs::V1 {
+            refill_period: 60,
+            refill_amount: 1,
+            min_use_interval: 0,
+        };
+
+        set_use_config(&mut context, &test_metadata, args.clone())
+            .await
+            .unwrap();
+
+        use_asset(&mut context, &test_metadata, true).await.unwrap();
+
+        let error = set_use_config(&mut context, &test_metadata, args)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(error, MetadataError::CannotChangeUsesAfterFirstUse);
+    }
+}
@@ -133,7 +133,12 @@ impl DigitalAsset {
             builder.edition(edition);
         }
 
-        if token_standard == TokenStandard::ProgrammableNonFungible {
+        if matches!(
+            token_standard,
+            TokenStandard::ProgrammableNonFungible
+                | TokenStandard::ProgrammableFungibleAsset
+                | TokenStandard::ProgrammableNonTransferable
+        ) {
             builder.token_record(self.token_record.unwrap());
         }
 
@@ -141,6 +146,14 @@ impl DigitalAsset {
             builder.collection_metadata(collection_metadata);
         }
 
+        if let Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        }) = md.programmable_config
+        {
+            builder.authorization_rules(rule_set);
+            builder.authorization_rules_program(mpl_token_auth_rules::ID);
+        }
+
         let burn_ix = builder.build(args).unwrap().instruction();
 
         let transaction = Transaction::new_signed_with_payer(
@@ -312,7 +325,10 @@ impl DigitalAsset {
             .update_authority_as_signer(true);
 
         let edition = match token_standard {
-            TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible => {
+            TokenStandard::NonFungible
+            | TokenStandard::ProgrammableNonFungible
+            | TokenStandard::ProgrammableFungibleAsset
+            | TokenStandard::ProgrammableNonTransferable => {
                 // master edition PDA address
                 let edition_seeds = &[
                     PREFIX.as_bytes(),
@@ -371,7 +387,7 @@ impl DigitalAsset {
 
         let (token_record, _) = find_token_record_account(&self.mint.pubkey(), &token);
 
-        let token_record_opt = if self.is_pnft(context).await {
+        let token_record_opt = if self.is_programmable(context).await {
             Some(token_record)
         } else {
             None
@@ -578,7 +594,8 @@ impl DigitalAsset {
             | DelegateArgs::TransferV1 { .. }
             | DelegateArgs::UtilityV1 { .. }
             | DelegateArgs::StakingV1 { .. }
-            | DelegateArgs::LockedTransferV1 { .. } => {
+            | DelegateArgs::LockedTransferV1 { .. }
+            | DelegateArgs::RentalV1 { .. } => {
                 let (token_record, _) =
                     find_token_record_account(&self.mint.pubkey(), &self.token.unwrap());
                 builder.token_record(token_record);
@@ -657,6 +674,36 @@ impl DigitalAsset {
                 builder.delegate_record(delegate_record);
                 delegate_or_token_record = Some(delegate_record);
             }
+            DelegateArgs::AttributesV1 { .. } => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::Attributes,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+                delegate_or_token_record = Some(delegate_record);
+            }
+            DelegateArgs::CollectionLockV1 { .. } => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::CollectionLock,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+                delegate_or_token_record = Some(delegate_record);
+            }
+            DelegateArgs::LimitedMetadataV1 { role, .. } => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    role,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+                delegate_or_token_record = Some(delegate_record);
+            }
         }
 
         if let Some(edition) = self.edition {
@@ -794,7 +841,8 @@ impl DigitalAsset {
             | RevokeArgs::UtilityV1
             | RevokeArgs::StakingV1
             | RevokeArgs::LockedTransferV1
-            | RevokeArgs::MigrationV1 => {
+            | RevokeArgs::MigrationV1
+            | RevokeArgs::RentalV1 => {
                 let (token_record, _) =
                     find_token_record_account(&self.mint.pubkey(), &self.token.unwrap());
                 builder.token_record(token_record);
@@ -866,6 +914,26 @@ impl DigitalAsset {
                 );
                 builder.delegate_record(delegate_record);
             }
+
+            RevokeArgs::AttributesV1 => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::Attributes,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
+
+            RevokeArgs::CollectionLockV1 => {
+                let (delegate_record, _) = find_metadata_delegate_record_account(
+                    &self.mint.pubkey(),
+                    MetadataDelegateRole::CollectionLock,
+                    &payer.pubkey(),
+                    &delegate,
+                );
+                builder.delegate_record(delegate_record);
+            }
         }
 
         if let Some(edition) = self.edition {
@@ -937,7 +1005,7 @@ impl DigitalAsset {
         // This can be optional for non pNFTs but always include it for now.
         let (destination_token_record, _bump) =
             find_token_record_account(&self.mint.pubkey(), &destination_token);
-        let destination_token_record_opt = if self.is_pnft(context).await {
+        let destination_token_record_opt = if self.is_programmable(context).await {
             builder.destination_token_record(destination_token_record);
             Some(destination_token_record)
         } else {
@@ -998,6 +1066,14 @@ impl DigitalAsset {
             builder.token(token);
         }
 
+        if let Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        }) = self.get_metadata(context).await.programmable_config
+        {
+            builder.authorization_rules(rule_set);
+            builder.authorization_rules_program(mpl_token_auth_rules::ID);
+        }
+
         let utility_ix = builder
             .build(LockArgs::V1 {
                 authorization_data: None,
@@ -1042,6 +1118,14 @@ impl DigitalAsset {
             builder.token(token);
         }
 
+        if let Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        }) = self.get_metadata(context).await.programmable_config
+        {
+            builder.authorization_rules(rule_set);
+            builder.authorization_rules_program(mpl_token_auth_rules::ID);
+        }
+
         let unlock_ix = builder
             .build(UnlockArgs::V1 {
                 authorization_data: None,
@@ -1146,6 +1230,16 @@ impl DigitalAsset {
         false
     }
 
+    pub async fn is_programmable(&self, context: &mut ProgramTestContext) -> bool {
+        let md = self.get_metadata(context).await;
+        matches!(
+            md.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+                | Some(TokenStandard::ProgrammableFungibleAsset)
+                | Some(TokenStandard::ProgrammableNonTransferable)
+        )
+    }
+
     pub async fn inject_close_authority(
         &self,
         context: &mut ProgramTestContext,
@@ -1203,7 +1297,7 @@ impl DigitalAsset {
             TokenStandard::NonFungible => {
                 self.non_fungigble_accounts_closed(context).await?;
             }
-            TokenStandard::ProgrammableNonFungible => {
+            TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonTransferable => {
                 self.programmable_non_fungigble_accounts_closed(context)
                     .await?;
             }
@@ -212,3 +212,51 @@ pub async fn create_default_metaplex_rule_set(
 
     (ruleset_addr, auth_data)
 }
+
+pub async fn create_rule_set(
+    context: &mut ProgramTestContext,
+    creator: Keypair,
+    name: String,
+    rules: Vec<(Operation, Rule)>,
+) -> Pubkey {
+    let (ruleset_addr, _ruleset_bump) =
+        mpl_token_auth_rules::pda::find_rule_set_address(creator.pubkey(), name.clone());
+
+    let mut rule_set = RuleSetV1::new(name, creator.pubkey());
+
+    for (operation, rule) in rules {
+        rule_set.add(operation.to_string(), rule).unwrap();
+    }
+
+    // Serialize the RuleSet using RMP serde.
+    let mut serialized_data = Vec::new();
+    rule_set
+        .serialize(&mut Serializer::new(&mut serialized_data))
+        .unwrap();
+
+    let create_ix = CreateOrUpdateBuilder::new()
+        .rule_set_pda(ruleset_addr)
+        .payer(creator.pubkey())
+        .build(CreateOrUpdateArgs::V1 {
+            serialized_rule_set: serialized_data,
+        })
+        .unwrap()
+        .instruction();
+
+    let compute_ix = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+
+    let create_tx = Transaction::new_signed_with_payer(
+        &[compute_ix, create_ix],
+        Some(&creator.pubkey()),
+        &[&creator],
+        context.last_blockhash,
+    );
+
+    context
+        .banks_client
+        .process_transaction(create_tx)
+        .await
+        .expect("creation should succeed");
+
+    ruleset_addr
+}
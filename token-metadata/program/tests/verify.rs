@@ -9,7 +9,7 @@ use mpl_token_metadata::{
         VerificationArgs,
     },
     pda::{find_metadata_delegate_record_account, find_token_record_account},
-    state::{Collection, CollectionDetails, Creator, TokenStandard},
+    state::{Collection, CollectionDetails, Creator, PrintSupply, TokenStandard},
 };
 use num_traits::FromPrimitive;
 use solana_program::native_token::LAMPORTS_PER_SOL;
@@ -2517,4 +2517,155 @@ mod verify_collection {
             .assert_collection_details_matches_on_chain(&mut context, &DEFAULT_COLLECTION_DETAILS)
             .await;
     }
+
+    #[tokio::test]
+    async fn pass_collection_item_propagates_size_to_parent_collections() {
+        let mut context = program_test().start_with_context().await;
+
+        // Create the top-level sized collection.
+        let mut season_da = DigitalAsset::new();
+        season_da
+            .create_and_mint_collection_parent(
+                &mut context,
+                TokenStandard::NonFungible,
+                None,
+                None,
+                1,
+                DEFAULT_COLLECTION_DETAILS,
+            )
+            .await
+            .unwrap();
+
+        // Create a sized collection nested in the top-level collection.
+        let mut drop_da = DigitalAsset::new();
+        drop_da
+            .create_advanced(
+                &mut context,
+                TokenStandard::NonFungible,
+                String::from(DEFAULT_NAME),
+                String::from(DEFAULT_SYMBOL),
+                String::from(DEFAULT_URI),
+                500,
+                None,
+                Some(Collection {
+                    key: season_da.mint.pubkey(),
+                    verified: false,
+                }),
+                DEFAULT_COLLECTION_DETAILS,
+                None,
+                PrintSupply::Zero,
+            )
+            .await
+            .unwrap();
+        drop_da.mint(&mut context, None, None, 1).await.unwrap();
+
+        let payer = context.payer.dirty_clone();
+        drop_da
+            .verify(
+                &mut context,
+                payer,
+                VerificationArgs::CollectionV1,
+                None,
+                None,
+                Some(season_da.mint.pubkey()),
+                Some(season_da.metadata),
+                Some(season_da.edition.unwrap()),
+            )
+            .await
+            .unwrap();
+
+        season_da
+            .assert_collection_details_matches_on_chain(
+                &mut context,
+                &Some(CollectionDetails::V1 { size: 1 }),
+            )
+            .await;
+
+        // Create and mint item in the nested collection.
+        let mut da = DigitalAsset::new();
+        da.create_and_mint_item_with_collection(
+            &mut context,
+            TokenStandard::NonFungible,
+            None,
+            None,
+            1,
+            Some(Collection {
+                key: drop_da.mint.pubkey(),
+                verified: false,
+            }),
+        )
+        .await
+        .unwrap();
+
+        let mut builder = VerifyBuilder::new();
+        builder
+            .authority(context.payer.pubkey())
+            .metadata(da.metadata)
+            .collection_mint(drop_da.mint.pubkey())
+            .collection_metadata(drop_da.metadata)
+            .collection_master_edition(drop_da.edition.unwrap());
+
+        // The parent collection metadata must match the parent of the collection.
+        let verify_ix = builder
+            .parent_collection(da.metadata)
+            .build(VerificationArgs::CollectionV1)
+            .unwrap()
+            .instruction();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidCollectionAncestor);
+
+        // Verify with the top-level collection as parent collection.
+        let verify_ix = builder
+            .parent_collection(season_da.metadata)
+            .build(VerificationArgs::CollectionV1)
+            .unwrap()
+            .instruction();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        da.assert_item_collection_matches_on_chain(
+            &mut context,
+            &Some(Collection {
+                key: drop_da.mint.pubkey(),
+                verified: true,
+            }),
+        )
+        .await;
+
+        // Both collections account for the new item.
+        drop_da
+            .assert_collection_details_matches_on_chain(
+                &mut context,
+                &Some(CollectionDetails::V1 { size: 1 }),
+            )
+            .await;
+        season_da
+            .assert_collection_details_matches_on_chain(
+                &mut context,
+                &Some(CollectionDetails::V1 { size: 2 }),
+            )
+            .await;
+    }
 }